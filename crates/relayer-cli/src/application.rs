@@ -11,6 +11,7 @@ use abscissa_core::{
     Application, Configurable, FrameworkError, FrameworkErrorKind, StandardPaths,
 };
 use ibc_relayer::{config::Config, util::debug_section::DebugSection};
+use ibc_relayer_types::utils::redact::set_packet_data_redaction;
 
 use crate::{
     components::{JsonTracing, PrettyTracing},
@@ -148,6 +149,8 @@ impl Application for CliApp {
 
         tracing::info!("running Hermes v{}", clap::crate_version!());
 
+        set_packet_data_redaction(config.global.redact_packet_data);
+
         self.config.set_once(config);
 
         Ok(())
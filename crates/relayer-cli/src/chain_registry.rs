@@ -19,7 +19,7 @@ use ibc_chain_registry::querier::*;
 use ibc_relayer::config::filter::{FilterPattern, PacketFilter};
 use ibc_relayer::config::gas_multiplier::GasMultiplier;
 use ibc_relayer::config::types::{MaxMsgNum, MaxTxSize, Memo};
-use ibc_relayer::config::{default, AddressType, ChainConfig, GasPrice};
+use ibc_relayer::config::{default, AddressType, ChainConfig, EventSourceMode, GasPrice};
 use ibc_relayer::keyring::Store;
 
 use tendermint_light_client_verifier::types::TrustThreshold;
@@ -120,8 +120,12 @@ where
         grpc_addr: grpc_address,
         rpc_timeout: default::rpc_timeout(),
         batch_delay: default::batch_delay(),
+        event_rpc_fallback: default::event_rpc_fallback(),
+        event_source: EventSourceMode::default(),
+        max_block_lag: None,
         trusted_node: default::trusted_node(),
         genesis_restart: None,
+        evidence_dir: None,
         account_prefix: chain_data.bech32_prefix,
         key_name: String::new(),
         key_store_type: Store::default(),
@@ -134,7 +138,11 @@ where
         fee_granter: None,
         max_msg_num: MaxMsgNum::default(),
         max_tx_size: MaxTxSize::default(),
+        tx_rate_limit: None,
+        gas_multiplier_tuning: None,
         max_grpc_decoding_size: default::max_grpc_decoding_size(),
+        max_ws_message_size: default::max_ws_message_size(),
+        max_ws_frame_size: default::max_ws_frame_size(),
         clock_drift: default::clock_drift(),
         max_block_time: default::max_block_time(),
         trusting_period: None,
@@ -150,6 +158,7 @@ where
         address_type: AddressType::default(),
         sequential_batch_tx: false,
         extension_options: Vec::new(),
+        denom_metadata: Vec::new(),
     })
 }
 
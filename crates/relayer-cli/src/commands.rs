@@ -8,19 +8,21 @@ mod fee;
 mod health;
 mod keys;
 mod listen;
+mod migrate;
 mod misbehaviour;
 mod query;
 mod start;
 mod tx;
 mod update;
 mod upgrade;
+mod verify;
 mod version;
 
 use self::{
     clear::ClearCmds, completions::CompletionsCmd, config::ConfigCmd, create::CreateCmds,
-    fee::FeeCmd, health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd,
+    fee::FeeCmd, health::HealthCheckCmd, keys::KeysCmd, listen::ListenCmd, migrate::MigrateCmds,
     misbehaviour::MisbehaviourCmd, query::QueryCmd, start::StartCmd, tx::TxCmd, update::UpdateCmds,
-    upgrade::UpgradeCmds, version::VersionCmd,
+    upgrade::UpgradeCmds, verify::VerifyCmds, version::VersionCmd,
 };
 
 use core::time::Duration;
@@ -61,6 +63,14 @@ pub enum CliCmd {
     #[clap(subcommand)]
     Upgrade(UpgradeCmds),
 
+    /// Verify objects (clients) against the chains they track
+    #[clap(subcommand)]
+    Verify(VerifyCmds),
+
+    /// Migrate objects (paths) to new channels
+    #[clap(subcommand)]
+    Migrate(MigrateCmds),
+
     /// Clear objects, such as outstanding packets on a channel.
     #[clap(subcommand)]
     Clear(ClearCmds),
@@ -103,19 +103,17 @@ pub struct FeeTransferCmd {
 
     #[clap(
         long = "timeout-height-offset",
-        default_value = "0",
         value_name = "TIMEOUT_HEIGHT_OFFSET",
-        help = "Timeout in number of blocks since current. Default: 0"
+        help = "Timeout in number of blocks since current, 0 for none. If neither this nor the timeout in seconds is given, the packet expires after 10 minutes, or the number of blocks the destination chain produces in 10 minutes"
     )]
-    timeout_height_offset: u64,
+    timeout_height_offset: Option<u64>,
 
     #[clap(
         long = "timeout-seconds",
-        default_value = "0",
         value_name = "TIMEOUT_SECONDS",
-        help = "Timeout in seconds since current. Default: 0"
+        help = "Timeout in seconds since current, 0 for none"
     )]
-    timeout_seconds: u64,
+    timeout_seconds: Option<u64>,
 
     #[clap(
         long = "receive-fee",
@@ -198,7 +196,7 @@ impl FeeTransferCmd {
             denom,
             receiver: self.recipient.clone(),
             timeout_height_offset: self.timeout_height_offset,
-            timeout_duration: Duration::from_secs(self.timeout_seconds),
+            timeout_duration: self.timeout_seconds.map(Duration::from_secs),
             number_msgs,
             receive_fee: self.receive_fee,
             ack_fee: self.ack_fee,
@@ -235,8 +233,8 @@ pub struct FeeTransferOptions {
     pub amount: Amount,
     pub denom: String,
     pub receiver: Option<String>,
-    pub timeout_height_offset: u64,
-    pub timeout_duration: Duration,
+    pub timeout_height_offset: Option<u64>,
+    pub timeout_duration: Option<Duration>,
     pub number_msgs: usize,
     pub receive_fee: Amount,
     pub ack_fee: Amount,
@@ -328,8 +326,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -364,8 +362,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -400,8 +398,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -438,8 +436,8 @@ mod tests {
                 recipient: Some("other_recipient".to_owned()),
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -476,8 +474,8 @@ mod tests {
                 recipient: None,
                 number_msgs: Some(10),
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -514,8 +512,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: Some("other_wallet".to_owned()),
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -552,8 +550,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 42,
-                timeout_seconds: 0,
+                timeout_height_offset: Some(42),
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -590,8 +588,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 21,
+                timeout_height_offset: None,
+                timeout_seconds: Some(21),
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -628,8 +626,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(51u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
@@ -665,8 +663,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(52u64),
                 timeout_fee: Amount::from(0u64),
@@ -703,8 +701,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(53u64),
@@ -741,8 +739,8 @@ mod tests {
                 recipient: None,
                 number_msgs: None,
                 key_name: None,
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receive_fee: Amount::from(0u64),
                 ack_fee: Amount::from(0u64),
                 timeout_fee: Amount::from(0u64),
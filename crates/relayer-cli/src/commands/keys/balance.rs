@@ -72,15 +72,15 @@ fn get_balance(chain: impl ChainHandle, key_name: Option<String>, denom: Option<
     match chain.query_balance(key_name.clone(), denom) {
         Ok(balance) if json() => Output::success(balance).exit(),
         Ok(balance) => {
+            let chain_config = chain.config().unwrap_or_else(exit_with_unrecoverable_error);
+
             // Retrieve the key name string to output.
-            let key_name = key_name.unwrap_or_else(|| {
-                let chain_config = chain.config().unwrap_or_else(exit_with_unrecoverable_error);
-                chain_config.key_name
-            });
+            let key_name = key_name.unwrap_or_else(|| chain_config.key_name.clone());
 
             Output::success_msg(format!(
-                "balance for key `{}`: {} {}",
-                key_name, balance.amount, balance.denom
+                "balance for key `{}`: {}",
+                key_name,
+                chain_config.format_amount(&balance.amount, &balance.denom)
             ))
             .exit()
         }
@@ -92,16 +92,19 @@ fn get_balances(chain: impl ChainHandle, key_name: Option<String>) {
     match chain.query_all_balances(key_name.clone()) {
         Ok(balances) if json() => Output::success(balances).exit(),
         Ok(balances) => {
+            let chain_config = chain.config().unwrap_or_else(exit_with_unrecoverable_error);
+
             // Retrieve the key name string to output.
-            let key_name = key_name.unwrap_or_else(|| {
-                let chain_config = chain.config().unwrap_or_else(exit_with_unrecoverable_error);
-                chain_config.key_name
-            });
+            let key_name = key_name.unwrap_or_else(|| chain_config.key_name.clone());
 
             let mut pretty_output = format!("Balances for key `{key_name}`:");
             for balance in balances {
-                write!(pretty_output, "\n\t{} {}", balance.amount, balance.denom)
-                    .unwrap_or_else(exit_with_unrecoverable_error);
+                write!(
+                    pretty_output,
+                    "\n\t{}",
+                    chain_config.format_amount(&balance.amount, &balance.denom)
+                )
+                .unwrap_or_else(exit_with_unrecoverable_error);
             }
 
             Output::success_msg(pretty_output).exit()
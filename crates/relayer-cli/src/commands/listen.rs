@@ -140,21 +140,14 @@ fn subscribe(
     compat_mode: CompatMode,
     rt: Arc<TokioRuntime>,
 ) -> eyre::Result<Subscription> {
-    let (mut event_monitor, tx_cmd) = EventMonitor::new(
-        chain_config.id.clone(),
-        chain_config.websocket_addr.clone(),
-        compat_mode,
-        chain_config.batch_delay,
-        rt,
-    )
-    .map_err(|e| eyre!("could not initialize event monitor: {}", e))?;
+    let (mut event_monitor, tx_cmd) = EventMonitor::from_config(chain_config, compat_mode, rt)
+        .map_err(|e| eyre!("could not initialize event monitor: {}", e))?;
 
     event_monitor
         .init_subscriptions()
         .map_err(|e| eyre!("could not initialize subscriptions: {}", e))?;
 
-    let queries = event_monitor.queries();
-    info!("listening for queries: {}", queries.iter().format(", "),);
+    info!("listening for events from {}", event_monitor.description());
 
     thread::spawn(|| event_monitor.run());
 
@@ -0,0 +1,183 @@
+//! `migrate` subcommand
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::handle::BaseChainHandle;
+use ibc_relayer::migrate_path::{close_old_channel, migrate_path_report};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::cli_utils::spawn_chain_counterparty;
+use crate::conclude::Output;
+use crate::error::Error;
+use crate::prelude::*;
+
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum MigrateCmds {
+    /// Report what must be cleared on a channel before abandoning it for a new channel
+    Path(MigratePathCmd),
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct MigratePathCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain on which the channels are"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the port of the channels"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        visible_alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel to abandon"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "new-channel",
+        required = true,
+        value_name = "NEW_CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel replacing it"
+    )]
+    new_channel_id: ChannelId,
+
+    #[clap(
+        long = "close",
+        help = "Initiate the closing handshake of the old channel if no packets are in flight on it and no tokens are escrowed"
+    )]
+    close: bool,
+}
+
+impl Runnable for MigratePathCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let (chains, chan_conn_cli) = match spawn_chain_counterparty::<BaseChainHandle>(
+            &config,
+            &self.chain_id,
+            &self.port_id,
+            &self.channel_id,
+        ) {
+            Ok(res) => res,
+            Err(e) => Output::error(e).exit(),
+        };
+
+        let report = match migrate_path_report(
+            &chains.src,
+            &chains.dst,
+            &chan_conn_cli.channel,
+            &chan_conn_cli.connection,
+            &self.new_channel_id,
+        ) {
+            Ok(report) => report,
+            Err(e) => Output::error(Error::migrate_path(e)).exit(),
+        };
+
+        for action in &report.actions {
+            info!("{}", action);
+        }
+
+        if !self.close {
+            Output::success(report).exit();
+        }
+
+        match close_old_channel(
+            chains.src,
+            chains.dst,
+            &chan_conn_cli.channel,
+            &chan_conn_cli.connection,
+            &report,
+        ) {
+            Ok(event) => Output::success(event).exit(),
+            Err(e) => Output::error(Error::migrate_path(e)).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MigratePathCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_migrate_path_required_only() {
+        assert_eq!(
+            MigratePathCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("transfer").unwrap(),
+                channel_id: ChannelId::from_str("channel-0").unwrap(),
+                new_channel_id: ChannelId::from_str("channel-5").unwrap(),
+                close: false,
+            },
+            MigratePathCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "transfer",
+                "--channel",
+                "channel-0",
+                "--new-channel",
+                "channel-5"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_migrate_path_close() {
+        assert_eq!(
+            MigratePathCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("transfer").unwrap(),
+                channel_id: ChannelId::from_str("channel-0").unwrap(),
+                new_channel_id: ChannelId::from_str("channel-5").unwrap(),
+                close: true,
+            },
+            MigratePathCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "transfer",
+                "--chan",
+                "channel-0",
+                "--new-channel",
+                "channel-5",
+                "--close"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_migrate_path_no_new_channel() {
+        assert!(MigratePathCmd::try_parse_from([
+            "test",
+            "--chain",
+            "chain_id",
+            "--port",
+            "transfer",
+            "--channel",
+            "channel-0"
+        ])
+        .is_err())
+    }
+}
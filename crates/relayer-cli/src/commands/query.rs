@@ -16,7 +16,10 @@ mod client;
 mod clients;
 mod connection;
 mod connections;
+mod dump;
+mod fee;
 mod packet;
+mod rpc_stats;
 mod transfer;
 mod tx;
 
@@ -55,6 +58,16 @@ pub enum QueryCmd {
     /// Query information about token transfers
     #[clap(subcommand)]
     Transfer(transfer::TransferCmd),
+
+    /// Query information about fees escrowed and payees registered with the fee middleware
+    #[clap(subcommand)]
+    Fee(fee::QueryFeeCmd),
+
+    /// Dump the IBC state of a chain (clients, connections, channels and packet commitments)
+    Dump(dump::QueryDumpCmd),
+
+    /// Query the latency and error rate of the queries submitted to the node of a chain
+    RpcStats(rpc_stats::QueryRpcStatsCmd),
 }
 
 #[derive(Command, Debug, Parser, Runnable)]
@@ -73,6 +86,9 @@ pub enum QueryClientCmds {
 
     /// Query the client connections
     Connections(client::QueryClientConnectionsCmd),
+
+    /// Query the channels built on top of the client connections
+    Channels(client::QueryClientChannelsCmd),
 }
 
 #[derive(Command, Debug, Parser, Runnable)]
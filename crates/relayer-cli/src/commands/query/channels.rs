@@ -70,6 +70,7 @@ fn run_query_channels<Chain: ChainHandle>(
 
     let identified_channels = chain.query_channels(QueryChannelsRequest {
         pagination: Some(PageRequest::all()),
+        query_height: QueryHeight::Latest,
     })?;
 
     for identified_channel in identified_channels {
@@ -2,6 +2,7 @@ use abscissa_core::clap::Parser;
 use abscissa_core::{Command, Runnable};
 use color_eyre::eyre::eyre;
 
+use ibc_relayer::chain::counterparty::client_channels;
 use ibc_relayer::chain::handle::ChainHandle;
 use ibc_relayer::chain::requests::{
     IncludeProof, PageRequest, QueryClientConnectionsRequest, QueryClientEventRequest,
@@ -12,6 +13,7 @@ use ibc_relayer::chain::requests::{
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::core::ics24_host::identifier::ClientId;
+use ibc_relayer_types::core::ics24_host::identifier::PortChannelId;
 use ibc_relayer_types::events::WithBlockDataType;
 use ibc_relayer_types::Height;
 
@@ -163,6 +165,7 @@ impl Runnable for QueryClientConsensusCmd {
             let res = chain.query_consensus_state_heights(QueryConsensusStateHeightsRequest {
                 client_id: self.client_id.clone(),
                 pagination: Some(PageRequest::all()),
+                query_height: QueryHeight::Latest,
             });
 
             match res {
@@ -322,6 +325,7 @@ fn client_status(
         chain.query_consensus_state_heights(QueryConsensusStateHeightsRequest {
             client_id: client_id.clone(),
             pagination: Some(PageRequest::all()),
+            query_height: QueryHeight::Latest,
         })?;
 
     let latest_consensus_height = consensus_state_heights.last().copied().ok_or_else(|| {
@@ -357,6 +361,52 @@ fn client_status(
     }
 }
 
+/// Query client channels command
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryClientChannelsCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to query"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "client",
+        required = true,
+        value_name = "CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the client to query"
+    )]
+    client_id: ClientId,
+}
+
+// hermes query client channels --chain ibc-0 --client 07-tendermint-0
+impl Runnable for QueryClientChannelsCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        match client_channels(&chain, &self.client_id) {
+            Ok(channels) => {
+                let ids: Vec<PortChannelId> = channels
+                    .into_iter()
+                    .map(|identified_channel| PortChannelId {
+                        port_id: identified_channel.port_id,
+                        channel_id: identified_channel.channel_id,
+                    })
+                    .collect();
+                Output::success(ids).exit()
+            }
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
 /// Query client connections command
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 pub struct QueryClientConnectionsCmd {
@@ -408,8 +458,8 @@ impl Runnable for QueryClientConnectionsCmd {
 #[cfg(test)]
 mod tests {
     use super::{
-        QueryClientConnectionsCmd, QueryClientConsensusCmd, QueryClientHeaderCmd,
-        QueryClientStateCmd, QueryClientStatusCmd,
+        QueryClientChannelsCmd, QueryClientConnectionsCmd, QueryClientConsensusCmd,
+        QueryClientHeaderCmd, QueryClientStateCmd, QueryClientStatusCmd,
     };
 
     use std::str::FromStr;
@@ -417,6 +467,28 @@ mod tests {
     use abscissa_core::clap::Parser;
     use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
 
+    #[test]
+    fn test_query_client_channels() {
+        assert_eq!(
+            QueryClientChannelsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                client_id: ClientId::from_str("client_id").unwrap(),
+            },
+            QueryClientChannelsCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--client",
+                "client_id"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_client_channels_no_client() {
+        assert!(QueryClientChannelsCmd::try_parse_from(["test", "--chain", "chain_id"]).is_err())
+    }
+
     #[test]
     fn test_query_client_connections_required_only() {
         assert_eq!(
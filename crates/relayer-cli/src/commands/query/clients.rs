@@ -3,7 +3,7 @@ use abscissa_core::{Command, Runnable};
 use ibc_relayer::chain::handle::ChainHandle;
 use serde::Serialize;
 
-use ibc_relayer::chain::requests::{PageRequest, QueryClientStatesRequest};
+use ibc_relayer::chain::requests::{PageRequest, QueryClientStatesRequest, QueryHeight};
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
 
@@ -56,6 +56,7 @@ impl Runnable for QueryAllClientsCmd {
         let res: Result<_, Error> = chain
             .query_clients(QueryClientStatesRequest {
                 pagination: Some(PageRequest::all()),
+                query_height: QueryHeight::Latest,
             })
             .map_err(Error::relayer);
 
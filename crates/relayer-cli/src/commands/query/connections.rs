@@ -47,6 +47,7 @@ impl Runnable for QueryConnectionsCmd {
 
         let res = chain.query_connections(QueryConnectionsRequest {
             pagination: Some(PageRequest::all()),
+            query_height: QueryHeight::Latest,
         });
 
         let connections = match res {
@@ -0,0 +1,244 @@
+use std::fs;
+use std::path::PathBuf;
+
+use abscissa_core::clap::Parser;
+use abscissa_core::Runnable;
+use serde::Serialize;
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::{
+    IncludeProof, PageRequest, QueryChannelsRequest, QueryClientStatesRequest,
+    QueryConnectionsRequest, QueryConsensusStateHeightsRequest, QueryConsensusStateRequest,
+    QueryHeight, QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest,
+};
+use ibc_relayer::client_state::IdentifiedAnyClientState;
+use ibc_relayer::consensus_state::AnyConsensusState;
+use ibc_relayer::error::Error as RelayerError;
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics03_connection::connection::IdentifiedConnectionEnd;
+use ibc_relayer_types::core::ics04_channel::channel::IdentifiedChannelEnd;
+use ibc_relayer_types::core::ics04_channel::packet::Sequence;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::prelude::*;
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryDumpCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to dump the IBC state of"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "output",
+        value_name = "FILE",
+        help = "Write the snapshot to the given file instead of the standard output"
+    )]
+    output: Option<PathBuf>,
+}
+
+/// A snapshot of the IBC state of a chain.
+///
+/// Every object is queried at `height`, so the snapshot is consistent even
+/// when the chain makes progress while it is taken.
+#[derive(Debug, Serialize)]
+struct ChainDump {
+    chain_id: ChainId,
+    height: Height,
+    clients: Vec<ClientDump>,
+    connections: Vec<IdentifiedConnectionEnd>,
+    channels: Vec<ChannelDump>,
+}
+
+#[derive(Debug, Serialize)]
+struct ClientDump {
+    client: IdentifiedAnyClientState,
+    consensus_state_heights: Vec<Height>,
+    /// The consensus state at the latest height of the client
+    latest_consensus_state: Option<AnyConsensusState>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChannelDump {
+    channel: IdentifiedChannelEnd,
+    packet_commitments: Vec<Sequence>,
+    packet_acknowledgements: Vec<Sequence>,
+}
+
+impl Runnable for QueryDumpCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let dump = match dump_chain(&chain) {
+            Ok(dump) => dump,
+            Err(e) => Output::error(format!(
+                "An error occurred trying to dump the IBC state of chain {}: {e}",
+                self.chain_id
+            ))
+            .exit(),
+        };
+
+        match &self.output {
+            None => Output::success(dump).exit(),
+            Some(path) => {
+                let json =
+                    serde_json::to_vec_pretty(&dump).unwrap_or_else(exit_with_unrecoverable_error);
+
+                fs::write(path, json).unwrap_or_else(exit_with_unrecoverable_error);
+
+                Output::success_msg(format!(
+                    "Wrote the IBC state of chain {} at height {} to {}",
+                    self.chain_id,
+                    dump.height,
+                    path.display()
+                ))
+                .exit()
+            }
+        }
+    }
+}
+
+fn dump_chain(chain: &impl ChainHandle) -> Result<ChainDump, RelayerError> {
+    let height = chain.query_latest_height()?;
+    let query_height = QueryHeight::Specific(height);
+
+    let clients = chain
+        .query_clients(QueryClientStatesRequest {
+            pagination: Some(PageRequest::all()),
+            query_height,
+        })?
+        .into_iter()
+        .map(|client| dump_client(chain, client, query_height))
+        .collect::<Result<_, _>>()?;
+
+    let connections = chain.query_connections(QueryConnectionsRequest {
+        pagination: Some(PageRequest::all()),
+        query_height,
+    })?;
+
+    let channels = chain
+        .query_channels(QueryChannelsRequest {
+            pagination: Some(PageRequest::all()),
+            query_height,
+        })?
+        .into_iter()
+        .map(|channel| dump_channel(chain, channel, query_height))
+        .collect::<Result<_, _>>()?;
+
+    Ok(ChainDump {
+        chain_id: chain.id(),
+        height,
+        clients,
+        connections,
+        channels,
+    })
+}
+
+fn dump_client(
+    chain: &impl ChainHandle,
+    client: IdentifiedAnyClientState,
+    query_height: QueryHeight,
+) -> Result<ClientDump, RelayerError> {
+    let consensus_state_heights =
+        chain.query_consensus_state_heights(QueryConsensusStateHeightsRequest {
+            client_id: client.client_id.clone(),
+            pagination: Some(PageRequest::all()),
+            query_height,
+        })?;
+
+    // The consensus state may have been pruned, in which case it is left out
+    let latest_consensus_state = chain
+        .query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: client.client_id.clone(),
+                consensus_height: client.client_state.latest_height(),
+                query_height,
+            },
+            IncludeProof::No,
+        )
+        .map(|(consensus_state, _)| consensus_state)
+        .ok();
+
+    Ok(ClientDump {
+        client,
+        consensus_state_heights,
+        latest_consensus_state,
+    })
+}
+
+fn dump_channel(
+    chain: &impl ChainHandle,
+    channel: IdentifiedChannelEnd,
+    query_height: QueryHeight,
+) -> Result<ChannelDump, RelayerError> {
+    let (packet_commitments, _) =
+        chain.query_packet_commitments(QueryPacketCommitmentsRequest {
+            port_id: channel.port_id.clone(),
+            channel_id: channel.channel_id.clone(),
+            pagination: Some(PageRequest::all()),
+            query_height,
+        })?;
+
+    // Without any commitment sequence to filter on, every acknowledgement is listed
+    let (packet_acknowledgements, _) =
+        chain.query_packet_acknowledgements(QueryPacketAcknowledgementsRequest {
+            port_id: channel.port_id.clone(),
+            channel_id: channel.channel_id.clone(),
+            pagination: Some(PageRequest::all()),
+            query_height,
+            packet_commitment_sequences: Vec::new(),
+        })?;
+
+    Ok(ChannelDump {
+        channel,
+        packet_commitments,
+        packet_acknowledgements,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryDumpCmd;
+
+    use std::path::PathBuf;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_query_dump_required_only() {
+        assert_eq!(
+            QueryDumpCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                output: None,
+            },
+            QueryDumpCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_query_dump_output() {
+        assert_eq!(
+            QueryDumpCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                output: Some(PathBuf::from("dump.json")),
+            },
+            QueryDumpCmd::parse_from(["test", "--chain", "chain_id", "--output", "dump.json"])
+        )
+    }
+
+    #[test]
+    fn test_query_dump_no_chain() {
+        assert!(QueryDumpCmd::try_parse_from(["test"]).is_err())
+    }
+}
@@ -0,0 +1,17 @@
+//! `query fee` subcommand
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+mod counterparty_payee;
+mod packets;
+
+/// `query fee` subcommand
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum QueryFeeCmd {
+    /// Query the packets on a channel which have fees escrowed for them
+    Packets(packets::QueryIncentivizedPacketsCmd),
+
+    /// Query the counterparty payee registered by a relayer on a channel
+    CounterpartyPayee(counterparty_payee::QueryCounterpartyPayeeCmd),
+}
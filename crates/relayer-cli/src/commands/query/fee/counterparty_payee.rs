@@ -0,0 +1,123 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId};
+use ibc_relayer_types::signer::Signer;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+
+/// The data structure that represents the arguments when invoking the `query fee counterparty-payee` CLI command.
+///
+/// The command has the following format:
+///
+/// `query fee counterparty-payee --chain <CHAIN_ID> --channel <CHANNEL_ID> [--address <RELAYER_ADDRESS>]`
+///
+/// If no address is given, the address of the relayer key configured for the chain is used.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryCounterpartyPayeeCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to query"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "channel",
+        alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel to query"
+    )]
+    channel_id: ChannelId,
+
+    #[clap(
+        long = "address",
+        value_name = "RELAYER_ADDRESS",
+        help = "Address of the relayer on the chain. Leave unspecified to use the address of the configured relayer key."
+    )]
+    address: Option<Signer>,
+}
+
+impl Runnable for QueryCounterpartyPayeeCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let address = match &self.address {
+            Some(address) => address.clone(),
+            None => chain
+                .get_signer()
+                .unwrap_or_else(exit_with_unrecoverable_error),
+        };
+
+        match chain.query_counterparty_payee(self.channel_id.clone(), address.clone()) {
+            Ok(Some(payee)) => Output::success(payee).exit(),
+            Ok(None) => Output::error(format!(
+                "no counterparty payee registered for relayer {} on channel {}",
+                address, self.channel_id
+            ))
+            .exit(),
+            Err(e) => Output::error(format!(
+                "there was a problem querying the counterparty payee: {e}"
+            ))
+            .exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryCounterpartyPayeeCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId};
+
+    #[test]
+    fn test_query_counterparty_payee() {
+        assert_eq!(
+            QueryCounterpartyPayeeCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                address: None,
+            },
+            QueryCounterpartyPayeeCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--channel",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_counterparty_payee_address() {
+        assert_eq!(
+            QueryCounterpartyPayeeCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+                address: Some("cosmos1relayer".parse().unwrap()),
+            },
+            QueryCounterpartyPayeeCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--channel",
+                "channel-07",
+                "--address",
+                "cosmos1relayer"
+            ])
+        )
+    }
+}
@@ -0,0 +1,102 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+
+/// The data structure that represents the arguments when invoking the `query fee packets` CLI command.
+///
+/// The command has the following format:
+///
+/// `query fee packets --chain <CHAIN_ID> --port <PORT_ID> --channel <CHANNEL_ID>`
+///
+/// If successful the packets with escrowed fees, along with their fees, will be displayed.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryIncentivizedPacketsCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to query"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "port",
+        required = true,
+        value_name = "PORT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the port to query"
+    )]
+    port_id: PortId,
+
+    #[clap(
+        long = "channel",
+        alias = "chan",
+        required = true,
+        value_name = "CHANNEL_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the channel to query"
+    )]
+    channel_id: ChannelId,
+}
+
+impl Runnable for QueryIncentivizedPacketsCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        match chain.query_incentivized_packets(self.channel_id.clone(), self.port_id.clone()) {
+            Ok(packets) => Output::success(packets).exit(),
+            Err(e) => Output::error(format!(
+                "there was a problem querying the incentivized packets: {e}"
+            ))
+            .exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryIncentivizedPacketsCmd;
+
+    use std::str::FromStr;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    #[test]
+    fn test_query_fee_packets() {
+        assert_eq!(
+            QueryIncentivizedPacketsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                port_id: PortId::from_str("port_id").unwrap(),
+                channel_id: ChannelId::from_str("channel-07").unwrap(),
+            },
+            QueryIncentivizedPacketsCmd::parse_from([
+                "test",
+                "--chain",
+                "chain_id",
+                "--port",
+                "port_id",
+                "--channel",
+                "channel-07"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_query_fee_packets_no_channel() {
+        assert!(QueryIncentivizedPacketsCmd::try_parse_from([
+            "test", "--chain", "chain_id", "--port", "port_id"
+        ])
+        .is_err())
+    }
+}
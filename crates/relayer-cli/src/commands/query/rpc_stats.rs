@@ -0,0 +1,89 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::application::app_config;
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+
+/// Query the node of a chain a number of times, and report the latency
+/// and error rate of each query type.
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct QueryRpcStatsCmd {
+    #[clap(
+        long = "chain",
+        required = true,
+        value_name = "CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain to query"
+    )]
+    chain_id: ChainId,
+
+    #[clap(
+        long = "samples",
+        value_name = "SAMPLES",
+        default_value = "10",
+        help = "Number of times each query is submitted"
+    )]
+    samples: u32,
+}
+
+// hermes query rpc-stats --chain ibc-0
+impl Runnable for QueryRpcStatsCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let chain = spawn_chain_runtime(&config, &self.chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        // Failures are part of the statistics, so they are not reported here
+        for _ in 0..self.samples {
+            // Over the RPC endpoint
+            let _ = chain.query_application_status();
+            // Over the gRPC endpoint
+            let _ = chain.query_staking_params();
+        }
+
+        match chain.query_rpc_stats() {
+            Ok(stats) => Output::success(stats).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::QueryRpcStatsCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+    #[test]
+    fn test_query_rpc_stats() {
+        assert_eq!(
+            QueryRpcStatsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                samples: 10
+            },
+            QueryRpcStatsCmd::parse_from(["test", "--chain", "chain_id"])
+        )
+    }
+
+    #[test]
+    fn test_query_rpc_stats_samples() {
+        assert_eq!(
+            QueryRpcStatsCmd {
+                chain_id: ChainId::from_string("chain_id"),
+                samples: 50
+            },
+            QueryRpcStatsCmd::parse_from(["test", "--chain", "chain_id", "--samples", "50"])
+        )
+    }
+
+    #[test]
+    fn test_query_rpc_stats_no_chain() {
+        assert!(QueryRpcStatsCmd::try_parse_from(["test"]).is_err())
+    }
+}
@@ -14,7 +14,9 @@ use crate::conclude::{exit_with_unrecoverable_error, json, Output};
 ///
 /// `query transfer denom-trace --chain <CHAIN_ID> --hash <HASH>`
 ///
-/// If successful the the base denomination and the path will be displayed.
+/// The hash may be given with or without the `ibc/` prefix of the voucher denomination.
+/// If successful the the base denomination and the path will be displayed, once
+/// checked that they hash to the queried hash.
 #[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
 pub struct DenomTraceCmd {
     #[clap(
@@ -29,7 +31,7 @@ pub struct DenomTraceCmd {
         long = "hash",
         required = true,
         help_heading = "REQUIRED",
-        help = "Trace hash to query"
+        help = "Trace hash to query, eg. the hash of the ibc/<HASH> denomination of a voucher"
     )]
     hash: String,
 }
@@ -41,17 +43,36 @@ impl Runnable for DenomTraceCmd {
         let chain = spawn_chain_runtime(&config, &self.chain_id)
             .unwrap_or_else(exit_with_unrecoverable_error);
 
-        match chain.query_denom_trace(self.hash.clone()) {
-            Ok(denom_trace) if json() => Output::success(denom_trace).exit(),
-            Ok(denom_trace) => Output::success_msg(format!(
-                "base_denom: {}\n path: {}",
-                denom_trace.base_denom, denom_trace.path
-            ))
-            .exit(),
+        let hash = self.hash.strip_prefix("ibc/").unwrap_or(&self.hash);
+
+        let denom_trace = match chain.query_denom_trace(hash.to_string()) {
+            Ok(denom_trace) => denom_trace,
             Err(e) => Output::error(format!(
                 "there was a problem querying the denomination trace: {e}"
             ))
             .exit(),
+        };
+
+        if !denom_trace.hash().eq_ignore_ascii_case(hash) {
+            Output::error(format!(
+                "the denomination trace returned by the chain, {}, hashes to {} instead of {}",
+                denom_trace.full_denom(),
+                denom_trace.hash(),
+                hash
+            ))
+            .exit()
+        }
+
+        if json() {
+            Output::success(denom_trace).exit()
+        } else {
+            Output::success_msg(format!(
+                "base_denom: {}\n path: {}\n full_denom: {}",
+                denom_trace.base_denom,
+                denom_trace.path,
+                denom_trace.full_denom()
+            ))
+            .exit()
         }
     }
 }
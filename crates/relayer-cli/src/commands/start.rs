@@ -24,6 +24,12 @@ pub struct StartCmd {
         help = "Force a full scan of the chains for clients, connections and channels"
     )]
     full_scan: bool,
+
+    #[clap(
+        long = "profile-memory",
+        help = "Periodically report the amount of data held in memory by the packet workers"
+    )]
+    profile_memory: bool,
 }
 
 impl Runnable for StartCmd {
@@ -59,6 +65,7 @@ impl Runnable for StartCmd {
         let options = SupervisorOptions {
             force_full_scan: self.full_scan,
             health_check: true,
+            profile_memory: self.profile_memory,
         };
 
         let supervisor_handle = make_supervisor::<CachingChainHandle>(config, options)
@@ -79,7 +86,11 @@ impl Runnable for StartCmd {
 
         info!("Hermes has started");
 
-        supervisor_handle.wait();
+        // Rather than keep running without relaying, exit so that Hermes can be restarted
+        supervisor_handle.wait_until_failed();
+
+        Output::error("a task of the supervisor stopped unexpectedly, Hermes is no longer relaying")
+            .exit()
     }
 }
 
@@ -249,7 +260,10 @@ mod tests {
     #[test]
     fn test_start_required_only() {
         assert_eq!(
-            StartCmd { full_scan: false },
+            StartCmd {
+                full_scan: false,
+                profile_memory: false
+            },
             StartCmd::parse_from(["test"])
         )
     }
@@ -257,8 +271,22 @@ mod tests {
     #[test]
     fn test_start_full_scan() {
         assert_eq!(
-            StartCmd { full_scan: true },
+            StartCmd {
+                full_scan: true,
+                profile_memory: false
+            },
             StartCmd::parse_from(["test", "--full-scan"])
         )
     }
+
+    #[test]
+    fn test_start_profile_memory() {
+        assert_eq!(
+            StartCmd {
+                full_scan: false,
+                profile_memory: true
+            },
+            StartCmd::parse_from(["test", "--profile-memory"])
+        )
+    }
 }
@@ -7,6 +7,7 @@ mod channel;
 pub(crate) mod client;
 mod connection;
 mod packet;
+mod recover;
 mod transfer;
 mod upgrade;
 
@@ -55,6 +56,9 @@ pub enum TxCmd {
 
     /// Send an IBC upgrade plan
     UpgradeChain(upgrade::TxIbcUpgradeChainCmd),
+
+    /// Submit a governance proposal to recover an expired or frozen client
+    RecoverClient(recover::TxRecoverClientCmd),
 }
 
 impl Override<Config> for TxCmd {
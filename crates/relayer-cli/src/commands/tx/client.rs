@@ -469,6 +469,7 @@ impl TxUpgradeClientsCmd {
 
         let req = QueryClientStatesRequest {
             pagination: Some(PageRequest::all()),
+            query_height: QueryHeight::Latest,
         };
         let outputs = host_chain
             .query_clients(req)
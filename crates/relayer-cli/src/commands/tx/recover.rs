@@ -0,0 +1,162 @@
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::recover_client::{build_and_send_recover_client_proposal, RecoverClientOptions};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct TxRecoverClientCmd {
+    #[clap(
+        long = "host-chain",
+        required = true,
+        value_name = "HOST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain hosting both the subject and substitute clients"
+    )]
+    host_chain_id: ChainId,
+
+    #[clap(
+        long = "subject-client",
+        required = true,
+        value_name = "SUBJECT_CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the expired or frozen client to recover"
+    )]
+    subject_client_id: ClientId,
+
+    #[clap(
+        long = "substitute-client",
+        required = true,
+        value_name = "SUBSTITUTE_CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the healthy client substituting the subject client"
+    )]
+    substitute_client_id: ClientId,
+
+    #[clap(
+        long = "amount",
+        required = true,
+        value_name = "AMOUNT",
+        help_heading = "REQUIRED",
+        help = "Amount of stake to deposit with the proposal"
+    )]
+    amount: u64,
+
+    #[clap(
+        long = "denom",
+        value_name = "DENOM",
+        help = "Denomination for the deposit (default: 'stake')"
+    )]
+    denom: Option<String>,
+}
+
+impl TxRecoverClientCmd {
+    fn options(&self) -> RecoverClientOptions {
+        RecoverClientOptions {
+            subject_client_id: self.subject_client_id.clone(),
+            substitute_client_id: self.substitute_client_id.clone(),
+            amount: self.amount,
+            denom: self.denom.as_deref().unwrap_or("stake").into(),
+            title: format!("recover client {}", self.subject_client_id),
+            description: format!(
+                "substitute the state of client {} with the state of client {}",
+                self.subject_client_id, self.substitute_client_id
+            ),
+        }
+    }
+}
+
+impl Runnable for TxRecoverClientCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let host_chain = spawn_chain_runtime(&config, &self.host_chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let res = build_and_send_recover_client_proposal(host_chain, &self.options())
+            .map_err(Error::recover_client);
+
+        match res {
+            Ok(tx_hash) => Output::success(tx_hash).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TxRecoverClientCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_recover_client_required_only() {
+        assert_eq!(
+            TxRecoverClientCmd {
+                host_chain_id: ChainId::from_string("chain_host"),
+                subject_client_id: ClientId::from_str("client_subject").unwrap(),
+                substitute_client_id: ClientId::from_str("client_substitute").unwrap(),
+                amount: 42,
+                denom: None
+            },
+            TxRecoverClientCmd::parse_from([
+                "test",
+                "--host-chain",
+                "chain_host",
+                "--subject-client",
+                "client_subject",
+                "--substitute-client",
+                "client_substitute",
+                "--amount",
+                "42"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_recover_client_denom() {
+        assert_eq!(
+            TxRecoverClientCmd {
+                host_chain_id: ChainId::from_string("chain_host"),
+                subject_client_id: ClientId::from_str("client_subject").unwrap(),
+                substitute_client_id: ClientId::from_str("client_substitute").unwrap(),
+                amount: 42,
+                denom: Some("my_denom".to_owned())
+            },
+            TxRecoverClientCmd::parse_from([
+                "test",
+                "--host-chain",
+                "chain_host",
+                "--subject-client",
+                "client_subject",
+                "--substitute-client",
+                "client_substitute",
+                "--amount",
+                "42",
+                "--denom",
+                "my_denom"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_recover_client_no_substitute() {
+        assert!(TxRecoverClientCmd::try_parse_from([
+            "test",
+            "--host-chain",
+            "chain_host",
+            "--subject-client",
+            "client_subject",
+            "--amount",
+            "42"
+        ])
+        .is_err())
+    }
+}
@@ -11,7 +11,7 @@ use ibc_relayer::{
     transfer::{build_and_send_transfer_messages, TransferOptions},
 };
 use ibc_relayer_types::{
-    applications::transfer::Amount,
+    applications::transfer::{forward::ForwardMetadata, Amount},
     core::ics24_host::identifier::{ChainId, ChannelId, PortId},
 };
 
@@ -70,19 +70,17 @@ pub struct TxIcs20MsgTransferCmd {
 
     #[clap(
         long = "timeout-height-offset",
-        default_value = "0",
         value_name = "TIMEOUT_HEIGHT_OFFSET",
-        help = "Timeout in number of blocks since current"
+        help = "Timeout in number of blocks since current, 0 for none. If neither this nor the timeout in seconds is given, the packet expires after 10 minutes, or the number of blocks the destination chain produces in 10 minutes"
     )]
-    timeout_height_offset: u64,
+    timeout_height_offset: Option<u64>,
 
     #[clap(
         long = "timeout-seconds",
-        default_value = "0",
         value_name = "TIMEOUT_SECONDS",
-        help = "Timeout in seconds since current"
+        help = "Timeout in seconds since current, 0 for none"
     )]
-    timeout_seconds: u64,
+    timeout_seconds: Option<u64>,
 
     #[clap(
         long = "receiver",
@@ -119,6 +117,23 @@ pub struct TxIcs20MsgTransferCmd {
         help = "Optional memo included in the transfer"
     )]
     memo: Option<String>,
+
+    #[clap(
+        long = "forward-channel",
+        value_name = "FORWARD_CHANNEL_ID",
+        requires = "forward_receiver",
+        conflicts_with = "memo",
+        help = "Have the packet forward middleware of the destination chain forward the tokens over this channel of its transfer port"
+    )]
+    forward_channel: Option<ChannelId>,
+
+    #[clap(
+        long = "forward-receiver",
+        value_name = "FORWARD_RECEIVER",
+        requires = "forward_channel",
+        help = "The account address which will receive the forwarded tokens"
+    )]
+    forward_receiver: Option<String>,
 }
 
 impl Override<Config> for TxIcs20MsgTransferCmd {
@@ -161,6 +176,14 @@ impl TxIcs20MsgTransferCmd {
             return Err(eyre!("number of messages should be greater than zero"));
         }
 
+        // Instruct the destination chain to forward the tokens, if requested
+        let memo = match (&self.forward_channel, &self.forward_receiver) {
+            (Some(channel), Some(receiver)) => {
+                Some(ForwardMetadata::new(PortId::transfer(), channel.clone(), receiver).to_memo())
+            }
+            _ => self.memo.clone(),
+        };
+
         let opts = TransferOptions {
             src_port_id: self.src_port_id.clone(),
             src_channel_id: self.src_channel_id.clone(),
@@ -168,9 +191,9 @@ impl TxIcs20MsgTransferCmd {
             denom,
             receiver: self.receiver.clone(),
             timeout_height_offset: self.timeout_height_offset,
-            timeout_duration: Duration::from_secs(self.timeout_seconds),
+            timeout_duration: self.timeout_seconds.map(Duration::from_secs),
             number_msgs,
-            memo: self.memo.clone(),
+            memo,
         };
 
         Ok(opts)
@@ -197,6 +220,16 @@ impl Runnable for TxIcs20MsgTransferCmd {
         )
         .unwrap_or_else(exit_with_unrecoverable_error);
 
+        if let Some(src_config) = config.find_chain(&self.src_chain_id) {
+            info!(
+                "sending {} message(s) transferring {} each from {} to {}",
+                opts.number_msgs,
+                src_config.format_amount(&opts.amount.to_string(), &opts.denom),
+                self.src_chain_id,
+                self.dst_chain_id
+            );
+        }
+
         // Checks pass, build and send the tx
         let res: Result<Vec<IbcEventWithHeight>, Error> =
             build_and_send_transfer_messages(&chains.src, &chains.dst, &opts)
@@ -230,13 +263,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receiver: None,
                 denom: "samoleans".to_owned(),
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -263,13 +298,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receiver: None,
                 denom: "samoleans".to_owned(),
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -296,13 +333,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receiver: None,
                 denom: "my_denom".to_owned(),
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -331,13 +370,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receiver: None,
                 denom: "samoleans".to_owned(),
                 number_msgs: None,
                 key_name: Some("key_name".to_owned()),
                 memo: None,
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -366,13 +407,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receiver: None,
                 denom: "samoleans".to_owned(),
                 number_msgs: Some(21),
                 key_name: None,
                 memo: None,
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -401,13 +444,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receiver: Some("receiver_addr".to_owned()),
                 denom: "samoleans".to_owned(),
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -436,13 +481,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 21,
-                timeout_seconds: 0,
+                timeout_height_offset: Some(21),
+                timeout_seconds: None,
                 receiver: None,
                 denom: "samoleans".to_owned(),
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -471,13 +518,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 0,
-                timeout_seconds: 21,
+                timeout_height_offset: None,
+                timeout_seconds: Some(21),
                 receiver: None,
                 denom: "samoleans".to_owned(),
                 number_msgs: None,
                 key_name: None,
                 memo: None,
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -506,13 +555,15 @@ mod tests {
                 src_port_id: PortId::from_str("port_sender").unwrap(),
                 src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
                 amount: Amount::from(42u64),
-                timeout_height_offset: 0,
-                timeout_seconds: 0,
+                timeout_height_offset: None,
+                timeout_seconds: None,
                 receiver: None,
                 denom: "samoleans".to_owned(),
                 number_msgs: None,
                 key_name: None,
                 memo: Some("test memo".to_owned()),
+                forward_channel: None,
+                forward_receiver: None,
             },
             TxIcs20MsgTransferCmd::parse_from([
                 "test",
@@ -532,6 +583,89 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_ft_transfer_forward() {
+        assert_eq!(
+            TxIcs20MsgTransferCmd {
+                dst_chain_id: ChainId::from_string("chain_receiver"),
+                src_chain_id: ChainId::from_string("chain_sender"),
+                src_port_id: PortId::from_str("port_sender").unwrap(),
+                src_channel_id: ChannelId::from_str("channel_sender").unwrap(),
+                amount: Amount::from(42u64),
+                timeout_height_offset: None,
+                timeout_seconds: None,
+                receiver: None,
+                denom: "samoleans".to_owned(),
+                number_msgs: None,
+                key_name: None,
+                memo: None,
+                forward_channel: Some(ChannelId::from_str("channel-1").unwrap()),
+                forward_receiver: Some("final_receiver".to_owned()),
+            },
+            TxIcs20MsgTransferCmd::parse_from([
+                "test",
+                "--dst-chain",
+                "chain_receiver",
+                "--src-chain",
+                "chain_sender",
+                "--src-port",
+                "port_sender",
+                "--src-channel",
+                "channel_sender",
+                "--amount",
+                "42",
+                "--forward-channel",
+                "channel-1",
+                "--forward-receiver",
+                "final_receiver",
+            ])
+        )
+    }
+
+    #[test]
+    fn test_ft_transfer_forward_no_receiver() {
+        assert!(TxIcs20MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "port_sender",
+            "--src-channel",
+            "channel_sender",
+            "--amount",
+            "42",
+            "--forward-channel",
+            "channel-1"
+        ])
+        .is_err())
+    }
+
+    #[test]
+    fn test_ft_transfer_forward_and_memo() {
+        assert!(TxIcs20MsgTransferCmd::try_parse_from([
+            "test",
+            "--dst-chain",
+            "chain_receiver",
+            "--src-chain",
+            "chain_sender",
+            "--src-port",
+            "port_sender",
+            "--src-channel",
+            "channel_sender",
+            "--amount",
+            "42",
+            "--memo",
+            "test memo",
+            "--forward-channel",
+            "channel-1",
+            "--forward-receiver",
+            "final_receiver"
+        ])
+        .is_err())
+    }
+
     #[test]
     fn test_ft_transfer_no_amount() {
         assert!(TxIcs20MsgTransferCmd::try_parse_from([
@@ -0,0 +1,162 @@
+//! `verify` subcommand
+
+use abscissa_core::clap::Parser;
+use abscissa_core::{Command, Runnable};
+
+use ibc_relayer::chain::handle::ChainHandle;
+use ibc_relayer::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use ibc_relayer::verify_client::verify_client;
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::Height;
+
+use crate::cli_utils::spawn_chain_runtime;
+use crate::conclude::{exit_with_unrecoverable_error, Output};
+use crate::error::Error;
+use crate::prelude::*;
+
+#[derive(Command, Debug, Parser, Runnable)]
+pub enum VerifyCmds {
+    /// Verify the consensus state of an IBC client against the chain it tracks
+    Client(VerifyClientCmd),
+}
+
+#[derive(Clone, Command, Debug, Parser, PartialEq, Eq)]
+pub struct VerifyClientCmd {
+    #[clap(
+        long = "host-chain",
+        required = true,
+        value_name = "HOST_CHAIN_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the chain that hosts the client"
+    )]
+    host_chain_id: ChainId,
+
+    #[clap(
+        long = "client",
+        required = true,
+        value_name = "CLIENT_ID",
+        help_heading = "REQUIRED",
+        help = "Identifier of the client to verify"
+    )]
+    client_id: ClientId,
+
+    #[clap(
+        long = "height",
+        value_name = "REFERENCE_HEIGHT",
+        help = "The height of the consensus state to verify. Leave unspecified for the latest height of the client."
+    )]
+    height: Option<u64>,
+
+    #[clap(
+        long = "trusted-height",
+        value_name = "REFERENCE_TRUSTED_HEIGHT",
+        help = "The height from which to verify the reference header. Leave unspecified for the latest consensus state of the client below the verified height."
+    )]
+    trusted_height: Option<u64>,
+}
+
+impl Runnable for VerifyClientCmd {
+    fn run(&self) {
+        let config = app_config();
+
+        let host_chain = spawn_chain_runtime(&config, &self.host_chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let reference_chain_id = match host_chain.query_client_state(
+            QueryClientStateRequest {
+                client_id: self.client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        ) {
+            Ok((cs, _)) => cs.chain_id(),
+            Err(e) => {
+                Output::error(format!(
+                    "Query of client '{}' on chain '{}' failed with error: {}",
+                    self.client_id, self.host_chain_id, e
+                ))
+                .exit();
+            }
+        };
+
+        let reference_chain = spawn_chain_runtime(&config, &reference_chain_id)
+            .unwrap_or_else(exit_with_unrecoverable_error);
+
+        let to_height = |height| {
+            Height::new(reference_chain_id.version(), height)
+                .unwrap_or_else(exit_with_unrecoverable_error)
+        };
+
+        let res = verify_client(
+            &host_chain,
+            &reference_chain,
+            &self.client_id,
+            self.height.map(to_height),
+            self.trusted_height.map(to_height),
+        )
+        .map_err(Error::verify_client);
+
+        match res {
+            Ok(report) if report.matches => Output::success(report).exit(),
+            Ok(report) => Output::with_error().with_result(report).exit(),
+            Err(e) => Output::error(e).exit(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VerifyClientCmd;
+
+    use abscissa_core::clap::Parser;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+    use std::str::FromStr;
+
+    #[test]
+    fn test_verify_client_required_only() {
+        assert_eq!(
+            VerifyClientCmd {
+                host_chain_id: ChainId::from_string("chain_host"),
+                client_id: ClientId::from_str("client_to_verify").unwrap(),
+                height: None,
+                trusted_height: None,
+            },
+            VerifyClientCmd::parse_from([
+                "test",
+                "--host-chain",
+                "chain_host",
+                "--client",
+                "client_to_verify"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_verify_client_heights() {
+        assert_eq!(
+            VerifyClientCmd {
+                host_chain_id: ChainId::from_string("chain_host"),
+                client_id: ClientId::from_str("client_to_verify").unwrap(),
+                height: Some(42),
+                trusted_height: Some(21),
+            },
+            VerifyClientCmd::parse_from([
+                "test",
+                "--host-chain",
+                "chain_host",
+                "--client",
+                "client_to_verify",
+                "--height",
+                "42",
+                "--trusted-height",
+                "21"
+            ])
+        )
+    }
+
+    #[test]
+    fn test_verify_client_no_client() {
+        assert!(VerifyClientCmd::try_parse_from(["test", "--host-chain", "chain_host"]).is_err())
+    }
+}
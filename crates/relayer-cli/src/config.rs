@@ -8,8 +8,10 @@ use alloc::collections::BTreeSet;
 use std::path::PathBuf;
 
 use flex_error::{define_error, TraceError};
-use ibc_relayer::config::{ChainConfig, Config, ModeConfig};
+use ibc_relayer::config::{ChainConfig, Config, ModeConfig, WebhookConfig};
+use ibc_relayer::webhook::Webhook;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::events::IbcEventType;
 use tendermint_light_client_verifier::types::TrustThreshold;
 use tracing_subscriber::filter::ParseError;
 
@@ -73,6 +75,20 @@ define_error! {
                     e.chain_id, e.gas_adjustment, e.gas_multiplier
                 )
             },
+
+        InvalidTxRateLimit
+            { chain_id: ChainId, per_second: f64 }
+            |e| {
+                format!("config file specifies an invalid `tx_rate_limit.per_second` ({0}) for the chain '{1}', it must be strictly positive",
+                    e.per_second, e.chain_id)
+            },
+
+        InvalidWebhook
+            { url: String, reason: String }
+            |e| {
+                format!("config file specifies an invalid webhook '{0}', caused by: {1}",
+                    e.url, e.reason)
+            },
     }
 }
 
@@ -96,11 +112,50 @@ pub fn validate_config(config: &Config) -> Result<(), Diagnostic<Error>> {
 
         // Validate gas-related settings
         validate_gas_settings(&c.id, c)?;
+
+        validate_tx_rate_limit(&c.id, c)?;
     }
 
     // Check for invalid mode config
     validate_mode(&config.mode)?;
 
+    for webhook in config.webhooks.iter() {
+        validate_webhook(webhook)?;
+    }
+
+    Ok(())
+}
+
+fn validate_webhook(webhook: &WebhookConfig) -> Result<(), Diagnostic<Error>> {
+    let invalid =
+        |reason: String| Diagnostic::Error(Error::invalid_webhook(webhook.url.clone(), reason));
+
+    Webhook::new(webhook.clone()).map_err(|e| invalid(e.to_string()))?;
+
+    if webhook.events.is_empty() {
+        return Err(invalid("no event type to notify".to_string()));
+    }
+
+    for kind in webhook.events.iter() {
+        if kind.parse::<IbcEventType>().is_err() {
+            return Err(invalid(format!("unknown event type '{kind}'")));
+        }
+    }
+
+    Ok(())
+}
+
+/// Check that the transactions can be submitted at a strictly positive rate.
+fn validate_tx_rate_limit(id: &ChainId, config: &ChainConfig) -> Result<(), Diagnostic<Error>> {
+    if let Some(limit) = config.tx_rate_limit {
+        if !(limit.per_second.is_finite() && limit.per_second > 0.0) {
+            return Err(Diagnostic::Error(Error::invalid_tx_rate_limit(
+                id.clone(),
+                limit.per_second,
+            )));
+        }
+    }
+
     Ok(())
 }
 
@@ -16,10 +16,13 @@ use ibc_relayer::error::Error as RelayerError;
 use ibc_relayer::foreign_client::ForeignClientError;
 use ibc_relayer::keyring::errors::Error as KeyRingError;
 use ibc_relayer::link::error::LinkError;
+use ibc_relayer::migrate_path::MigratePathError;
+use ibc_relayer::recover_client::RecoverClientError;
 use ibc_relayer::spawn::SpawnError;
 use ibc_relayer::supervisor::Error as SupervisorError;
 use ibc_relayer::transfer::TransferError;
 use ibc_relayer::upgrade_chain::UpgradeChainError;
+use ibc_relayer::verify_client::VerifyClientError;
 
 define_error! {
     /// An error raised within the relayer CLI
@@ -112,6 +115,18 @@ define_error! {
             [ UpgradeChainError ]
             |_| { "upgrade chain error" },
 
+        RecoverClient
+            [ RecoverClientError ]
+            |_| { "recover client error" },
+
+        VerifyClient
+            [ VerifyClientError ]
+            |_| { "verify client error" },
+
+        MigratePath
+            [ MigratePathError ]
+            |_| { "migrate path error" },
+
         Signer
             [ SignerError ]
             |_| { "signer error" },
@@ -64,6 +64,23 @@ pub fn supervisor_state(
     submit_request(sender, |reply_to| Request::State { reply_to })
 }
 
+/// Schedule the event batches moved to the dead letters for a retry.
+pub fn reprocess_dead_letters(sender: &channel::Sender<Request>) -> Result<usize, RestApiError> {
+    submit_request(sender, |reply_to| Request::ReprocessDeadLetters {
+        reply_to,
+    })
+}
+
+/// Resume the workers suspended by their circuit breaker.
+pub fn resume_workers(sender: &channel::Sender<Request>) -> Result<usize, RestApiError> {
+    submit_request(sender, |reply_to| Request::ResumeWorkers { reply_to })
+}
+
+/// Check that all the tasks of the supervisor are still running.
+pub fn health(sender: &channel::Sender<Request>) -> Result<(), RestApiError> {
+    submit_request(sender, |reply_to| Request::Health { reply_to })
+}
+
 pub fn assemble_version_info(sender: &channel::Sender<Request>) -> Vec<VersionInfo> {
     // Fetch the relayer library version
     let lib_version = submit_request(sender, |reply_to| Request::Version { reply_to })
@@ -3,17 +3,26 @@ use std::{
     net::{SocketAddr, ToSocketAddrs},
 };
 
-use axum::{extract::Path, response::IntoResponse, routing::get, Extension, Json, Router, Server};
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::IntoResponse,
+    routing::{get, post},
+    Extension, Json, Router, Server,
+};
 use crossbeam_channel as channel;
 use serde::{Deserialize, Serialize};
 use tokio::task::JoinHandle;
 
 use ibc_relayer::{
     rest::{request::Request, RestApiError},
-    supervisor::dump_state::SupervisorState,
+    supervisor::dump_state::{SupervisorState, WorkerDesc},
 };
 
-use crate::handle::{all_chain_ids, assemble_version_info, chain_config, supervisor_state};
+use crate::handle::{
+    all_chain_ids, assemble_version_info, chain_config, health, reprocess_dead_letters,
+    resume_workers, supervisor_state,
+};
 
 pub type BoxError = Box<dyn Error + Send + Sync>;
 
@@ -68,6 +77,41 @@ async fn get_state(
     Json(JsonResult::from(state))
 }
 
+async fn get_workers(
+    Extension(sender): Extension<Sender>,
+) -> Json<JsonResult<Vec<WorkerDesc>, RestApiError>> {
+    let workers = supervisor_state(&sender).map(|state| state.all_workers().cloned().collect());
+    Json(JsonResult::from(workers))
+}
+
+/// Liveness probe, which fails with a `503 Service Unavailable` status
+/// if some tasks of the supervisor have stopped, eg. because they panicked.
+async fn get_health(Extension(sender): Extension<Sender>) -> impl IntoResponse {
+    let health = health(&sender);
+
+    let status = if health.is_ok() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(JsonResult::from(health)))
+}
+
+async fn post_reprocess_dead_letters(
+    Extension(sender): Extension<Sender>,
+) -> Json<JsonResult<usize, RestApiError>> {
+    let count = reprocess_dead_letters(&sender);
+    Json(JsonResult::from(count))
+}
+
+async fn post_resume_workers(
+    Extension(sender): Extension<Sender>,
+) -> Json<JsonResult<usize, RestApiError>> {
+    let count = resume_workers(&sender);
+    Json(JsonResult::from(count))
+}
+
 type Sender = channel::Sender<Request>;
 
 async fn run(addr: SocketAddr, sender: Sender) {
@@ -76,6 +120,10 @@ async fn run(addr: SocketAddr, sender: Sender) {
         .route("/chains", get(get_chains))
         .route("/chain/:id", get(get_chain))
         .route("/state", get(get_state))
+        .route("/workers", get(get_workers))
+        .route("/health", get(get_health))
+        .route("/dead_letters/reprocess", post(post_reprocess_dead_letters))
+        .route("/workers/resume", post(post_resume_workers))
         .layer(Extension(sender));
 
     Server::bind(&addr)
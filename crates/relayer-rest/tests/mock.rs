@@ -4,8 +4,10 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use ibc_relayer::{
     config::ChainConfig,
+    object::{Client, Object, ObjectType},
     rest::request::{Request, VersionInfo},
-    supervisor::dump_state::SupervisorState,
+    supervisor::dump_state::{SupervisorState, WorkerDesc},
+    worker::{WorkerId, WorkerStatsSnapshot},
 };
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
@@ -142,3 +144,53 @@ async fn state() {
     })
     .await;
 }
+
+#[tokio::test]
+async fn workers() {
+    let worker = WorkerDesc::new(
+        WorkerId::new(1),
+        Object::Client(Client {
+            dst_chain_id: "mock-0".parse().unwrap(),
+            dst_client_id: "07-tendermint-0".parse().unwrap(),
+            src_chain_id: "mock-1".parse().unwrap(),
+        }),
+        None,
+        WorkerStatsSnapshot {
+            last_activity: Some("2023-01-01T00:00:00.000Z".to_string()),
+            retries: 2,
+            queued_operational_data: 0,
+            pending_txs: 0,
+            suspended: false,
+        },
+    );
+
+    let state = SupervisorState {
+        chains: vec!["mock-0".parse().unwrap(), "mock-1".parse().unwrap()],
+        workers: [(ObjectType::Client, vec![worker.clone()])].into(),
+    };
+
+    let result: JsonResult<_, ()> = JsonResult::Success(vec![worker]);
+
+    run_test(19105, "/workers", result, |req| match req {
+        Request::State { reply_to } => {
+            reply_to.send(Ok(state)).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn health() {
+    let result: JsonResult<_, ()> = JsonResult::Success(());
+
+    run_test(19106, "/health", result, |req| match req {
+        Request::Health { reply_to } => {
+            reply_to.send(Ok(())).unwrap();
+            TestResult::Success
+        }
+        req => TestResult::WrongRequest(req),
+    })
+    .await;
+}
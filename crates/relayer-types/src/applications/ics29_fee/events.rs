@@ -11,6 +11,7 @@ use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::events::IbcEventType;
 use crate::prelude::*;
 use crate::signer::Signer;
+use crate::utils::redact::Redacted;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct IncentivizedPacket {
@@ -115,13 +116,24 @@ impl TryFrom<&str> for DistributionType {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[derive(Clone, Serialize, Deserialize, Eq, PartialEq)]
 pub struct DistributeFeePacket {
+    #[serde(serialize_with = "crate::utils::redact::ser_redacted")]
     pub receiver: Signer,
     pub fee: RawCoin,
     pub distribution_type: DistributionType,
 }
 
+impl core::fmt::Debug for DistributeFeePacket {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("DistributeFeePacket")
+            .field("receiver", &Redacted(&self.receiver))
+            .field("fee", &self.fee)
+            .field("distribution_type", &self.distribution_type)
+            .finish()
+    }
+}
+
 impl From<DistributeFeePacket> for abci::Event {
     fn from(event: DistributeFeePacket) -> Self {
         let attributes = vec![
@@ -40,6 +40,14 @@ impl TracePrefix {
             channel_id,
         }
     }
+
+    pub fn port_id(&self) -> &PortId {
+        &self.port_id
+    }
+
+    pub fn channel_id(&self) -> &ChannelId {
+        &self.channel_id
+    }
 }
 
 impl Display for TracePrefix {
@@ -77,6 +85,12 @@ impl TracePath {
     pub fn is_empty(&self) -> bool {
         self.0.is_empty()
     }
+
+    /// Returns the prefixes of the path, starting with the outermost one,
+    /// ie. in the order in which they appear in the path.
+    pub fn prefixes(&self) -> impl Iterator<Item = &TracePrefix> {
+        self.0.iter().rev()
+    }
 }
 
 impl<'a> TryFrom<Vec<&'a str>> for TracePath {
@@ -153,6 +167,14 @@ impl PrefixedDenom {
     pub fn add_trace_prefix(&mut self, prefix: TracePrefix) {
         self.trace_path.add_prefix(prefix)
     }
+
+    pub fn trace_path(&self) -> &TracePath {
+        &self.trace_path
+    }
+
+    pub fn base_denom(&self) -> &BaseDenom {
+        &self.base_denom
+    }
 }
 
 /// Returns true if the denomination originally came from the sender chain and
@@ -0,0 +1,168 @@
+//! Metadata understood by the packet forward middleware (PFM), which is carried in the
+//! memo of an ICS-20 packet to have the receiving chain forward the tokens to another chain.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
+
+/// Instructs the chain receiving a packet to forward its tokens to `receiver`,
+/// over the channel `channel` bound to the port `port` on that chain.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ForwardMetadata {
+    pub receiver: String,
+    pub port: PortId,
+    pub channel: ChannelId,
+    /// Timeout of the forwarded packet, eg. `10m`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+    /// Number of times the forwarded packet is sent again if it fails
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u8>,
+    /// Memo of the forwarded packet, which can itself forward the tokens further
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next: Option<Value>,
+}
+
+/// The memo of a packet to be forwarded.
+#[derive(Serialize, Deserialize)]
+struct ForwardMemo {
+    forward: ForwardMetadata,
+}
+
+impl ForwardMetadata {
+    pub fn new(port: PortId, channel: ChannelId, receiver: impl Into<String>) -> Self {
+        Self {
+            receiver: receiver.into(),
+            port,
+            channel,
+            timeout: None,
+            retries: None,
+            next: None,
+        }
+    }
+
+    /// Have the tokens forwarded once more, from the last chain they are forwarded to.
+    pub fn then(mut self, hop: ForwardMetadata) -> Self {
+        let next = match self.next_hop() {
+            Some(next) => next.then(hop),
+            None => hop,
+        };
+
+        self.next = Some(ForwardMemo::from(next).to_value());
+        self
+    }
+
+    /// The memo to set on a transfer for its tokens to be forwarded.
+    pub fn to_memo(&self) -> String {
+        ForwardMemo::from(self.clone()).to_value().to_string()
+    }
+
+    /// The forwarding instructions in the given memo, if any.
+    pub fn from_memo(memo: &str) -> Option<Self> {
+        serde_json::from_str::<ForwardMemo>(memo)
+            .ok()
+            .map(|memo| memo.forward)
+    }
+
+    /// The forwarding instructions of the forwarded packet, if any.
+    ///
+    /// The middleware accepts the next memo either as a JSON object or as a string.
+    pub fn next_hop(&self) -> Option<Self> {
+        match self.next.as_ref()? {
+            Value::String(memo) => Self::from_memo(memo),
+            value => serde_json::from_value::<ForwardMemo>(value.clone())
+                .ok()
+                .map(|memo| memo.forward),
+        }
+    }
+
+    /// All the hops the tokens are forwarded over according to the given memo,
+    /// starting with the one from the chain receiving the packet.
+    pub fn route(memo: &str) -> Vec<Self> {
+        let mut route = vec![];
+        let mut hop = Self::from_memo(memo);
+
+        while let Some(current) = hop {
+            hop = current.next_hop();
+            route.push(current);
+        }
+
+        route
+    }
+}
+
+impl From<ForwardMetadata> for ForwardMemo {
+    fn from(forward: ForwardMetadata) -> Self {
+        Self { forward }
+    }
+}
+
+impl ForwardMemo {
+    fn to_value(&self) -> Value {
+        serde_json::to_value(self).expect("ForwardMemo's infallible Serialize impl failed")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::str::FromStr;
+
+    fn hop(channel: &str, receiver: &str) -> ForwardMetadata {
+        ForwardMetadata::new(
+            PortId::transfer(),
+            ChannelId::from_str(channel).unwrap(),
+            receiver,
+        )
+    }
+
+    #[test]
+    fn forward_memo_round_trip() {
+        let forward = hop("channel-1", "osmo1receiver")
+            .then(hop("channel-2", "juno1receiver"))
+            .then(hop("channel-3", "stars1receiver"));
+
+        let memo = forward.to_memo();
+
+        assert_eq!(
+            ForwardMetadata::route(&memo),
+            vec![
+                forward.clone(),
+                forward.next_hop().unwrap(),
+                hop("channel-3", "stars1receiver"),
+            ]
+        );
+        assert_eq!(ForwardMetadata::route(&memo)[1].receiver, "juno1receiver");
+    }
+
+    #[test]
+    fn forward_memo_with_string_next() {
+        let memo = r#"{
+            "forward": {
+                "receiver": "osmo1receiver",
+                "port": "transfer",
+                "channel": "channel-1",
+                "timeout": "10m",
+                "retries": 2,
+                "next": "{\"forward\":{\"receiver\":\"juno1receiver\",\"port\":\"transfer\",\"channel\":\"channel-2\"}}"
+            }
+        }"#;
+
+        let route = ForwardMetadata::route(memo);
+
+        assert_eq!(route.len(), 2);
+        assert_eq!(route[0].timeout.as_deref(), Some("10m"));
+        assert_eq!(route[0].retries, Some(2));
+        assert_eq!(route[1], hop("channel-2", "juno1receiver"));
+    }
+
+    #[test]
+    fn memo_without_forward() {
+        assert!(ForwardMetadata::route("").is_empty());
+        assert!(ForwardMetadata::route("hello").is_empty());
+        assert!(ForwardMetadata::route(r#"{"wasm": {}}"#).is_empty());
+    }
+}
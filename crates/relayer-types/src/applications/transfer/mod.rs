@@ -8,6 +8,7 @@ pub mod coin;
 pub mod denom;
 pub mod error;
 pub mod events;
+pub mod forward;
 pub mod msgs;
 pub mod packet;
 
@@ -24,3 +25,6 @@ pub const PORT_ID_STR: &str = "transfer";
 
 /// ICS20 application current version.
 pub const VERSION: &str = "ics20-1";
+
+/// ICS20 application version whose packets can carry multiple tokens.
+pub const VERSION_V2: &str = "ics20-2";
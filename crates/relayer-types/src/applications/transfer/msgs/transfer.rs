@@ -13,6 +13,7 @@ use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::signer::Signer;
 use crate::timestamp::Timestamp;
 use crate::tx_msg::Msg;
+use crate::utils::redact::Redacted;
 
 pub const TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
 
@@ -23,7 +24,7 @@ pub const TYPE_URL: &str = "/ibc.applications.transfer.v1.MsgTransfer";
 /// packet, which might be the user of a command line application, should only
 /// have to specify the information related to the transfer of the token, and
 /// let the library figure out how to build the packet properly.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct MsgTransfer<C = Coin> {
     /// the port on which the packet will be sent
     pub source_port: PortId,
@@ -45,6 +46,21 @@ pub struct MsgTransfer<C = Coin> {
     pub memo: Option<String>,
 }
 
+impl<C: core::fmt::Debug> core::fmt::Debug for MsgTransfer<C> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MsgTransfer")
+            .field("source_port", &self.source_port)
+            .field("source_channel", &self.source_channel)
+            .field("token", &self.token)
+            .field("sender", &Redacted(&self.sender))
+            .field("receiver", &Redacted(&self.receiver))
+            .field("timeout_height", &self.timeout_height)
+            .field("timeout_timestamp", &self.timeout_timestamp)
+            .field("memo", &Redacted(&self.memo))
+            .finish()
+    }
+}
+
 impl Msg for MsgTransfer {
     type ValidationError = Error;
     type Raw = RawMsgTransfer;
@@ -1,15 +1,18 @@
-use alloc::string::{String, ToString};
-use core::convert::TryFrom;
 use core::str::FromStr;
 
 use ibc_proto::ibc::applications::transfer::v2::FungibleTokenPacketData as RawPacketData;
+use prost::Message;
 use serde::{Deserialize, Serialize};
 
 use super::error::Error;
-use super::{Amount, PrefixedCoin, PrefixedDenom};
+use super::{Amount, BaseDenom, PrefixedCoin, PrefixedDenom, TracePrefix, VERSION_V2};
+use crate::core::ics04_channel::version::Version;
+use crate::core::ics24_host::identifier::{ChannelId, PortId};
+use crate::prelude::*;
 use crate::signer::Signer;
+use crate::utils::redact::Redacted;
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(try_from = "RawPacketData", into = "RawPacketData")]
 pub struct PacketData {
     pub token: PrefixedCoin,
@@ -18,6 +21,17 @@ pub struct PacketData {
     pub memo: Option<String>,
 }
 
+impl core::fmt::Debug for PacketData {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("PacketData")
+            .field("token", &self.token)
+            .field("sender", &Redacted(&self.sender))
+            .field("receiver", &Redacted(&self.receiver))
+            .field("memo", &Redacted(&self.memo))
+            .finish()
+    }
+}
+
 impl TryFrom<RawPacketData> for PacketData {
     type Error = Error;
 
@@ -47,3 +61,373 @@ impl From<PacketData> for RawPacketData {
         }
     }
 }
+
+/// ICS-20 packet data carrying several tokens, as sent over
+/// channels of version [`VERSION_V2`](super::VERSION_V2).
+#[derive(Clone, PartialEq, Eq)]
+pub struct MultiTokenPacketData {
+    pub tokens: Vec<PrefixedCoin>,
+    pub sender: Signer,
+    pub receiver: Signer,
+    pub memo: Option<String>,
+    pub forwarding: Forwarding,
+}
+
+/// The channels through which the tokens of a packet are forwarded once received,
+/// and the memo of the packet sent over the last one.
+#[derive(Clone, Default, PartialEq, Eq)]
+pub struct Forwarding {
+    pub hops: Vec<TracePrefix>,
+    pub destination_memo: Option<String>,
+}
+
+impl core::fmt::Debug for MultiTokenPacketData {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("MultiTokenPacketData")
+            .field("tokens", &self.tokens)
+            .field("sender", &Redacted(&self.sender))
+            .field("receiver", &Redacted(&self.receiver))
+            .field("memo", &Redacted(&self.memo))
+            .field("forwarding", &self.forwarding)
+            .finish()
+    }
+}
+
+impl core::fmt::Debug for Forwarding {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Forwarding")
+            .field("hops", &self.hops)
+            .field("destination_memo", &Redacted(&self.destination_memo))
+            .finish()
+    }
+}
+
+impl From<PacketData> for MultiTokenPacketData {
+    fn from(pkt_data: PacketData) -> Self {
+        Self {
+            tokens: vec![pkt_data.token],
+            sender: pkt_data.sender,
+            receiver: pkt_data.receiver,
+            memo: pkt_data.memo,
+            forwarding: Forwarding::default(),
+        }
+    }
+}
+
+// The protobuf encoding of `MultiTokenPacketData` used by ibc-go v9, which `ibc-proto` does not provide yet.
+// protobuf messages: https://github.com/cosmos/ibc-go/blob/v9.0.0/proto/ibc/applications/transfer/v2/packet.proto
+// and https://github.com/cosmos/ibc-go/blob/v9.0.0/proto/ibc/applications/transfer/v1/token.proto
+#[derive(Clone, PartialEq, Eq, Message)]
+struct RawMultiTokenPacketData {
+    #[prost(message, repeated, tag = "1")]
+    tokens: Vec<RawToken>,
+    #[prost(string, tag = "2")]
+    sender: String,
+    #[prost(string, tag = "3")]
+    receiver: String,
+    #[prost(string, tag = "4")]
+    memo: String,
+    #[prost(message, optional, tag = "5")]
+    forwarding: Option<RawForwarding>,
+}
+
+#[derive(Clone, PartialEq, Eq, Message)]
+struct RawToken {
+    #[prost(message, optional, tag = "1")]
+    denom: Option<RawDenom>,
+    #[prost(string, tag = "2")]
+    amount: String,
+}
+
+/// A denomination, whose trace is given as a list of hops, outermost first.
+#[derive(Clone, PartialEq, Eq, Message)]
+struct RawDenom {
+    #[prost(string, tag = "1")]
+    base: String,
+    #[prost(message, repeated, tag = "3")]
+    trace: Vec<RawHop>,
+}
+
+#[derive(Clone, PartialEq, Eq, Message)]
+struct RawForwarding {
+    #[prost(string, tag = "1")]
+    destination_memo: String,
+    #[prost(message, repeated, tag = "2")]
+    hops: Vec<RawHop>,
+}
+
+#[derive(Clone, PartialEq, Eq, Message)]
+struct RawHop {
+    #[prost(string, tag = "1")]
+    port_id: String,
+    #[prost(string, tag = "2")]
+    channel_id: String,
+}
+
+impl RawHop {
+    fn try_into_prefix(self, pos: usize) -> Result<TracePrefix, Error> {
+        let port_id =
+            PortId::from_str(&self.port_id).map_err(|e| Error::invalid_trace_port_id(pos, e))?;
+        let channel_id = ChannelId::from_str(&self.channel_id)
+            .map_err(|e| Error::invalid_trace_channel_id(pos, e))?;
+
+        Ok(TracePrefix::new(port_id, channel_id))
+    }
+}
+
+impl From<&TracePrefix> for RawHop {
+    fn from(prefix: &TracePrefix) -> Self {
+        Self {
+            port_id: prefix.port_id().to_string(),
+            channel_id: prefix.channel_id().to_string(),
+        }
+    }
+}
+
+impl TryFrom<RawToken> for PrefixedCoin {
+    type Error = Error;
+
+    fn try_from(raw_token: RawToken) -> Result<Self, Self::Error> {
+        let raw_denom = raw_token.denom.unwrap_or_default();
+        let mut denom = PrefixedDenom::from(BaseDenom::from_str(&raw_denom.base)?);
+
+        // Prefixes are added from the innermost one
+        for (pos, hop) in raw_denom.trace.into_iter().enumerate().rev() {
+            denom.add_trace_prefix(hop.try_into_prefix(pos)?);
+        }
+
+        Ok(PrefixedCoin {
+            denom,
+            amount: Amount::from_str(&raw_token.amount)?,
+        })
+    }
+}
+
+impl From<PrefixedCoin> for RawToken {
+    fn from(token: PrefixedCoin) -> Self {
+        let trace = token
+            .denom
+            .trace_path()
+            .prefixes()
+            .map(RawHop::from)
+            .collect();
+
+        Self {
+            denom: Some(RawDenom {
+                base: token.denom.base_denom().to_string(),
+                trace,
+            }),
+            amount: token.amount.to_string(),
+        }
+    }
+}
+
+impl TryFrom<RawForwarding> for Forwarding {
+    type Error = Error;
+
+    fn try_from(raw_forwarding: RawForwarding) -> Result<Self, Self::Error> {
+        let hops = raw_forwarding
+            .hops
+            .into_iter()
+            .enumerate()
+            .map(|(pos, hop)| hop.try_into_prefix(pos))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            hops,
+            destination_memo: Some(raw_forwarding.destination_memo).filter(|m| !m.is_empty()),
+        })
+    }
+}
+
+impl From<Forwarding> for RawForwarding {
+    fn from(forwarding: Forwarding) -> Self {
+        Self {
+            destination_memo: forwarding.destination_memo.unwrap_or_default(),
+            hops: forwarding.hops.iter().map(RawHop::from).collect(),
+        }
+    }
+}
+
+impl TryFrom<RawMultiTokenPacketData> for MultiTokenPacketData {
+    type Error = Error;
+
+    fn try_from(raw_pkt_data: RawMultiTokenPacketData) -> Result<Self, Self::Error> {
+        let tokens = raw_pkt_data
+            .tokens
+            .into_iter()
+            .map(PrefixedCoin::try_from)
+            .collect::<Result<_, _>>()?;
+        let memo = Some(raw_pkt_data.memo).filter(|m| !m.is_empty());
+
+        Ok(Self {
+            tokens,
+            sender: raw_pkt_data.sender.parse().map_err(Error::signer)?,
+            receiver: raw_pkt_data.receiver.parse().map_err(Error::signer)?,
+            memo,
+            forwarding: raw_pkt_data.forwarding.unwrap_or_default().try_into()?,
+        })
+    }
+}
+
+impl From<MultiTokenPacketData> for RawMultiTokenPacketData {
+    fn from(pkt_data: MultiTokenPacketData) -> Self {
+        Self {
+            tokens: pkt_data.tokens.into_iter().map(RawToken::from).collect(),
+            sender: pkt_data.sender.to_string(),
+            receiver: pkt_data.receiver.to_string(),
+            memo: pkt_data.memo.unwrap_or_default(),
+            forwarding: Some(pkt_data.forwarding.into()),
+        }
+    }
+}
+
+/// The data of an ICS-20 packet, in the format of the version of its channel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AnyPacketData {
+    /// A single token, sent over a channel of version [`VERSION`](super::VERSION)
+    V1(PacketData),
+    /// Several tokens, sent over a channel of version [`VERSION_V2`](super::VERSION_V2)
+    V2(MultiTokenPacketData),
+}
+
+impl AnyPacketData {
+    /// Decode the data of a packet sent over a channel with the given version,
+    /// which may be wrapped by the fee middleware.
+    ///
+    /// The packet data of [`VERSION_V2`](super::VERSION_V2) channels is encoded
+    /// in protobuf, and that of other channels in JSON.
+    pub fn decode(channel_version: &Version, data: &[u8]) -> Result<Self, Error> {
+        if channel_version.app_version() == VERSION_V2 {
+            let raw_pkt_data = RawMultiTokenPacketData::decode(data)
+                .map_err(|_| Error::packet_data_deserialization())?;
+
+            MultiTokenPacketData::try_from(raw_pkt_data).map(Self::V2)
+        } else {
+            serde_json::from_slice(data)
+                .map(Self::V1)
+                .map_err(|_| Error::packet_data_deserialization())
+        }
+    }
+
+    /// Encode the packet data in the format of its version.
+    pub fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::V1(pkt_data) => {
+                serde_json::to_vec(pkt_data).expect("PacketData's infallible Serialize impl failed")
+            }
+            Self::V2(pkt_data) => RawMultiTokenPacketData::from(pkt_data.clone()).encode_to_vec(),
+        }
+    }
+
+    pub fn tokens(&self) -> Vec<PrefixedCoin> {
+        match self {
+            Self::V1(pkt_data) => vec![pkt_data.token.clone()],
+            Self::V2(pkt_data) => pkt_data.tokens.clone(),
+        }
+    }
+
+    pub fn sender(&self) -> &Signer {
+        match self {
+            Self::V1(pkt_data) => &pkt_data.sender,
+            Self::V2(pkt_data) => &pkt_data.sender,
+        }
+    }
+
+    pub fn receiver(&self) -> &Signer {
+        match self {
+            Self::V1(pkt_data) => &pkt_data.receiver,
+            Self::V2(pkt_data) => &pkt_data.receiver,
+        }
+    }
+
+    pub fn memo(&self) -> Option<&str> {
+        match self {
+            Self::V1(pkt_data) => pkt_data.memo.as_deref(),
+            Self::V2(pkt_data) => pkt_data.memo.as_deref(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENDER: &str = "cosmos1wxeyh7zgn4tctjzs0vtqpc6p5cxq5t2muzl7ng";
+    const RECEIVER: &str = "cosmos1j5lm9qhlxh0j3s9quqzldtw3vmdx5nrzdlmnaz";
+
+    #[test]
+    fn decode_v1_packet_data() {
+        let data = format!(
+            r#"{{"denom":"transfer/channel-1/uatom","amount":"100","sender":"{SENDER}","receiver":"{RECEIVER}","memo":""}}"#
+        );
+
+        let pkt_data = AnyPacketData::decode(&Version::ics20_with_fee(), data.as_bytes()).unwrap();
+
+        assert!(matches!(pkt_data, AnyPacketData::V1(_)));
+        assert_eq!(
+            pkt_data.tokens(),
+            vec![PrefixedCoin::from_str("100transfer/channel-1/uatom").unwrap()]
+        );
+        assert_eq!(pkt_data.memo(), None);
+    }
+
+    /// A `FungibleTokenPacketDataV2` in the protobuf encoding of ibc-go v9, with two tokens,
+    /// the first one having a trace of two hops, forwarded over `transfer/channel-2`
+    /// with the destination memo "hello".
+    const V2_PACKET_DATA: &str = concat!(
+        "0a3c0a350a057561746f6d1a150a087472616e7366657212096368616e6e656c",
+        "2d311a150a087472616e7366657212096368616e6e656c2d3012033130300a0c",
+        "0a070a057374616b65120137122d636f736d6f73317778657968377a676e3474",
+        "63746a7a733076747170633670356378713574326d757a6c376e671a2d636f73",
+        "6d6f73316a356c6d3971686c7868306a3373397175717a6c64747733766d6478",
+        "356e727a646c6d6e617a2a1e0a0568656c6c6f12150a087472616e7366657212",
+        "096368616e6e656c2d32",
+    );
+
+    #[test]
+    fn decode_v2_packet_data() {
+        let data = subtle_encoding::hex::decode(V2_PACKET_DATA).unwrap();
+
+        let pkt_data = AnyPacketData::decode(&Version::ics20_v2(), &data).unwrap();
+
+        assert_eq!(
+            pkt_data.tokens(),
+            vec![
+                PrefixedCoin::from_str("100transfer/channel-1/transfer/channel-0/uatom").unwrap(),
+                PrefixedCoin::from_str("7stake").unwrap(),
+            ]
+        );
+        assert_eq!(pkt_data.sender().as_ref(), SENDER);
+        assert_eq!(pkt_data.receiver().as_ref(), RECEIVER);
+        assert_eq!(pkt_data.memo(), None);
+
+        let AnyPacketData::V2(v2) = &pkt_data else {
+            panic!("expected ics20-2 packet data");
+        };
+        assert_eq!(
+            v2.forwarding,
+            Forwarding {
+                hops: vec![TracePrefix::new(
+                    PortId::transfer(),
+                    ChannelId::from_str("channel-2").unwrap()
+                )],
+                destination_memo: Some("hello".to_string()),
+            }
+        );
+
+        // Encoding the packet data gives back the bytes of ibc-go
+        assert_eq!(pkt_data.encode(), data);
+    }
+
+    #[test]
+    fn decode_packet_data_of_other_version() {
+        let data = format!(
+            r#"{{"denom":"uatom","amount":"100","sender":"{SENDER}","receiver":"{RECEIVER}","memo":""}}"#
+        );
+        assert!(AnyPacketData::decode(&Version::ics20_v2(), data.as_bytes()).is_err());
+
+        let data = subtle_encoding::hex::decode(V2_PACKET_DATA).unwrap();
+        assert!(AnyPacketData::decode(&Version::ics20(), &data).is_err());
+    }
+}
@@ -180,6 +180,14 @@ define_error! {
                     e.value)
             },
 
+        InvalidHexPacketAttribute
+            { key: String, value: String }
+            | e | {
+                format_args!(
+                    "packet attribute {0} is not valid hex: {1}",
+                    e.key, e.value)
+            },
+
         InvalidPacketSequence
             {
                 given_sequence: Sequence,
@@ -11,6 +11,7 @@ use crate::core::ics24_host::identifier::{ChannelId, ConnectionId, PortId};
 use crate::events::{Error as EventError, IbcEvent, IbcEventType};
 use crate::prelude::*;
 use crate::utils::pretty::PrettySlice;
+use crate::utils::redact::{packet_data_redacted, RedactedData};
 
 /// Channel event attribute keys
 pub const CONNECTION_ID_ATTRIBUTE_KEY: &str = "connection_id";
@@ -22,6 +23,7 @@ pub const COUNTERPARTY_PORT_ID_ATTRIBUTE_KEY: &str = "counterparty_port_id";
 /// Packet event attribute keys
 pub const PKT_SEQ_ATTRIBUTE_KEY: &str = "packet_sequence";
 pub const PKT_DATA_ATTRIBUTE_KEY: &str = "packet_data";
+pub const PKT_DATA_HEX_ATTRIBUTE_KEY: &str = "packet_data_hex";
 pub const PKT_SRC_PORT_ATTRIBUTE_KEY: &str = "packet_src_port";
 pub const PKT_SRC_CHANNEL_ATTRIBUTE_KEY: &str = "packet_src_channel";
 pub const PKT_DST_PORT_ATTRIBUTE_KEY: &str = "packet_dst_port";
@@ -29,6 +31,7 @@ pub const PKT_DST_CHANNEL_ATTRIBUTE_KEY: &str = "packet_dst_channel";
 pub const PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY: &str = "packet_timeout_height";
 pub const PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY: &str = "packet_timeout_timestamp";
 pub const PKT_ACK_ATTRIBUTE_KEY: &str = "packet_ack";
+pub const PKT_ACK_HEX_ATTRIBUTE_KEY: &str = "packet_ack_hex";
 
 #[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize, Serialize)]
 pub struct Attributes {
@@ -600,10 +603,10 @@ impl TryFrom<ReceivePacket> for abci::Event {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+#[derive(Clone, PartialEq, Eq, Serialize)]
 pub struct WriteAcknowledgement {
     pub packet: Packet,
-    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
+    #[serde(serialize_with = "crate::utils::redact::ser_packet_data")]
     pub ack: Vec<u8>,
 }
 
@@ -622,14 +625,32 @@ impl WriteAcknowledgement {
     }
 }
 
+impl core::fmt::Debug for WriteAcknowledgement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        f.debug_struct("WriteAcknowledgement")
+            .field("packet", &self.packet)
+            .field("ack", &RedactedData(&self.ack))
+            .finish()
+    }
+}
+
 impl Display for WriteAcknowledgement {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
-        write!(
-            f,
-            "WriteAcknowledgement {{ packet: {}, ack: {} }}",
-            self.packet,
-            PrettySlice(&self.ack)
-        )
+        if packet_data_redacted() {
+            write!(
+                f,
+                "WriteAcknowledgement {{ packet: {}, ack: {} }}",
+                self.packet,
+                RedactedData(&self.ack)
+            )
+        } else {
+            write!(
+                f,
+                "WriteAcknowledgement {{ packet: {}, ack: {} }}",
+                self.packet,
+                PrettySlice(&self.ack)
+            )
+        }
     }
 }
 
@@ -10,6 +10,7 @@ use super::timeout::TimeoutHeight;
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 use crate::timestamp::{Expiry::Expired, Timestamp};
+use crate::utils::redact::RedactedData;
 use crate::Height;
 
 /// Enumeration of proof carrying ICS4 message, helper for relayer.
@@ -112,20 +113,12 @@ pub struct Packet {
     pub source_channel: ChannelId,
     pub destination_port: PortId,
     pub destination_channel: ChannelId,
-    #[serde(serialize_with = "crate::serializers::ser_hex_upper")]
+    #[serde(serialize_with = "crate::utils::redact::ser_packet_data")]
     pub data: Vec<u8>,
     pub timeout_height: TimeoutHeight,
     pub timeout_timestamp: Timestamp,
 }
 
-struct PacketData<'a>(&'a [u8]);
-
-impl<'a> core::fmt::Debug for PacketData<'a> {
-    fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
-        write!(formatter, "{:?}", self.0)
-    }
-}
-
 impl core::fmt::Debug for Packet {
     fn fmt(&self, formatter: &mut core::fmt::Formatter<'_>) -> Result<(), core::fmt::Error> {
         // Remember: if you alter the definition of `Packet`,
@@ -142,7 +135,7 @@ impl core::fmt::Debug for Packet {
             timeout_height: _,
             timeout_timestamp: _,
         } = self;
-        let data_wrapper = PacketData(data);
+        let data_wrapper = RedactedData(data);
 
         formatter
             .debug_struct("Packet")
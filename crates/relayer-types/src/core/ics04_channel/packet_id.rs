@@ -1,12 +1,13 @@
 use core::convert::TryFrom;
 use core::str::FromStr;
 use ibc_proto::ibc::core::channel::v1::PacketId as ProtoPacketId;
+use serde::Serialize;
 
 use crate::core::ics04_channel::error::Error;
 use crate::core::ics04_channel::packet::Sequence;
 use crate::core::ics24_host::identifier::{ChannelId, PortId};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PacketId {
     pub channel_id: ChannelId,
     pub port_id: PortId,
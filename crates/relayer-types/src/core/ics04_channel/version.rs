@@ -28,6 +28,10 @@ impl Version {
         Self::new(transfer::VERSION.to_string())
     }
 
+    pub fn ics20_v2() -> Self {
+        Self::new(transfer::VERSION_V2.to_string())
+    }
+
     pub fn ics20_with_fee() -> Self {
         let val = json::json!({
             "fee_version": "ics29-1",
@@ -53,6 +57,15 @@ impl Version {
             })
             .unwrap_or(false)
     }
+
+    /// The version of the application bound to the channel, ie. the `app_version`
+    /// if the version is that of the fee middleware, or the version itself otherwise.
+    pub fn app_version(&self) -> String {
+        json::from_str::<json::Value>(&self.0)
+            .ok()
+            .and_then(|val| Some(val.get("app_version")?.as_str()?.to_string()))
+            .unwrap_or_else(|| self.0.clone())
+    }
 }
 
 impl From<String> for Version {
@@ -98,4 +111,11 @@ mod test {
             assert!(version.supports_fee());
         }
     }
+
+    #[test]
+    fn test_app_version() {
+        assert_eq!(Version::ics20().app_version(), "ics20-1");
+        assert_eq!(Version::ics20_v2().app_version(), "ics20-2");
+        assert_eq!(Version::ics20_with_fee().app_version(), "ics20-1");
+    }
 }
@@ -1 +1,2 @@
 pub mod pretty;
+pub mod redact;
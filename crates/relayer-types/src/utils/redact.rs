@@ -0,0 +1,131 @@
+//! Redaction of the packet data in the logs and in the output of the relayer,
+//! for operators who must not record the payloads, memos and addresses
+//! carried by the packets they relay.
+//!
+//! When enabled, the packet data and the acknowledgements written for the packets
+//! are replaced by their length and SHA-256 hash, which still allows to correlate
+//! packets across logs. The addresses and memos of the decoded transfers, and the
+//! receivers of the fees, are replaced by `<redacted>`. The other fields of the
+//! packets, eg. their sequence numbers, are left untouched.
+
+use core::fmt;
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use ics23::HostFunctionsProvider;
+use serde::ser::{Serialize, Serializer};
+use subtle_encoding::{Encoding, Hex};
+
+use crate::prelude::*;
+
+static REDACT_PACKET_DATA: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable the redaction of the packet data, for the whole process.
+pub fn set_packet_data_redaction(enabled: bool) {
+    REDACT_PACKET_DATA.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether the packet data must be redacted.
+pub fn packet_data_redacted() -> bool {
+    REDACT_PACKET_DATA.load(Ordering::Relaxed)
+}
+
+/// Formats the given packet data as-is, or as its length
+/// and hash if the packet data must be redacted.
+pub struct RedactedData<'a>(pub &'a [u8]);
+
+impl<'a> RedactedData<'a> {
+    fn hash(&self) -> String {
+        let hash = ics23::HostFunctionsManager::sha2_256(self.0);
+        Hex::upper_case().encode_to_string(hash).unwrap()
+    }
+}
+
+impl<'a> fmt::Display for RedactedData<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "<redacted: {} bytes, sha256:{}>",
+            self.0.len(),
+            self.hash()
+        )
+    }
+}
+
+impl<'a> fmt::Debug for RedactedData<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if packet_data_redacted() {
+            fmt::Display::fmt(self, f)
+        } else {
+            write!(f, "{:?}", self.0)
+        }
+    }
+}
+
+/// Serializes the packet data as an upper-case hex string,
+/// or as its redacted form if the packet data must be redacted.
+pub fn ser_packet_data<S, T>(data: T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: AsRef<[u8]>,
+{
+    if packet_data_redacted() {
+        RedactedData(data.as_ref())
+            .to_string()
+            .serialize(serializer)
+    } else {
+        crate::serializers::ser_hex_upper(data, serializer)
+    }
+}
+
+/// Formats the given value as-is, or as `<redacted>` if the packet data must be redacted.
+///
+/// Used for the addresses and memos carried by the packets.
+pub struct Redacted<'a, T: ?Sized>(pub &'a T);
+
+const REDACTED: &str = "<redacted>";
+
+impl<'a, T: fmt::Display + ?Sized> fmt::Display for Redacted<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if packet_data_redacted() {
+            f.write_str(REDACTED)
+        } else {
+            fmt::Display::fmt(self.0, f)
+        }
+    }
+}
+
+impl<'a, T: fmt::Debug + ?Sized> fmt::Debug for Redacted<'a, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if packet_data_redacted() {
+            f.write_str(REDACTED)
+        } else {
+            fmt::Debug::fmt(self.0, f)
+        }
+    }
+}
+
+/// Serializes the given value as-is, or as `<redacted>` if the packet data must be redacted.
+pub fn ser_redacted<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize,
+{
+    if packet_data_redacted() {
+        REDACTED.serialize(serializer)
+    } else {
+        value.serialize(serializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::RedactedData;
+
+    #[test]
+    fn redacted_data() {
+        assert_eq!(
+            RedactedData(b"hello").to_string(),
+            "<redacted: 5 bytes, sha256:2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824>"
+        );
+    }
+}
@@ -0,0 +1,204 @@
+//! Programmatic entry point for running the relayer as a library,
+//! without going through the Hermes CLI.
+//!
+//! ```ignore
+//! let relayer = RelayerBuilder::new(config)
+//!     .with_runtime(rt)
+//!     .spawn::<CachingChainHandle>()?;
+//!
+//! let events = relayer.subscribe(&chain_id)?;
+//! // ...
+//! relayer.shutdown();
+//! ```
+
+use alloc::sync::Arc;
+
+use flex_error::define_error;
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::info;
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+
+use crate::{
+    chain::handle::{ChainHandle, Subscription},
+    config::Config,
+    error::Error as RelayerError,
+    event::IbcEventWithHeight,
+    registry::SharedRegistry,
+    rest,
+    spawn::SpawnError,
+    supervisor::{
+        dump_state::SupervisorState, spawn_supervisor, Error as SupervisorError, SupervisorHandle,
+        SupervisorOptions,
+    },
+    transfer::{build_and_send_transfer_messages, TransferError, TransferOptions},
+};
+
+define_error! {
+    RelayerBuilderError {
+        Spawn
+            [ SpawnError ]
+            | _ | { "failed to spawn chain runtime" },
+
+        Supervisor
+            [ SupervisorError ]
+            | _ | { "supervisor error" },
+
+        Relayer
+            [ RelayerError ]
+            | _ | { "relayer error" },
+
+        Transfer
+            [ TransferError ]
+            | _ | { "transfer error" },
+    }
+}
+
+/// Builder for an embedded relayer instance.
+///
+/// By default, a health check is performed on all chains at startup,
+/// no full scan is forced, the REST API is disabled, and the chain
+/// runtimes are spawned onto a dedicated Tokio runtime.
+pub struct RelayerBuilder {
+    config: Config,
+    rt: Option<Arc<TokioRuntime>>,
+    rest_rx: Option<rest::Receiver>,
+    options: SupervisorOptions,
+}
+
+impl RelayerBuilder {
+    pub fn new(config: Config) -> Self {
+        Self {
+            config,
+            rt: None,
+            rest_rx: None,
+            options: SupervisorOptions {
+                health_check: true,
+                force_full_scan: false,
+                profile_memory: false,
+            },
+        }
+    }
+
+    /// Spawn the chain runtimes onto the given Tokio runtime
+    /// instead of creating a new one.
+    pub fn with_runtime(mut self, rt: Arc<TokioRuntime>) -> Self {
+        self.rt = Some(rt);
+        self
+    }
+
+    /// Serve requests coming from the REST API over the given channel.
+    pub fn with_rest_receiver(mut self, rest_rx: rest::Receiver) -> Self {
+        self.rest_rx = Some(rest_rx);
+        self
+    }
+
+    pub fn with_health_check(mut self, health_check: bool) -> Self {
+        self.options.health_check = health_check;
+        self
+    }
+
+    pub fn with_full_scan(mut self, force_full_scan: bool) -> Self {
+        self.options.force_full_scan = force_full_scan;
+        self
+    }
+
+    /// Spawn the chain runtimes and the supervisor, and return
+    /// a [`RelayerHandle`] to interact with the running relayer.
+    pub fn spawn<Chain: ChainHandle>(self) -> Result<RelayerHandle<Chain>, RelayerBuilderError> {
+        let registry = match self.rt {
+            Some(rt) => SharedRegistry::with_runtime(self.config.clone(), rt),
+            None => SharedRegistry::new(self.config.clone()),
+        };
+
+        let supervisor = spawn_supervisor(
+            self.config.clone(),
+            registry.clone(),
+            self.rest_rx,
+            self.options,
+        )
+        .map_err(RelayerBuilderError::supervisor)?;
+
+        info!("relayer has started");
+
+        Ok(RelayerHandle {
+            config: self.config,
+            registry,
+            supervisor,
+        })
+    }
+}
+
+/// Handle to a relayer spawned with [`RelayerBuilder`].
+///
+/// Dropping the handle without calling [`RelayerHandle::shutdown`]
+/// leaves the supervisor and chain runtimes running in the background.
+pub struct RelayerHandle<Chain: ChainHandle> {
+    config: Config,
+    registry: SharedRegistry<Chain>,
+    supervisor: SupervisorHandle,
+}
+
+impl<Chain: ChainHandle> RelayerHandle<Chain> {
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    /// Return the handle for the chain with the given identifier,
+    /// spawning its runtime if it is not running yet.
+    pub fn chain(&self, chain_id: &ChainId) -> Result<Chain, RelayerBuilderError> {
+        self.registry
+            .get_or_spawn(chain_id)
+            .map_err(RelayerBuilderError::spawn)
+    }
+
+    /// Subscribe to the batches of IBC events emitted by the given chain.
+    pub fn subscribe(&self, chain_id: &ChainId) -> Result<Subscription, RelayerBuilderError> {
+        self.chain(chain_id)?
+            .subscribe()
+            .map_err(RelayerBuilderError::relayer)
+    }
+
+    /// Submit an ICS-20 fungible token transfer from `src_chain_id`
+    /// to `dst_chain_id`, and wait for it to be committed.
+    pub fn transfer(
+        &self,
+        src_chain_id: &ChainId,
+        dst_chain_id: &ChainId,
+        opts: &TransferOptions,
+    ) -> Result<Vec<IbcEventWithHeight>, RelayerBuilderError> {
+        let src_chain = self.chain(src_chain_id)?;
+        let dst_chain = self.chain(dst_chain_id)?;
+
+        build_and_send_transfer_messages(&src_chain, &dst_chain, opts)
+            .map_err(RelayerBuilderError::transfer)
+    }
+
+    /// Ask the supervisor to dump its internal state.
+    pub fn dump_state(&self) -> Result<SupervisorState, RelayerBuilderError> {
+        self.supervisor
+            .dump_state()
+            .map_err(RelayerBuilderError::supervisor)
+    }
+
+    /// Block until the supervisor terminates.
+    pub fn wait(self) {
+        self.supervisor.wait()
+    }
+
+    /// Stop the supervisor, then shut down all the chain runtimes.
+    pub fn shutdown(self) {
+        self.supervisor.shutdown();
+
+        let chain_ids = self
+            .registry
+            .read()
+            .chains()
+            .map(|chain| chain.id())
+            .collect::<Vec<_>>();
+
+        for chain_id in chain_ids {
+            self.registry.shutdown(&chain_id);
+        }
+    }
+}
@@ -4,11 +4,14 @@ pub mod counterparty;
 pub mod endpoint;
 pub mod handle;
 pub mod requests;
+pub mod rpc_stats;
 pub mod runtime;
 pub mod tracking;
 
 use serde::{de::Error, Deserialize, Serialize};
 
+use ibc_relayer_types::Height;
+
 // NOTE(new): When adding a variant to `ChainType`, make sure to update
 //            the `Deserialize` implementation below and the tests.
 //            See the NOTE(new) comments below.
@@ -37,6 +40,39 @@ impl<'de> Deserialize<'de> for ChainType {
     }
 }
 
+impl ChainType {
+    /// How the proofs queried from this type of chain relate to the height they were queried at.
+    pub fn proof_height_policy(&self) -> ProofHeightPolicy {
+        match self {
+            Self::CosmosSdk => ProofHeightPolicy::NextHeight,
+            // NOTE(new): Add a case here
+        }
+    }
+}
+
+/// The height of the consensus state against which a proof queried
+/// at some height must be verified on the counterparty chain.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ProofHeightPolicy {
+    /// The proof is checked against the app hash of the next block,
+    /// as with Tendermint, where the app hash of a block is only
+    /// committed to in the header of the block after it.
+    NextHeight,
+    /// The proof is checked against the state root of the block it was queried at.
+    SameHeight,
+}
+
+impl ProofHeightPolicy {
+    /// The height at which the counterparty client must have a consensus state
+    /// to verify a proof queried at `query_height`.
+    pub fn proof_height(&self, query_height: Height) -> Height {
+        match self {
+            Self::NextHeight => query_height.increment(),
+            Self::SameHeight => query_height,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -62,4 +98,19 @@ mod tests {
 
         assert!(matches!(parse("hello-world"), Err(_)));
     }
+
+    #[test]
+    fn proof_height() {
+        let height = Height::new(1, 10).unwrap();
+
+        assert_eq!(
+            ProofHeightPolicy::NextHeight.proof_height(height),
+            Height::new(1, 11).unwrap()
+        );
+        assert_eq!(ProofHeightPolicy::SameHeight.proof_height(height), height);
+        assert_eq!(
+            ChainType::CosmosSdk.proof_height_policy(),
+            ProofHeightPolicy::NextHeight
+        );
+    }
 }
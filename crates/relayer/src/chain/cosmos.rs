@@ -25,6 +25,7 @@ use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
 use ibc_proto::protobuf::Protobuf;
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::clients::ics07_tendermint::client_state::{
     AllowUpdate, ClientState as TmClientState,
@@ -72,15 +73,19 @@ use crate::chain::cosmos::batch::{
 use crate::chain::cosmos::encode::key_pair_to_signer;
 use crate::chain::cosmos::fee::maybe_register_counterparty_payee;
 use crate::chain::cosmos::gas::{calculate_fee, mul_ceil};
+use crate::chain::cosmos::gas_tuning::GasMultiplierTuner;
 use crate::chain::cosmos::query::account::get_or_fetch_account;
 use crate::chain::cosmos::query::balance::{query_all_balances, query_balance};
 use crate::chain::cosmos::query::consensus_state::query_consensus_state_heights;
 use crate::chain::cosmos::query::custom::cross_chain_query_via_rpc;
 use crate::chain::cosmos::query::denom_trace::query_denom_trace;
-use crate::chain::cosmos::query::fee::query_incentivized_packet;
+use crate::chain::cosmos::query::fee::{
+    query_counterparty_payee, query_incentivized_packet, query_incentivized_packets,
+};
 use crate::chain::cosmos::query::status::query_status;
 use crate::chain::cosmos::query::tx::{
-    filter_matching_event, query_packets_from_block, query_packets_from_txs, query_txs,
+    filter_matching_event, query_block_events, query_packets_from_block, query_packets_from_txs,
+    query_txs,
 };
 use crate::chain::cosmos::query::{abci_query, fetch_version_specs, packet_query, QueryResponse};
 use crate::chain::cosmos::types::account::Account;
@@ -88,11 +93,12 @@ use crate::chain::cosmos::types::config::TxConfig;
 use crate::chain::cosmos::types::gas::{
     default_gas_from_config, gas_multiplier_from_config, max_gas_from_config,
 };
-use crate::chain::endpoint::{ChainEndpoint, ChainStatus, HealthCheck};
+use crate::chain::endpoint::{ChainEndpoint, ChainStakingParams, ChainStatus, HealthCheck};
 use crate::chain::handle::Subscription;
 use crate::chain::requests::*;
-use crate::chain::tracking::TrackedMsgs;
+use crate::chain::tracking::{TrackedMsgs, TrackingId};
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+use crate::config::types::Memo;
 use crate::config::{parse_gas_prices, ChainConfig, GasPrice};
 use crate::consensus_state::AnyConsensusState;
 use crate::denom::DenomTrace;
@@ -114,6 +120,7 @@ pub mod encode;
 pub mod estimate;
 pub mod fee;
 pub mod gas;
+pub mod gas_tuning;
 pub mod query;
 pub mod retry;
 pub mod simulate;
@@ -170,6 +177,15 @@ impl CosmosSdkChain {
             .map_err(Error::key_base)
     }
 
+    /// The memo of a transaction carrying the messages tracked by `tracking_id`,
+    /// ie. the configured memo prefix with the tracking identifier appended,
+    /// so that the transaction can be matched with the relayer logs.
+    fn tx_memo(&self, tracking_id: TrackingId) -> Memo {
+        let mut memo = self.config.memo_prefix.clone();
+        memo.apply_suffix(&format!("tracking_id={tracking_id}"));
+        memo
+    }
+
     /// Fetches the trusting period as a `Duration` from the chain config.
     /// If no trusting period exists in the config, the trusting period is calculated
     /// as two-thirds of the `unbonding_period`.
@@ -191,7 +207,7 @@ impl CosmosSdkChain {
     /// Emits a log warning in case any error is encountered and
     /// exits early without doing subsequent validations.
     pub fn validate_params(&self) -> Result<(), Error> {
-        let unbonding_period = self.unbonding_period()?;
+        let unbonding_period = ChainEndpoint::query_staking_params(self)?.unbonding_period;
         let trusting_period = self.trusting_period(unbonding_period);
 
         // Check that the trusting period is greater than zero
@@ -283,6 +299,16 @@ impl CosmosSdkChain {
             ));
         }
 
+        if let Some(tuning) = &self.config.gas_multiplier_tuning {
+            if tuning.min.to_f64() > tuning.max.to_f64() {
+                return Err(Error::config_validation_gas_multiplier_tuning_bounds(
+                    self.id().clone(),
+                    tuning.min.to_f64(),
+                    tuning.max.to_f64(),
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -294,14 +320,9 @@ impl CosmosSdkChain {
             }
         );
 
-        let (mut event_monitor, monitor_tx) = EventMonitor::new(
-            self.config.id.clone(),
-            self.config.websocket_addr.clone(),
-            self.compat_mode,
-            self.config.batch_delay,
-            self.rt.clone(),
-        )
-        .map_err(Error::event_monitor)?;
+        let (mut event_monitor, monitor_tx) =
+            EventMonitor::from_config(&self.config, self.compat_mode, self.rt.clone())
+                .map_err(Error::event_monitor)?;
 
         event_monitor
             .init_subscriptions()
@@ -460,52 +481,9 @@ impl CosmosSdkChain {
         Ok(min_gas_price)
     }
 
-    /// The unbonding period of this chain
-    pub fn unbonding_period(&self) -> Result<Duration, Error> {
-        crate::time!(
-            "unbonding_period",
-            {
-                "src_chain": self.config().id.to_string(),
-            }
-        );
-
-        let unbonding_time = if self.config.ccv_consumer_chain {
-            self.query_ccv_consumer_chain_params()?
-                .unbonding_period
-                .ok_or_else(|| {
-                    Error::grpc_response_param("no unbonding time in staking params".to_string())
-                })?
-        } else {
-            self.query_staking_params()?.unbonding_time.ok_or_else(|| {
-                Error::grpc_response_param("no unbonding time in staking params".to_string())
-            })?
-        };
-
-        Ok(Duration::new(
-            unbonding_time.seconds as u64,
-            unbonding_time.nanos as u32,
-        ))
-    }
-
     /// The number of historical entries kept by this chain
     pub fn historical_entries(&self) -> Result<u32, Error> {
-        crate::time!(
-            "historical_entries",
-            {
-                "src_chain": self.config().id.to_string(),
-            }
-        );
-        if self.config.ccv_consumer_chain {
-            let ccv_parameters = self.query_ccv_consumer_chain_params()?;
-            ccv_parameters.historical_entries.try_into().map_err(|_| {
-                Error::invalid_historical_entries(
-                    self.id().clone(),
-                    ccv_parameters.historical_entries,
-                )
-            })
-        } else {
-            self.query_staking_params().map(|p| p.historical_entries)
-        }
+        ChainEndpoint::query_staking_params(self).map(|params| params.historical_entries)
     }
 
     /// Run a future to completion on the Tokio runtime.
@@ -648,6 +626,7 @@ impl CosmosSdkChain {
             }
         );
 
+        let tx_memo = self.tx_memo(tracked_msgs.tracking_id);
         let proto_msgs = tracked_msgs.msgs;
 
         let key_pair = self.key()?;
@@ -662,7 +641,7 @@ impl CosmosSdkChain {
                 &self.tx_config,
                 &key_pair,
                 account,
-                &self.config.memo_prefix,
+                &tx_memo,
                 proto_msgs,
             )
             .await
@@ -672,7 +651,7 @@ impl CosmosSdkChain {
                 &self.tx_config,
                 &key_pair,
                 account,
-                &self.config.memo_prefix,
+                &tx_memo,
                 proto_msgs,
             )
             .await
@@ -699,6 +678,7 @@ impl CosmosSdkChain {
             }
         );
 
+        let tx_memo = self.tx_memo(tracked_msgs.tracking_id);
         let proto_msgs = tracked_msgs.msgs;
 
         let key_pair = self.key()?;
@@ -712,7 +692,7 @@ impl CosmosSdkChain {
             &self.tx_config,
             &key_pair,
             account,
-            &self.config.memo_prefix,
+            &tx_memo,
             proto_msgs,
         )
         .await
@@ -862,7 +842,15 @@ impl ChainEndpoint for CosmosSdkChain {
         let grpc_addr = Uri::from_str(&config.grpc_addr.to_string())
             .map_err(|e| Error::invalid_uri(config.grpc_addr.to_string(), e))?;
 
-        let tx_config = TxConfig::try_from(&config)?;
+        let mut tx_config = TxConfig::try_from(&config)?;
+
+        tx_config.gas_config.gas_multiplier_tuner = config.gas_multiplier_tuning.map(|tuning| {
+            Arc::new(GasMultiplierTuner::new(
+                config.id.clone(),
+                gas_multiplier_from_config(&config),
+                &tuning,
+            ))
+        });
 
         // Retrieve the version specification of this chain
 
@@ -912,6 +900,13 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(subscription)
     }
 
+    fn subscribe_if_running(&mut self) -> Result<Option<Subscription>, Error> {
+        self.tx_monitor_cmd
+            .as_ref()
+            .map(|tx_monitor_cmd| tx_monitor_cmd.subscribe().map_err(Error::event_monitor))
+            .transpose()
+    }
+
     /// Does multiple RPC calls to the full node, to check for
     /// reachability and some basic APIs are available.
     ///
@@ -943,6 +938,18 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(HealthCheck::Healthy)
     }
 
+    /// Query the node's `/status` endpoint and report whether
+    /// `sync_info.catching_up` is `true`.
+    fn is_syncing(&self) -> Result<bool, Error> {
+        crate::telemetry!(query, self.id(), "status");
+
+        let status = self
+            .block_on(self.rpc_client.status())
+            .map_err(|e| Error::rpc(self.config.rpc_addr.clone(), e))?;
+
+        Ok(status.sync_info.catching_up)
+    }
+
     /// Fetch a header from the chain at the given height and verify it.
     fn verify_header(
         &mut self,
@@ -1060,6 +1067,10 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(balance)
     }
 
+    fn query_address_balances(&self, address: &str) -> Result<Vec<Balance>, Error> {
+        self.block_on(query_all_balances(&self.grpc_addr, address))
+    }
+
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
         let denom_trace = self.block_on(query_denom_trace(&self.grpc_addr, &hash))?;
 
@@ -1112,6 +1123,47 @@ impl ChainEndpoint for CosmosSdkChain {
         Ok(ChainStatus { height, timestamp })
     }
 
+    /// Query the staking parameters, taking them from the provider chain
+    /// parameters if this chain is a CCV consumer chain
+    fn query_staking_params(&self) -> Result<ChainStakingParams, Error> {
+        crate::time!(
+            "query_chain_staking_params",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+
+        let no_unbonding_time =
+            || Error::grpc_response_param("no unbonding time in staking params".to_string());
+
+        let (unbonding_time, historical_entries) = if self.config.ccv_consumer_chain {
+            let params = self.query_ccv_consumer_chain_params()?;
+            let historical_entries = params.historical_entries.try_into().map_err(|_| {
+                Error::invalid_historical_entries(self.id().clone(), params.historical_entries)
+            })?;
+
+            (
+                params.unbonding_period.ok_or_else(no_unbonding_time)?,
+                historical_entries,
+            )
+        } else {
+            let params = CosmosSdkChain::query_staking_params(self)?;
+
+            (
+                params.unbonding_time.ok_or_else(no_unbonding_time)?,
+                params.historical_entries,
+            )
+        };
+
+        Ok(ChainStakingParams {
+            unbonding_period: Duration::new(
+                unbonding_time.seconds as u64,
+                unbonding_time.nanos as u32,
+            ),
+            historical_entries,
+        })
+    }
+
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
@@ -1135,7 +1187,12 @@ impl ChainEndpoint for CosmosSdkChain {
         client = client
             .max_decoding_message_size(self.config().max_grpc_decoding_size.get_bytes() as usize);
 
-        let request = tonic::Request::new(request.into());
+        let height_param = AsciiMetadataValue::try_from(request.query_height)?;
+
+        let mut request = tonic::Request::new(request.into());
+        request
+            .metadata_mut()
+            .insert("x-cosmos-block-height", height_param);
         let response = self
             .block_on(client.client_states(request))
             .map_err(|e| Error::grpc_status(e, "query_clients".to_owned()))?
@@ -1285,6 +1342,13 @@ impl ChainEndpoint for CosmosSdkChain {
             matches!(include_proof, IncludeProof::Yes),
         )?;
 
+        if res.value.is_empty() {
+            return Err(Error::consensus_state_not_found(
+                request.client_id,
+                request.consensus_height,
+            ));
+        }
+
         let consensus_state = AnyConsensusState::decode_vec(&res.value).map_err(Error::decode)?;
 
         if !matches!(consensus_state, AnyConsensusState::Tendermint(_)) {
@@ -1370,7 +1434,12 @@ impl ChainEndpoint for CosmosSdkChain {
         client = client
             .max_decoding_message_size(self.config().max_grpc_decoding_size.get_bytes() as usize);
 
-        let request = tonic::Request::new(request.into());
+        let height_param = AsciiMetadataValue::try_from(request.query_height)?;
+
+        let mut request = tonic::Request::new(request.into());
+        request
+            .metadata_mut()
+            .insert("x-cosmos-block-height", height_param);
 
         let response = self
             .block_on(client.connections(request))
@@ -1555,7 +1624,12 @@ impl ChainEndpoint for CosmosSdkChain {
         client = client
             .max_decoding_message_size(self.config().max_grpc_decoding_size.get_bytes() as usize);
 
-        let request = tonic::Request::new(request.into());
+        let height_param = AsciiMetadataValue::try_from(request.query_height)?;
+
+        let mut request = tonic::Request::new(request.into());
+        request
+            .metadata_mut()
+            .insert("x-cosmos-block-height", height_param);
 
         let response = self
             .block_on(client.channels(request))
@@ -1697,7 +1771,12 @@ impl ChainEndpoint for CosmosSdkChain {
         client = client
             .max_decoding_message_size(self.config().max_grpc_decoding_size.get_bytes() as usize);
 
-        let request = tonic::Request::new(request.into());
+        let height_param = AsciiMetadataValue::try_from(request.query_height)?;
+
+        let mut request = tonic::Request::new(request.into());
+        request
+            .metadata_mut()
+            .insert("x-cosmos-block-height", height_param);
 
         let response = self
             .block_on(client.packet_commitments(request))
@@ -1821,10 +1900,6 @@ impl ChainEndpoint for CosmosSdkChain {
             }
         );
 
-        if request.packet_commitment_sequences.is_empty() {
-            return Ok((Vec::new(), self.query_chain_latest_height()?));
-        }
-
         let mut client = self
             .block_on(
                 ibc_proto::ibc::core::channel::v1::query_client::QueryClient::connect(
@@ -1836,7 +1911,12 @@ impl ChainEndpoint for CosmosSdkChain {
         client = client
             .max_decoding_message_size(self.config().max_grpc_decoding_size.get_bytes() as usize);
 
-        let request = tonic::Request::new(request.into());
+        let height_param = AsciiMetadataValue::try_from(request.query_height)?;
+
+        let mut request = tonic::Request::new(request.into());
+        request
+            .metadata_mut()
+            .insert("x-cosmos-block-height", height_param);
 
         let response = self
             .block_on(client.packet_acknowledgements(request))
@@ -1971,6 +2051,22 @@ impl ChainEndpoint for CosmosSdkChain {
         ))
     }
 
+    fn query_block_events(&self, height: ICSHeight) -> Result<Vec<IbcEventWithHeight>, Error> {
+        crate::time!(
+            "query_block_events",
+            {
+                "src_chain": self.config().id.to_string(),
+            }
+        );
+
+        self.block_on(query_block_events(
+            self.id(),
+            &self.rpc_client,
+            &self.config.rpc_addr,
+            height,
+        ))
+    }
+
     /// This function queries transactions for packet events matching certain criteria.
     /// It returns at most one packet event for each sequence specified in the request.
     ///    Note - there is no way to format the packet query such that it asks for Tx-es with either
@@ -2087,7 +2183,7 @@ impl ChainEndpoint for CosmosSdkChain {
         settings: ClientSettings,
     ) -> Result<Self::ClientState, Error> {
         let ClientSettings::Tendermint(settings) = settings;
-        let unbonding_period = self.unbonding_period()?;
+        let unbonding_period = ChainEndpoint::query_staking_params(self)?.unbonding_period;
         let trusting_period = settings
             .trusting_period
             .unwrap_or_else(|| self.trusting_period(unbonding_period));
@@ -2201,6 +2297,34 @@ impl ChainEndpoint for CosmosSdkChain {
             self.block_on(query_incentivized_packet(&self.grpc_addr, request))?;
         Ok(incentivized_response)
     }
+
+    fn query_incentivized_packets(
+        &self,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        crate::telemetry!(query, self.id(), "query_incentivized_packets");
+
+        self.block_on(query_incentivized_packets(
+            &self.grpc_addr,
+            channel_id,
+            port_id,
+        ))
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        channel_id: &ChannelId,
+        address: &Signer,
+    ) -> Result<Option<String>, Error> {
+        crate::telemetry!(query, self.id(), "query_counterparty_payee");
+
+        self.block_on(query_counterparty_payee(
+            &self.grpc_addr,
+            channel_id,
+            address,
+        ))
+    }
 }
 
 fn sort_events_by_sequence(events: &mut [IbcEventWithHeight]) {
@@ -48,6 +48,7 @@ pub async fn send_batched_messages_and_wait_commit(
         rpc_client,
         &config.rpc_address,
         &config.rpc_timeout,
+        config.gas_config.gas_multiplier_tuner.as_deref(),
         &mut tx_sync_results,
     )
     .await?;
@@ -201,6 +202,7 @@ async fn sequential_send_messages_as_batches(
             rpc_client,
             &config.rpc_address,
             &config.rpc_timeout,
+            config.gas_config.gas_multiplier_tuner.as_deref(),
             &mut tx_sync_results,
         )
         .await?;
@@ -5,7 +5,8 @@ use tonic::codegen::http::Uri;
 use tracing::{debug, error, span, warn, Level};
 
 use crate::chain::cosmos::encode::sign_tx;
-use crate::chain::cosmos::gas::gas_amount_to_fee;
+use crate::chain::cosmos::gas::gas_amount_to_fee_with_multiplier;
+use crate::chain::cosmos::gas_tuning::message_type;
 use crate::chain::cosmos::simulate::send_tx_simulate;
 use crate::chain::cosmos::types::account::Account;
 use crate::chain::cosmos::types::config::TxConfig;
@@ -15,13 +16,21 @@ use crate::error::Error;
 use crate::keyring::Secp256k1KeyPair;
 use crate::util::pretty::PrettyFee;
 
+/// The fee to pay for a transaction, and the gas estimate it is computed from.
+pub struct FeeEstimate {
+    pub fee: Fee,
+    /// The gas used by the simulation of the transaction, if it could be
+    /// simulated, or `None` if the estimate fell back on the default gas
+    pub simulated_gas: Option<u64>,
+}
+
 pub async fn estimate_tx_fees(
     config: &TxConfig,
     key_pair: &Secp256k1KeyPair,
     account: &Account,
     tx_memo: &Memo,
     messages: &[Any],
-) -> Result<Fee, Error> {
+) -> Result<FeeEstimate, Error> {
     let gas_config = &config.gas_config;
 
     debug!(
@@ -44,8 +53,14 @@ pub async fn estimate_tx_fees(
         signatures: signed_tx.signatures,
     };
 
-    let estimated_fee =
-        estimate_fee_with_tx(gas_config, &config.grpc_address, &config.chain_id, tx).await?;
+    let estimated_fee = estimate_fee_with_tx(
+        gas_config,
+        &config.grpc_address,
+        &config.chain_id,
+        tx,
+        message_type(messages),
+    )
+    .await?;
 
     Ok(estimated_fee)
 }
@@ -55,8 +70,9 @@ async fn estimate_fee_with_tx(
     grpc_address: &Uri,
     chain_id: &ChainId,
     tx: Tx,
-) -> Result<Fee, Error> {
-    let estimated_gas = {
+    message_type: &str,
+) -> Result<FeeEstimate, Error> {
+    let simulated_gas = {
         crate::time!(
             "estimate_gas_with_tx",
             {
@@ -67,6 +83,8 @@ async fn estimate_fee_with_tx(
         estimate_gas_with_tx(gas_config, grpc_address, tx).await
     }?;
 
+    let estimated_gas = simulated_gas.unwrap_or(gas_config.default_gas);
+
     if estimated_gas > gas_config.max_gas {
         debug!(
             id = %chain_id, estimated = ?estimated_gas, max = ?gas_config.max_gas,
@@ -80,7 +98,12 @@ async fn estimate_fee_with_tx(
         ));
     }
 
-    let adjusted_fee = gas_amount_to_fee(gas_config, estimated_gas);
+    let gas_multiplier = match &gas_config.gas_multiplier_tuner {
+        Some(tuner) => tuner.multiplier(message_type),
+        None => gas_config.gas_multiplier,
+    };
+
+    let adjusted_fee = gas_amount_to_fee_with_multiplier(gas_config, estimated_gas, gas_multiplier);
 
     debug!(
         id = %chain_id,
@@ -89,7 +112,10 @@ async fn estimate_fee_with_tx(
         PrettyFee(&adjusted_fee)
     );
 
-    Ok(adjusted_fee)
+    Ok(FeeEstimate {
+        fee: adjusted_fee,
+        simulated_gas,
+    })
 }
 
 /// Try to simulate the given tx in order to estimate how much gas will be needed to submit it.
@@ -99,12 +125,12 @@ async fn estimate_fee_with_tx(
 /// [`MsgUpdateClient`, `MsgRecvPacket`, ..., `MsgRecvPacket`]
 ///
 /// If the batch is split in two TX-es, the second one will fail the simulation in `deliverTx` check.
-/// In this case we use the `default_gas` param.
+/// In this case we fall back on the `default_gas` param, and return `None`.
 async fn estimate_gas_with_tx(
     gas_config: &GasConfig,
     grpc_address: &Uri,
     tx: Tx,
-) -> Result<u64, Error> {
+) -> Result<Option<u64>, Error> {
     let simulated_gas = send_tx_simulate(grpc_address, tx)
         .await
         .map(|sr| sr.gas_info);
@@ -118,7 +144,7 @@ async fn estimate_gas_with_tx(
                 gas_info.gas_used
             );
 
-            Ok(gas_info.gas_used)
+            Ok(Some(gas_info.gas_used))
         }
 
         Ok(None) => {
@@ -127,7 +153,7 @@ async fn estimate_gas_with_tx(
                 gas_config.default_gas
             );
 
-            Ok(gas_config.default_gas)
+            Ok(None)
         }
 
         // If there is a chance that the tx will be accepted once actually submitted, we fall
@@ -139,7 +165,7 @@ async fn estimate_gas_with_tx(
                 e.detail()
             );
 
-            Ok(gas_config.default_gas)
+            Ok(None)
         }
 
         Err(e) => {
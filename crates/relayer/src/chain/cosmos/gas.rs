@@ -8,8 +8,17 @@ use crate::chain::cosmos::types::gas::GasConfig;
 use crate::config::GasPrice;
 
 pub fn gas_amount_to_fee(config: &GasConfig, gas_amount: u64) -> Fee {
+    gas_amount_to_fee_with_multiplier(config, gas_amount, config.gas_multiplier)
+}
+
+/// Same as [`gas_amount_to_fee`], using the given gas multiplier instead of the configured one.
+pub fn gas_amount_to_fee_with_multiplier(
+    config: &GasConfig,
+    gas_amount: u64,
+    gas_multiplier: f64,
+) -> Fee {
     let adjusted_gas_limit = adjust_estimated_gas(AdjustGas {
-        gas_multiplier: config.gas_multiplier,
+        gas_multiplier,
         max_gas: config.max_gas,
         gas_amount,
     });
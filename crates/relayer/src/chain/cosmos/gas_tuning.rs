@@ -0,0 +1,302 @@
+//! Tuning of the gas multiplier of a chain, per type of message, from the gas
+//! used by the transactions submitted to the chain compared to their simulated gas.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use ibc_proto::google::protobuf::Any;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use tendermint::Hash as TxHash;
+use tracing::{debug, warn};
+
+use crate::config::GasMultiplierTuning;
+
+const STATE_FOLDER: &str = ".hermes/gas_multipliers";
+
+/// Maximum number of transactions awaiting confirmation which are tracked.
+const MAX_TRACKED_TXS: usize = 1000;
+
+/// Margin kept above the ratio of used to simulated gas when raising the multiplier.
+const SAFETY_MARGIN: f64 = 1.05;
+
+/// Weight of a new observation when lowering the multiplier.
+const DECREASE_WEIGHT: f64 = 0.1;
+
+/// Factor by which the multiplier is raised when a transaction runs out of gas.
+const OUT_OF_GAS_INCREASE: f64 = 1.2;
+
+/// Smallest change of a multiplier for which the tuned multipliers are persisted again.
+const PERSIST_THRESHOLD: f64 = 0.01;
+
+/// Tunes the gas multiplier used for each type of message within the configured bounds,
+/// raising it quickly when transactions need more gas than simulated and lowering
+/// it gradually when they need less.
+#[derive(Debug)]
+pub struct GasMultiplierTuner {
+    chain_id: ChainId,
+    path: Option<PathBuf>,
+    initial: f64,
+    min: f64,
+    max: f64,
+    state: Mutex<TunerState>,
+}
+
+#[derive(Debug)]
+struct TunerState {
+    multipliers: BTreeMap<String, f64>,
+    persisted: BTreeMap<String, f64>,
+    tracked: VecDeque<TrackedTx>,
+}
+
+#[derive(Debug)]
+struct TrackedTx {
+    hash: TxHash,
+    message_type: String,
+    simulated_gas: u64,
+}
+
+impl GasMultiplierTuner {
+    /// Create a tuner starting from the given gas multiplier, and from the
+    /// multipliers persisted for the chain on a previous run, if any.
+    pub fn new(chain_id: ChainId, gas_multiplier: f64, tuning: &GasMultiplierTuning) -> Self {
+        let path = dirs_next::home_dir()
+            .map(|home| home.join(STATE_FOLDER).join(format!("{}.json", chain_id)));
+
+        if path.is_none() {
+            warn!(
+                chain = %chain_id,
+                "home location is unavailable, tuned gas multipliers will not be persisted"
+            );
+        }
+
+        Self::with_path(chain_id, gas_multiplier, tuning, path)
+    }
+
+    fn with_path(
+        chain_id: ChainId,
+        gas_multiplier: f64,
+        tuning: &GasMultiplierTuning,
+        path: Option<PathBuf>,
+    ) -> Self {
+        let min = tuning.min.to_f64();
+        let max = tuning.max.to_f64().max(min);
+
+        let multipliers = path.as_deref().map(load).unwrap_or_default();
+
+        Self {
+            chain_id,
+            path,
+            initial: gas_multiplier.clamp(min, max),
+            min,
+            max,
+            state: Mutex::new(TunerState {
+                persisted: multipliers.clone(),
+                multipliers,
+                tracked: VecDeque::new(),
+            }),
+        }
+    }
+
+    /// The gas multiplier to use for a transaction with the given type of messages.
+    pub fn multiplier(&self, message_type: &str) -> f64 {
+        let state = self.state.lock().expect("poisoned lock");
+
+        state
+            .multipliers
+            .get(message_type)
+            .map(|multiplier| multiplier.clamp(self.min, self.max))
+            .unwrap_or(self.initial)
+    }
+
+    /// Track a transaction broadcast with the given simulated gas,
+    /// until its result is observed.
+    pub fn track(&self, hash: TxHash, message_type: &str, simulated_gas: u64) {
+        let mut state = self.state.lock().expect("poisoned lock");
+
+        if state.tracked.len() == MAX_TRACKED_TXS {
+            state.tracked.pop_front();
+        }
+
+        state.tracked.push_back(TrackedTx {
+            hash,
+            message_type: message_type.to_string(),
+            simulated_gas,
+        });
+    }
+
+    /// Tune the multiplier of the type of messages of the given transaction,
+    /// if tracked, from the gas it used once executed.
+    pub fn observe(&self, hash: &TxHash, gas_used: u64, out_of_gas: bool) {
+        let mut state = self.state.lock().expect("poisoned lock");
+
+        let Some(position) = state.tracked.iter().position(|tx| &tx.hash == hash) else {
+            return;
+        };
+
+        let tx = state
+            .tracked
+            .remove(position)
+            .expect("position is within bounds");
+
+        let current = state
+            .multipliers
+            .get(&tx.message_type)
+            .copied()
+            .unwrap_or(self.initial);
+
+        let tuned = tune(current, tx.simulated_gas, gas_used, out_of_gas).clamp(self.min, self.max);
+
+        if tuned != current {
+            debug!(
+                chain = %self.chain_id,
+                message_type = %tx.message_type,
+                simulated_gas = tx.simulated_gas,
+                gas_used,
+                out_of_gas,
+                "tuning gas multiplier from {:.3} to {:.3}",
+                current,
+                tuned
+            );
+        }
+
+        state.multipliers.insert(tx.message_type.clone(), tuned);
+
+        let changed = match state.persisted.get(&tx.message_type) {
+            Some(persisted) => (tuned - persisted).abs() >= PERSIST_THRESHOLD,
+            None => true,
+        };
+
+        if changed {
+            if let Some(path) = &self.path {
+                persist(path, &state.multipliers);
+            }
+
+            state.persisted = state.multipliers.clone();
+        }
+    }
+}
+
+/// The type of the messages of a transaction for the purpose of tuning, ie.
+/// the type of its last message, since the client updates prepended to a
+/// batch of packet messages do not vary much in gas.
+pub fn message_type(messages: &[Any]) -> &str {
+    messages
+        .last()
+        .map(|message| message.type_url.as_str())
+        .unwrap_or_default()
+}
+
+fn tune(current: f64, simulated_gas: u64, gas_used: u64, out_of_gas: bool) -> f64 {
+    if out_of_gas {
+        return current * OUT_OF_GAS_INCREASE;
+    }
+
+    if simulated_gas == 0 {
+        return current;
+    }
+
+    let target = gas_used as f64 / simulated_gas as f64 * SAFETY_MARGIN;
+
+    if target > current {
+        target
+    } else {
+        current + DECREASE_WEIGHT * (target - current)
+    }
+}
+
+fn load(path: &Path) -> BTreeMap<String, f64> {
+    match fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes).unwrap_or_else(|e| {
+            warn!(
+                "ignoring the tuned gas multipliers in {}, which cannot be decoded: {}",
+                path.display(),
+                e
+            );
+
+            BTreeMap::new()
+        }),
+        Err(_) => BTreeMap::new(),
+    }
+}
+
+fn persist(path: &Path, multipliers: &BTreeMap<String, f64>) {
+    let result = path
+        .parent()
+        .map_or(Ok(()), fs::create_dir_all)
+        .and_then(|_| {
+            let json =
+                serde_json::to_vec_pretty(multipliers).expect("multipliers are serializable");
+            fs::write(path, json)
+        });
+
+    if let Err(e) = result {
+        warn!(
+            "failed to persist the tuned gas multipliers to {}: {}",
+            path.display(),
+            e
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::config::gas_multiplier::GasMultiplier;
+
+    const RECV_PACKET: &str = "/ibc.core.channel.v1.MsgRecvPacket";
+
+    fn tuner() -> GasMultiplierTuner {
+        let tuning = GasMultiplierTuning {
+            min: GasMultiplier::unsafe_new(1.05),
+            max: GasMultiplier::unsafe_new(1.5),
+        };
+
+        GasMultiplierTuner::with_path(ChainId::from_string("ibc-0"), 1.1, &tuning, None)
+    }
+
+    fn observe(tuner: &GasMultiplierTuner, simulated_gas: u64, gas_used: u64, out_of_gas: bool) {
+        let hash = TxHash::Sha256([simulated_gas as u8; 32]);
+
+        tuner.track(hash, RECV_PACKET, simulated_gas);
+        tuner.observe(&hash, gas_used, out_of_gas);
+    }
+
+    #[test]
+    fn raises_and_lowers_within_bounds() {
+        let tuner = tuner();
+
+        // Used 20% more gas than simulated
+        observe(&tuner, 100_000, 120_000, false);
+        assert_eq!(tuner.multiplier(RECV_PACKET), 1.2 * SAFETY_MARGIN);
+
+        // Out of gas, raised up to the upper bound
+        observe(&tuner, 100_000, 130_000, true);
+        assert_eq!(tuner.multiplier(RECV_PACKET), 1.5);
+
+        // Used as much gas as simulated, lowered gradually
+        observe(&tuner, 100_000, 100_000, false);
+        let lowered = tuner.multiplier(RECV_PACKET);
+        assert!(lowered < 1.5 && lowered > SAFETY_MARGIN);
+
+        for _ in 0..100 {
+            observe(&tuner, 100_000, 90_000, false);
+        }
+
+        assert_eq!(tuner.multiplier(RECV_PACKET), 1.05);
+
+        // Other message types are not affected
+        assert_eq!(tuner.multiplier("/ibc.core.client.v1.MsgUpdateClient"), 1.1);
+    }
+
+    #[test]
+    fn ignores_untracked_transactions() {
+        let tuner = tuner();
+
+        tuner.observe(&TxHash::Sha256([1; 32]), 1_000_000, true);
+
+        assert_eq!(tuner.multiplier(RECV_PACKET), 1.1);
+    }
+}
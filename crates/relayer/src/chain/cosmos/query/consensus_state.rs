@@ -1,4 +1,5 @@
 use http::Uri;
+use tonic::metadata::AsciiMetadataValue;
 use tracing::{debug, warn};
 
 use ibc_relayer_types::{core::ics24_host::identifier::ChainId, Height};
@@ -41,7 +42,13 @@ pub async fn query_consensus_state_heights(
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
-    let grpc_request = tonic::Request::new(request.clone().into());
+    let height_param = AsciiMetadataValue::try_from(request.query_height)?;
+
+    let mut grpc_request = tonic::Request::new(request.clone().into());
+    grpc_request
+        .metadata_mut()
+        .insert("x-cosmos-block-height", height_param);
+
     let grpc_response = client.consensus_state_heights(grpc_request).await;
 
     if let Err(ref e) = grpc_response {
@@ -54,6 +61,7 @@ pub async fn query_consensus_state_heights(
                 QueryConsensusStatesRequest {
                     client_id: request.client_id,
                     pagination: request.pagination,
+                    query_height: request.query_height,
                 },
             )
             .await?;
@@ -108,8 +116,15 @@ pub async fn query_consensus_states(
 
     client = client.max_decoding_message_size(max_grpc_decoding_size().get_bytes() as usize);
 
+    let height_param = AsciiMetadataValue::try_from(request.query_height)?;
+
+    let mut request = tonic::Request::new(request.into());
+    request
+        .metadata_mut()
+        .insert("x-cosmos-block-height", height_param);
+
     let response = client
-        .consensus_states(tonic::Request::new(request.into()))
+        .consensus_states(request)
         .await
         .map_err(|e| Error::grpc_status(e, "query_consensus_states".to_owned()))?
         .into_inner();
@@ -15,6 +15,7 @@ use crate::chain::requests::{
     QueryClientEventRequest, QueryHeight, QueryPacketEventDataRequest, QueryTxHash, QueryTxRequest,
 };
 use crate::error::Error;
+use crate::event::rpc::get_block_results_events;
 use crate::event::{ibc_event_try_from_abci_event, IbcEventWithHeight};
 
 /// This function queries transactions for events matching certain criteria.
@@ -242,6 +243,27 @@ pub async fn query_packets_from_block(
     Ok(events)
 }
 
+/// Query all the IBC events emitted in the block at the given height via `/block_results`,
+/// in the order in which they were emitted, preceded by a `NewBlock` event.
+pub async fn query_block_events(
+    chain_id: &ChainId,
+    rpc_client: &HttpClient,
+    rpc_address: &Url,
+    height: Height,
+) -> Result<Vec<IbcEventWithHeight>, Error> {
+    crate::telemetry!(query, chain_id, "query_block_events");
+
+    let tm_height = tendermint::block::Height::try_from(height.revision_height())
+        .map_err(|_| Error::invalid_height_no_source())?;
+
+    let block_results = rpc_client
+        .block_results(tm_height)
+        .await
+        .map_err(|e| Error::rpc(rpc_address.clone(), e))?;
+
+    Ok(get_block_results_events(chain_id, height, &block_results))
+}
+
 // Extracts from the Tx the update client event for the requested client and height.
 // Note: in the Tx, there may have been multiple events, some of them may be
 // for update of other clients that are not relevant to the request.
@@ -6,6 +6,7 @@ use tendermint_rpc::{Client, HttpClient, Url};
 
 use crate::chain::cosmos::encode::sign_and_encode_tx;
 use crate::chain::cosmos::estimate::estimate_tx_fees;
+use crate::chain::cosmos::gas_tuning::message_type;
 use crate::chain::cosmos::query::account::query_account;
 use crate::chain::cosmos::query::tx::all_ibc_events_from_tx_search_response;
 use crate::chain::cosmos::types::account::Account;
@@ -26,12 +27,29 @@ pub async fn estimate_fee_and_send_tx(
     tx_memo: &Memo,
     messages: &[Any],
 ) -> Result<Response, Error> {
-    let fee = estimate_tx_fees(config, key_pair, account, tx_memo, messages).await?;
+    let estimate = estimate_tx_fees(config, key_pair, account, tx_memo, messages).await?;
 
-    send_tx_with_fee(
-        rpc_client, config, key_pair, account, tx_memo, messages, &fee,
+    let response = send_tx_with_fee(
+        rpc_client,
+        config,
+        key_pair,
+        account,
+        tx_memo,
+        messages,
+        &estimate.fee,
     )
-    .await
+    .await?;
+
+    if let (Some(tuner), Some(simulated_gas)) = (
+        &config.gas_config.gas_multiplier_tuner,
+        estimate.simulated_gas,
+    ) {
+        if response.code.is_ok() {
+            tuner.track(response.hash, message_type(messages), simulated_gas);
+        }
+    }
+
+    Ok(response)
 }
 
 async fn send_tx_with_fee(
@@ -5,9 +5,10 @@ use ibc_relayer_types::core::ics04_channel::error::Error;
 use ibc_relayer_types::core::ics04_channel::events::{
     AcknowledgePacket, Attributes, CloseConfirm, CloseInit, EventType, OpenAck, OpenConfirm,
     OpenInit, OpenTry, SendPacket, TimeoutPacket, WriteAcknowledgement, PKT_ACK_ATTRIBUTE_KEY,
-    PKT_DATA_ATTRIBUTE_KEY, PKT_DST_CHANNEL_ATTRIBUTE_KEY, PKT_DST_PORT_ATTRIBUTE_KEY,
-    PKT_SEQ_ATTRIBUTE_KEY, PKT_SRC_CHANNEL_ATTRIBUTE_KEY, PKT_SRC_PORT_ATTRIBUTE_KEY,
-    PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY, PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
+    PKT_ACK_HEX_ATTRIBUTE_KEY, PKT_DATA_ATTRIBUTE_KEY, PKT_DATA_HEX_ATTRIBUTE_KEY,
+    PKT_DST_CHANNEL_ATTRIBUTE_KEY, PKT_DST_PORT_ATTRIBUTE_KEY, PKT_SEQ_ATTRIBUTE_KEY,
+    PKT_SRC_CHANNEL_ATTRIBUTE_KEY, PKT_SRC_PORT_ATTRIBUTE_KEY, PKT_TIMEOUT_HEIGHT_ATTRIBUTE_KEY,
+    PKT_TIMEOUT_TIMESTAMP_ATTRIBUTE_KEY,
 };
 use ibc_relayer_types::core::ics04_channel::events::{ReceivePacket, TimeoutOnClosePacket};
 use ibc_relayer_types::core::ics04_channel::packet::Packet;
@@ -15,6 +16,8 @@ use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
 use ibc_relayer_types::events::Error as EventError;
 use ibc_relayer_types::Height;
 
+use crate::event::decode_hex_packet_attribute;
+
 fn extract_attributes(object: &RawObject<'_>, namespace: &str) -> Result<Attributes, EventError> {
     Ok(Attributes {
         port_id: extract_attribute(object, &format!("{namespace}.port_id"))?
@@ -66,10 +69,10 @@ macro_rules! impl_try_from_raw_obj_for_packet {
             type Error = EventError;
 
             fn try_from(obj: RawObject<'_>) -> Result<Self, Self::Error> {
-                let data_str: String = extract_attribute(&obj, &format!("{}.{}", obj.action, PKT_DATA_ATTRIBUTE_KEY))?;
+                let data = extract_packet_data(&obj)?;
 
                 let mut packet = Packet::try_from(obj)?;
-                packet.data = Vec::from(data_str.as_str().as_bytes());
+                packet.data = data;
 
                 Ok(Self { packet })
             }
@@ -89,18 +92,34 @@ impl TryFrom<RawObject<'_>> for WriteAcknowledgement {
     type Error = EventError;
 
     fn try_from(obj: RawObject<'_>) -> Result<Self, Self::Error> {
-        let data_str: String =
-            extract_attribute(&obj, &format!("{}.{}", obj.action, PKT_DATA_ATTRIBUTE_KEY))?;
-        let ack = extract_attribute(&obj, &format!("{}.{}", obj.action, PKT_ACK_ATTRIBUTE_KEY))?
-            .into_bytes();
+        let data = extract_packet_data(&obj)?;
+        let ack =
+            extract_hex_or_raw_attribute(&obj, PKT_ACK_HEX_ATTRIBUTE_KEY, PKT_ACK_ATTRIBUTE_KEY)?;
 
         let mut packet = Packet::try_from(obj)?;
-        packet.data = Vec::from(data_str.as_str().as_bytes());
+        packet.data = data;
 
         Ok(Self { packet, ack })
     }
 }
 
+fn extract_packet_data(obj: &RawObject<'_>) -> Result<Vec<u8>, EventError> {
+    extract_hex_or_raw_attribute(obj, PKT_DATA_HEX_ATTRIBUTE_KEY, PKT_DATA_ATTRIBUTE_KEY)
+}
+
+/// Extract the bytes of a packet attribute, preferring its hex-encoded
+/// variant `hex_key` if present and falling back to the raw `key` otherwise.
+fn extract_hex_or_raw_attribute(
+    obj: &RawObject<'_>,
+    hex_key: &str,
+    key: &str,
+) -> Result<Vec<u8>, EventError> {
+    match maybe_extract_attribute(obj, &format!("{}.{}", obj.action, hex_key)) {
+        Some(value) => decode_hex_packet_attribute(hex_key, &value).map_err(EventError::channel),
+        None => Ok(extract_attribute(obj, &format!("{}.{}", obj.action, key))?.into_bytes()),
+    }
+}
+
 /// Parse a string into a timeout height expected to be stored in
 /// `Packet.timeout_height`. We need to parse the timeout height differently
 /// because of a quirk introduced in ibc-go. See comment in
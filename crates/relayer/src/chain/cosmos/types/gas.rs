@@ -1,6 +1,9 @@
+use alloc::sync::Arc;
+
 use ibc_proto::cosmos::tx::v1beta1::Fee;
 
 use crate::chain::cosmos::calculate_fee;
+use crate::chain::cosmos::gas_tuning::GasMultiplierTuner;
 use crate::config::{ChainConfig, GasPrice};
 
 /// Default gas limit when submitting a transaction.
@@ -16,6 +19,8 @@ pub struct GasConfig {
     pub gas_price: GasPrice,
     pub max_fee: Fee,
     pub fee_granter: String,
+    /// Tunes the gas multiplier per type of message, if enabled for the chain
+    pub gas_multiplier_tuner: Option<Arc<GasMultiplierTuner>>,
 }
 
 impl<'a> From<&'a ChainConfig> for GasConfig {
@@ -27,6 +32,7 @@ impl<'a> From<&'a ChainConfig> for GasConfig {
             gas_price: config.gas_price.clone(),
             max_fee: max_fee_from_config(config),
             fee_granter: fee_granter_from_config(config),
+            gas_multiplier_tuner: None,
         }
     }
 }
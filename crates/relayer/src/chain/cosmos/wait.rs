@@ -11,6 +11,7 @@ use tendermint_rpc::{HttpClient, Url};
 use tokio::time::sleep;
 use tracing::{debug, debug_span, trace};
 
+use crate::chain::cosmos::gas_tuning::GasMultiplierTuner;
 use crate::chain::cosmos::query::tx::query_tx_response;
 use crate::chain::cosmos::types::events::from_tx_response_event;
 use crate::chain::cosmos::types::tx::{TxStatus, TxSyncResult};
@@ -19,14 +20,20 @@ use crate::event::IbcEventWithHeight;
 
 const WAIT_BACKOFF: Duration = Duration::from_millis(300);
 
+/// Code of the "out of gas" error in the `sdk` codespace.
+const OUT_OF_GAS_CODE: u32 = 11;
+
 /// Given a vector of `TxSyncResult` elements,
 /// each including a transaction response hash for one or more messages, periodically queries the chain
 /// with the transaction hashes to get the list of IbcEvents included in those transactions.
+///
+/// The gas used by each transaction is reported to the gas multiplier tuner, if any.
 pub async fn wait_for_block_commits(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
     rpc_timeout: &Duration,
+    gas_multiplier_tuner: Option<&GasMultiplierTuner>,
     tx_sync_results: &mut [TxSyncResult],
 ) -> Result<(), Error> {
     if all_tx_results_found(tx_sync_results) {
@@ -68,8 +75,14 @@ pub async fn wait_for_block_commits(
             thread::sleep(WAIT_BACKOFF);
 
             for tx_sync_result in tx_sync_results.iter_mut() {
-                let res =
-                    update_tx_sync_result(chain_id, rpc_client, rpc_address, tx_sync_result).await;
+                let res = update_tx_sync_result(
+                    chain_id,
+                    rpc_client,
+                    rpc_address,
+                    gas_multiplier_tuner,
+                    tx_sync_result,
+                )
+                .await;
                 if let Err(e) = res {
                     debug!("update_tx_sync_result failed: {e}");
                 }
@@ -82,6 +95,7 @@ async fn update_tx_sync_result(
     chain_id: &ChainId,
     rpc_client: &HttpClient,
     rpc_address: &Url,
+    gas_multiplier_tuner: Option<&GasMultiplierTuner>,
     tx_sync_result: &mut TxSyncResult,
 ) -> Result<(), Error> {
     if let TxStatus::Pending { message_count } = tx_sync_result.status {
@@ -91,6 +105,17 @@ async fn update_tx_sync_result(
         if let Some(response) = response {
             tx_sync_result.status = TxStatus::ReceivedResponse;
 
+            if let Some(tuner) = gas_multiplier_tuner {
+                let out_of_gas = response.tx_result.codespace == "sdk"
+                    && response.tx_result.code.value() == OUT_OF_GAS_CODE;
+
+                tuner.observe(
+                    &response.hash,
+                    response.tx_result.gas_used as u64,
+                    out_of_gas,
+                );
+            }
+
             let height = Height::new(chain_id.version(), u64::from(response.height)).unwrap();
             if response.tx_result.code.is_err() {
                 tx_sync_result.events = vec![
@@ -66,6 +66,34 @@ pub fn counterparty_chain_from_connection(
     Ok(client_state.chain_id())
 }
 
+/// Returns the channels built on top of the connections of the given client,
+/// ie. the channels whose packets are verified by this client.
+pub fn client_channels(
+    chain: &impl ChainHandle,
+    client_id: &ClientId,
+) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+    let connection_ids = chain
+        .query_client_connections(QueryClientConnectionsRequest {
+            client_id: client_id.clone(),
+        })
+        .map_err(Error::relayer)?;
+
+    let mut channels = vec![];
+
+    for connection_id in connection_ids {
+        let connection_channels = chain
+            .query_connection_channels(QueryConnectionChannelsRequest {
+                connection_id,
+                pagination: Some(PageRequest::all()),
+            })
+            .map_err(Error::relayer)?;
+
+        channels.extend(connection_channels);
+    }
+
+    Ok(channels)
+}
+
 fn connection_on_destination(
     connection_id_on_source: &ConnectionId,
     counterparty_client_id: &ClientId,
@@ -384,6 +412,7 @@ pub fn commitments_on_chain(
             port_id: port_id.clone(),
             channel_id: channel_id.clone(),
             pagination: Some(PageRequest::all()),
+            query_height: QueryHeight::Latest,
         })
         .map_err(Error::relayer)?;
 
@@ -436,6 +465,7 @@ pub fn packet_acknowledgements(
             port_id: port_id.clone(),
             channel_id: channel_id.clone(),
             pagination: Some(PageRequest::all()),
+            query_height: QueryHeight::Latest,
             packet_commitment_sequences: commit_sequences,
         })
         .map_err(Error::relayer)?;
@@ -1,11 +1,13 @@
 use alloc::sync::Arc;
 use core::convert::TryFrom;
+use core::time::Duration;
 
 use tokio::runtime::Runtime as TokioRuntime;
 
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::core::ics02_client::client_state::ClientState;
 use ibc_relayer_types::core::ics02_client::consensus_state::ConsensusState;
@@ -61,6 +63,16 @@ pub struct ChainStatus {
     pub timestamp: Timestamp,
 }
 
+/// The staking parameters of a chain which are relevant to the relayer.
+#[derive(Clone, Debug)]
+pub struct ChainStakingParams {
+    /// The unbonding period, from which the trusting period of
+    /// the clients tracking this chain is derived.
+    pub unbonding_period: Duration,
+    /// The number of historical entries kept by the chain.
+    pub historical_entries: u32,
+}
+
 /// Defines a blockchain as understood by the relayer
 pub trait ChainEndpoint: Sized {
     /// Type of light blocks for this chain
@@ -100,9 +112,16 @@ pub trait ChainEndpoint: Sized {
     /// Perform a health check
     fn health_check(&self) -> Result<HealthCheck, Error>;
 
+    /// Whether the node is still syncing and has not caught up with the chain yet
+    fn is_syncing(&self) -> Result<bool, Error>;
+
     // Events
     fn subscribe(&mut self) -> Result<Subscription, Error>;
 
+    /// Subscribe to the events emitted by the chain if its event monitor
+    /// is already running, without starting it otherwise.
+    fn subscribe_if_running(&mut self) -> Result<Option<Subscription>, Error>;
+
     // Keyring
 
     /// Returns the chain's keybase
@@ -180,6 +199,9 @@ pub trait ChainEndpoint: Sized {
     /// If no account is given, behavior must be specified, e.g. retrieve it from configuration file.
     fn query_all_balances(&self, key_name: Option<&str>) -> Result<Vec<Balance>, Error>;
 
+    /// Query the balances of the account with the given address for all the denom.
+    fn query_address_balances(&self, address: &str) -> Result<Vec<Balance>, Error>;
+
     /// Query the denomination trace given a trace hash.
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
 
@@ -193,6 +215,9 @@ pub trait ChainEndpoint: Sized {
     /// Query the latest height and timestamp the application is at
     fn query_application_status(&self) -> Result<ChainStatus, Error>;
 
+    /// Query the staking parameters of the chain
+    fn query_staking_params(&self) -> Result<ChainStakingParams, Error>;
+
     /// Performs a query to retrieve the state of all clients that a chain hosts.
     fn query_clients(
         &self,
@@ -360,6 +385,10 @@ pub trait ChainEndpoint: Sized {
         request: QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error>;
 
+    /// Query all the IBC events emitted in the block at the given height,
+    /// preceded by a `NewBlock` event.
+    fn query_block_events(&self, height: ICSHeight) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -493,7 +522,10 @@ pub trait ChainEndpoint: Sized {
                 client_proof,
                 consensus_proof,
                 None,
-                height.increment(),
+                self.config()
+                    .r#type
+                    .proof_height_policy()
+                    .proof_height(height),
             )
             .map_err(Error::malformed_proof)?,
         ))
@@ -523,7 +555,13 @@ pub trait ChainEndpoint: Sized {
         let channel_proof_bytes =
             CommitmentProofBytes::try_from(channel_proof).map_err(Error::malformed_proof)?;
 
-        Proofs::new(channel_proof_bytes, None, None, None, height.increment())
+        let proof_height = self
+            .config()
+            .r#type
+            .proof_height_policy()
+            .proof_height(height);
+
+        Proofs::new(channel_proof_bytes, None, None, None, proof_height)
             .map_err(Error::malformed_proof)
     }
 
@@ -663,7 +701,10 @@ pub trait ChainEndpoint: Sized {
             None,
             None,
             channel_proof,
-            height.increment(),
+            self.config()
+                .r#type
+                .proof_height_policy()
+                .proof_height(height),
         )
         .map_err(Error::malformed_proof)?;
 
@@ -686,4 +727,19 @@ pub trait ChainEndpoint: Sized {
         &self,
         request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error>;
+
+    /// Query the packets on the given channel which have fees escrowed for them.
+    fn query_incentivized_packets(
+        &self,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error>;
+
+    /// Query the counterparty payee registered by the given relayer address
+    /// on the given channel, if any.
+    fn query_counterparty_payee(
+        &self,
+        channel_id: &ChannelId,
+        address: &Signer,
+    ) -> Result<Option<String>, Error>;
 }
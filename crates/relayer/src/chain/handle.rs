@@ -1,5 +1,8 @@
 use alloc::sync::Arc;
 use core::fmt::{self, Debug, Display};
+use core::time::Duration;
+use std::thread;
+use std::time::Instant;
 
 use crossbeam_channel as channel;
 use tracing::Span;
@@ -8,6 +11,7 @@ use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
 use ibc_relayer_types::{
+    applications::ics29_fee::packet_fee::IdentifiedPacketFees,
     applications::ics31_icq::response::CrossChainQueryResponse,
     core::{
         ics02_client::events::UpdateClient,
@@ -46,17 +50,22 @@ use crate::{
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainStatus, HealthCheck},
+    endpoint::{ChainStakingParams, ChainStatus, HealthCheck},
     requests::*,
+    rpc_stats::QueryStatsSummary,
     tracking::TrackedMsgs,
 };
 
 mod base;
 mod cache;
 mod counting;
+#[cfg(any(test, feature = "mock"))]
+mod mock;
 
 pub use base::BaseChainHandle;
 pub use counting::CountingChainHandle;
+#[cfg(any(test, feature = "mock"))]
+pub use mock::MockChainHandle;
 
 pub type CachingChainHandle = cache::CachingChainHandle<BaseChainHandle>;
 pub type CountingAndCachingChainHandle =
@@ -88,6 +97,10 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Debug for ChainHandlePair<ChainA,
     }
 }
 
+/// Interval between two queries of the latest height of a chain when waiting for it
+/// to reach a height while its event monitor is not running.
+const WAIT_FOR_HEIGHT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+
 pub type Subscription = channel::Receiver<Arc<MonitorResult<EventBatch>>>;
 
 pub type ReplyTo<T> = channel::Sender<Result<T, Error>>;
@@ -109,10 +122,18 @@ pub enum ChainRequest {
         reply_to: ReplyTo<HealthCheck>,
     },
 
+    IsSyncing {
+        reply_to: ReplyTo<bool>,
+    },
+
     Subscribe {
         reply_to: ReplyTo<Subscription>,
     },
 
+    SubscribeIfRunning {
+        reply_to: ReplyTo<Option<Subscription>>,
+    },
+
     SendMessagesAndWaitCommit {
         tracked_msgs: TrackedMsgs,
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
@@ -156,15 +177,28 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<Balance>>,
     },
 
+    QueryAddressBalances {
+        address: String,
+        reply_to: ReplyTo<Vec<Balance>>,
+    },
+
     QueryDenomTrace {
         hash: String,
         reply_to: ReplyTo<DenomTrace>,
     },
 
+    QueryRpcStats {
+        reply_to: ReplyTo<Vec<QueryStatsSummary>>,
+    },
+
     QueryApplicationStatus {
         reply_to: ReplyTo<ChainStatus>,
     },
 
+    QueryStakingParams {
+        reply_to: ReplyTo<ChainStakingParams>,
+    },
+
     QueryClients {
         request: QueryClientStatesRequest,
         reply_to: ReplyTo<Vec<IdentifiedAnyClientState>>,
@@ -346,6 +380,11 @@ pub enum ChainRequest {
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     },
 
+    QueryBlockEvents {
+        height: Height,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    },
+
     QueryHostConsensusState {
         request: QueryHostConsensusStateRequest,
         reply_to: ReplyTo<AnyConsensusState>,
@@ -367,6 +406,18 @@ pub enum ChainRequest {
         request: QueryIncentivizedPacketRequest,
         reply_to: ReplyTo<QueryIncentivizedPacketResponse>,
     },
+
+    QueryIncentivizedPackets {
+        channel_id: ChannelId,
+        port_id: PortId,
+        reply_to: ReplyTo<Vec<IdentifiedPacketFees>>,
+    },
+
+    QueryCounterpartyPayee {
+        channel_id: ChannelId,
+        address: Signer,
+        reply_to: ReplyTo<Option<String>>,
+    },
 }
 
 pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
@@ -381,9 +432,16 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
     /// Perform a health check
     fn health_check(&self) -> Result<HealthCheck, Error>;
 
+    /// Whether the node is still syncing and has not caught up with the chain yet.
+    fn is_syncing(&self) -> Result<bool, Error>;
+
     /// Subscribe to the events emitted by the chain.
     fn subscribe(&self) -> Result<Subscription, Error>;
 
+    /// Subscribe to the events emitted by the chain if its event monitor
+    /// is already running, without starting it otherwise.
+    fn subscribe_if_running(&self) -> Result<Option<Subscription>, Error>;
+
     /// Send the given `msgs` to the chain, packaged as one or more transactions,
     /// and return the list of events emitted by the chain after the transaction was committed.
     fn send_messages_and_wait_commit(
@@ -424,9 +482,17 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
     /// If no account is given, behavior must be specified, e.g. retrieve it from configuration file.
     fn query_all_balances(&self, key_name: Option<String>) -> Result<Vec<Balance>, Error>;
 
+    /// Query the balances from all denom of the account with the given address,
+    /// which need not be managed by the relayer, eg. an ICS-20 escrow account.
+    fn query_address_balances(&self, address: String) -> Result<Vec<Balance>, Error>;
+
     /// Query the denomination trace given a trace hash.
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error>;
 
+    /// Return the latency and error statistics of the queries
+    /// submitted by the chain runtime so far, per query type.
+    fn query_rpc_stats(&self) -> Result<Vec<QueryStatsSummary>, Error>;
+
     /// Query the latest height and timestamp the application is at
     fn query_application_status(&self) -> Result<ChainStatus, Error>;
 
@@ -434,6 +500,61 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         Ok(self.query_application_status()?.height)
     }
 
+    /// Wait until the chain reaches the given `height`, or fail after `timeout`.
+    ///
+    /// If the event monitor of the chain is running, this follows the blocks it reports.
+    /// Otherwise, eg. in the CLI, or if the monitor stops, the latest height is polled.
+    fn wait_for_height(&self, height: Height, timeout: Duration) -> Result<(), Error> {
+        let deadline = Instant::now() + timeout;
+
+        // Subscribe before querying the latest height to not miss any block in between
+        let subscription = self.subscribe_if_running()?;
+
+        if self.query_latest_height()? >= height {
+            return Ok(());
+        }
+
+        if let Some(subscription) = subscription {
+            loop {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+
+                match subscription.recv_timeout(remaining) {
+                    Ok(batch) => {
+                        if matches!(batch.as_ref(), Ok(batch) if batch.height >= height) {
+                            return Ok(());
+                        }
+                    }
+                    Err(channel::RecvTimeoutError::Timeout) => {
+                        // The monitor may lag behind the chain
+                        if self.query_latest_height()? >= height {
+                            return Ok(());
+                        }
+
+                        return Err(Error::wait_for_height_timeout(self.id(), height, timeout));
+                    }
+                    Err(channel::RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        }
+
+        loop {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+
+            if remaining.is_zero() {
+                return Err(Error::wait_for_height_timeout(self.id(), height, timeout));
+            }
+
+            thread::sleep(remaining.min(WAIT_FOR_HEIGHT_POLL_INTERVAL));
+
+            if self.query_latest_height()? >= height {
+                return Ok(());
+            }
+        }
+    }
+
+    /// Query the staking parameters of the chain, eg. its unbonding period
+    fn query_staking_params(&self) -> Result<ChainStakingParams, Error>;
+
     /// Performs a query to retrieve the state of all clients that a chain hosts.
     fn query_clients(
         &self,
@@ -657,6 +778,11 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         request: QueryPacketEventDataRequest,
     ) -> Result<Vec<IbcEventWithHeight>, Error>;
 
+    /// Query all the IBC events emitted in the block at the given height,
+    /// preceded by a `NewBlock` event, as they would be received from the
+    /// chain's event source.
+    fn query_block_events(&self, height: Height) -> Result<Vec<IbcEventWithHeight>, Error>;
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -678,4 +804,19 @@ pub trait ChainHandle: Clone + Display + Send + Sync + Debug + 'static {
         &self,
         request: QueryIncentivizedPacketRequest,
     ) -> Result<QueryIncentivizedPacketResponse, Error>;
+
+    /// Query the packets on the given channel which have fees escrowed for them.
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error>;
+
+    /// Query the counterparty payee registered by the given relayer address
+    /// on the given channel, if any.
+    fn query_counterparty_payee(
+        &self,
+        channel_id: ChannelId,
+        address: Signer,
+    ) -> Result<Option<String>, Error>;
 }
@@ -7,6 +7,7 @@ use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
 use ibc_relayer_types::{
+    applications::ics29_fee::packet_fee::IdentifiedPacketFees,
     applications::ics31_icq::response::CrossChainQueryResponse,
     core::{
         ics02_client::events::UpdateClient,
@@ -26,7 +27,13 @@ use ibc_relayer_types::{
 
 use crate::{
     account::Balance,
-    chain::{client::ClientSettings, endpoint::ChainStatus, requests::*, tracking::TrackedMsgs},
+    chain::{
+        client::ClientSettings,
+        endpoint::{ChainStakingParams, ChainStatus},
+        requests::*,
+        rpc_stats::QueryStatsSummary,
+        tracking::TrackedMsgs,
+    },
     client_state::{AnyClientState, IdentifiedAnyClientState},
     config::ChainConfig,
     connection::ConnectionMsgType,
@@ -97,6 +104,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::HealthCheck { reply_to })
     }
 
+    fn is_syncing(&self) -> Result<bool, Error> {
+        self.send(|reply_to| ChainRequest::IsSyncing { reply_to })
+    }
+
     fn shutdown(&self) -> Result<(), Error> {
         self.send(|reply_to| ChainRequest::Shutdown { reply_to })
     }
@@ -105,6 +116,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::Subscribe { reply_to })
     }
 
+    fn subscribe_if_running(&self) -> Result<Option<Subscription>, Error> {
+        self.send(|reply_to| ChainRequest::SubscribeIfRunning { reply_to })
+    }
+
     fn send_messages_and_wait_commit(
         &self,
         tracked_msgs: TrackedMsgs,
@@ -165,14 +180,26 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryAllBalances { key_name, reply_to })
     }
 
+    fn query_address_balances(&self, address: String) -> Result<Vec<Balance>, Error> {
+        self.send(|reply_to| ChainRequest::QueryAddressBalances { address, reply_to })
+    }
+
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
         self.send(|reply_to| ChainRequest::QueryDenomTrace { hash, reply_to })
     }
 
+    fn query_rpc_stats(&self) -> Result<Vec<QueryStatsSummary>, Error> {
+        self.send(|reply_to| ChainRequest::QueryRpcStats { reply_to })
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.send(|reply_to| ChainRequest::QueryApplicationStatus { reply_to })
     }
 
+    fn query_staking_params(&self) -> Result<ChainStakingParams, Error> {
+        self.send(|reply_to| ChainRequest::QueryStakingParams { reply_to })
+    }
+
     fn query_clients(
         &self,
         request: QueryClientStatesRequest,
@@ -481,6 +508,10 @@ impl ChainHandle for BaseChainHandle {
         self.send(|reply_to| ChainRequest::QueryPacketEventData { request, reply_to })
     }
 
+    fn query_block_events(&self, height: Height) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.send(|reply_to| ChainRequest::QueryBlockEvents { height, reply_to })
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -515,4 +546,28 @@ impl ChainHandle for BaseChainHandle {
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
         self.send(|reply_to| ChainRequest::QueryIncentivizedPacket { request, reply_to })
     }
+
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.send(|reply_to| ChainRequest::QueryIncentivizedPackets {
+            channel_id,
+            port_id,
+            reply_to,
+        })
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        channel_id: ChannelId,
+        address: Signer,
+    ) -> Result<Option<String>, Error> {
+        self.send(|reply_to| ChainRequest::QueryCounterpartyPayee {
+            channel_id,
+            address,
+            reply_to,
+        })
+    }
 }
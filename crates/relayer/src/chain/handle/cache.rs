@@ -4,6 +4,7 @@ use tracing::Span;
 
 use ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketRequest;
 use ibc_proto::ibc::apps::fee::v1::QueryIncentivizedPacketResponse;
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
@@ -24,9 +25,10 @@ use ibc_relayer_types::Height;
 use crate::account::Balance;
 use crate::cache::{Cache, CacheStatus};
 use crate::chain::client::ClientSettings;
-use crate::chain::endpoint::{ChainStatus, HealthCheck};
+use crate::chain::endpoint::{ChainStakingParams, ChainStatus, HealthCheck};
 use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use crate::chain::requests::*;
+use crate::chain::rpc_stats::QueryStatsSummary;
 use crate::chain::tracking::TrackedMsgs;
 use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
 use crate::config::ChainConfig;
@@ -88,10 +90,18 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().health_check()
     }
 
+    fn is_syncing(&self) -> Result<bool, Error> {
+        self.inner().is_syncing()
+    }
+
     fn subscribe(&self) -> Result<Subscription, Error> {
         self.inner().subscribe()
     }
 
+    fn subscribe_if_running(&self) -> Result<Option<Subscription>, Error> {
+        self.inner().subscribe_if_running()
+    }
+
     fn send_messages_and_wait_commit(
         &self,
         tracked_msgs: TrackedMsgs,
@@ -138,14 +148,26 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_all_balances(key_name)
     }
 
+    fn query_address_balances(&self, address: String) -> Result<Vec<Balance>, Error> {
+        self.inner().query_address_balances(address)
+    }
+
     fn query_denom_trace(&self, hash: String) -> Result<DenomTrace, Error> {
         self.inner().query_denom_trace(hash)
     }
 
+    fn query_rpc_stats(&self) -> Result<Vec<QueryStatsSummary>, Error> {
+        self.inner().query_rpc_stats()
+    }
+
     fn query_application_status(&self) -> Result<ChainStatus, Error> {
         self.inner().query_application_status()
     }
 
+    fn query_staking_params(&self) -> Result<ChainStakingParams, Error> {
+        self.inner().query_staking_params()
+    }
+
     fn query_latest_height(&self) -> Result<Height, Error> {
         let handle = self.inner();
         let (result, in_cache) = self
@@ -480,6 +502,10 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
         self.inner().query_packet_events(request)
     }
 
+    fn query_block_events(&self, height: Height) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.inner().query_block_events(height)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -510,4 +536,20 @@ impl<Handle: ChainHandle> ChainHandle for CachingChainHandle<Handle> {
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
         self.inner.query_incentivized_packet(request)
     }
+
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.inner.query_incentivized_packets(channel_id, port_id)
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        channel_id: ChannelId,
+        address: Signer,
+    ) -> Result<Option<String>, Error> {
+        self.inner.query_counterparty_payee(channel_id, address)
+    }
 }
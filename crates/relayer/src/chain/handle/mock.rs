@@ -0,0 +1,1158 @@
+//! A [`ChainHandle`] backed by in-memory state instead of a chain runtime,
+//! for testing the logic built on top of chain handles, eg. the supervisor,
+//! the workers and the foreign client, without a running chain.
+//!
+//! The state of the chain (clients, connections, channels and packets) is
+//! populated by the test, which can also script the events returned when
+//! submitting transactions and inject failures into any method of the handle.
+//!
+//! The mock state is not versioned: queries at a past height return the
+//! latest state, and proofs cannot be built. Methods which need a full node,
+//! eg. to build headers or to query balances, fail with
+//! [`Error::mock_chain_handle`].
+
+use alloc::collections::{BTreeMap, BTreeSet, VecDeque};
+use alloc::sync::Arc;
+use core::fmt::{Display, Error as FmtError, Formatter};
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use crossbeam_channel as channel;
+use tracing::Span;
+
+use ibc_proto::ibc::apps::fee::v1::{
+    QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
+};
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
+use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
+use ibc_relayer_types::core::ics02_client::events::UpdateClient;
+use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
+use ibc_relayer_types::core::ics03_connection::connection::IdentifiedConnectionEnd;
+use ibc_relayer_types::core::ics03_connection::version::{get_compatible_versions, Version};
+use ibc_relayer_types::core::ics04_channel::channel::ChannelEnd;
+use ibc_relayer_types::core::ics04_channel::channel::IdentifiedChannelEnd;
+use ibc_relayer_types::core::ics04_channel::packet::{PacketMsgType, Sequence};
+use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
+use ibc_relayer_types::core::ics23_commitment::merkle::MerkleProof;
+use ibc_relayer_types::core::ics24_host::identifier::{
+    ChainId, ChannelId, ClientId, ConnectionId, PortId,
+};
+use ibc_relayer_types::proofs::Proofs;
+use ibc_relayer_types::signer::Signer;
+use ibc_relayer_types::timestamp::Timestamp;
+use ibc_relayer_types::Height;
+
+use crate::account::Balance;
+use crate::chain::client::ClientSettings;
+use crate::chain::endpoint::{ChainStakingParams, ChainStatus, HealthCheck};
+use crate::chain::handle::{ChainHandle, ChainRequest, Subscription};
+use crate::chain::requests::*;
+use crate::chain::rpc_stats::QueryStatsSummary;
+use crate::chain::tracking::{TrackedMsgs, TrackingId};
+use crate::client_state::{AnyClientState, IdentifiedAnyClientState};
+use crate::config::ChainConfig;
+use crate::connection::ConnectionMsgType;
+use crate::consensus_state::AnyConsensusState;
+use crate::denom::DenomTrace;
+use crate::error::Error;
+use crate::event::monitor::{EventBatch, Result as MonitorResult};
+use crate::event::IbcEventWithHeight;
+use crate::keyring::AnySigningKeyPair;
+use crate::light_client::AnyHeader;
+use crate::misbehaviour::MisbehaviourEvidence;
+use crate::util::lock::LockExt;
+
+type PacketKey = (PortId, ChannelId, Sequence);
+
+/// A chain handle over in-memory state, shared between all its clones.
+#[derive(Debug, Clone)]
+pub struct MockChainHandle {
+    chain_id: ChainId,
+    state: Arc<RwLock<MockChainState>>,
+}
+
+#[derive(Debug)]
+struct MockChainState {
+    latest_height: Height,
+    syncing: bool,
+    clients: BTreeMap<ClientId, AnyClientState>,
+    consensus_states: BTreeMap<(ClientId, Height), AnyConsensusState>,
+    connections: BTreeMap<ConnectionId, ConnectionEnd>,
+    channels: BTreeMap<(PortId, ChannelId), ChannelEnd>,
+    packet_commitments: BTreeMap<PacketKey, Vec<u8>>,
+    packet_receipts: BTreeSet<PacketKey>,
+    packet_acknowledgements: BTreeMap<PacketKey, Vec<u8>>,
+    block_events: BTreeMap<Height, Vec<IbcEventWithHeight>>,
+    tx_events: VecDeque<Vec<IbcEventWithHeight>>,
+    sent_messages: Vec<TrackedMsgs>,
+    subscribers: Vec<channel::Sender<Arc<MonitorResult<EventBatch>>>>,
+    /// Whether the event monitor was started, by a first subscription
+    monitoring: bool,
+    calls: HashMap<&'static str, u64>,
+    failures: HashMap<&'static str, u64>,
+}
+
+impl MockChainHandle {
+    /// Create a handle for a chain at height 1 of its revision, with an empty state.
+    pub fn new(chain_id: ChainId) -> Self {
+        let latest_height = Height::new(chain_id.version(), 1).expect("height 1 is a valid height");
+
+        let state = MockChainState {
+            latest_height,
+            syncing: false,
+            clients: BTreeMap::new(),
+            consensus_states: BTreeMap::new(),
+            connections: BTreeMap::new(),
+            channels: BTreeMap::new(),
+            packet_commitments: BTreeMap::new(),
+            packet_receipts: BTreeSet::new(),
+            packet_acknowledgements: BTreeMap::new(),
+            block_events: BTreeMap::new(),
+            tx_events: VecDeque::new(),
+            sent_messages: Vec::new(),
+            subscribers: Vec::new(),
+            monitoring: false,
+            calls: HashMap::new(),
+            failures: HashMap::new(),
+        };
+
+        Self {
+            chain_id,
+            state: Arc::new(RwLock::new(state)),
+        }
+    }
+
+    pub fn insert_client_state(&self, client_id: ClientId, client_state: AnyClientState) {
+        self.state
+            .acquire_write()
+            .clients
+            .insert(client_id, client_state);
+    }
+
+    pub fn insert_consensus_state(
+        &self,
+        client_id: ClientId,
+        height: Height,
+        consensus_state: AnyConsensusState,
+    ) {
+        self.state
+            .acquire_write()
+            .consensus_states
+            .insert((client_id, height), consensus_state);
+    }
+
+    pub fn insert_connection(&self, connection_id: ConnectionId, connection: ConnectionEnd) {
+        self.state
+            .acquire_write()
+            .connections
+            .insert(connection_id, connection);
+    }
+
+    pub fn insert_channel(&self, port_id: PortId, channel_id: ChannelId, channel: ChannelEnd) {
+        self.state
+            .acquire_write()
+            .channels
+            .insert((port_id, channel_id), channel);
+    }
+
+    /// Store the commitment of a packet sent on this chain.
+    pub fn insert_packet_commitment(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequence: Sequence,
+        commitment: Vec<u8>,
+    ) {
+        self.state
+            .acquire_write()
+            .packet_commitments
+            .insert((port_id, channel_id, sequence), commitment);
+    }
+
+    /// Remove the commitment of a packet sent on this chain,
+    /// as happens once the packet is acknowledged or timed out.
+    pub fn remove_packet_commitment(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequence: Sequence,
+    ) {
+        self.state
+            .acquire_write()
+            .packet_commitments
+            .remove(&(port_id, channel_id, sequence));
+    }
+
+    /// Record that a packet was received on this chain.
+    pub fn insert_packet_receipt(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequence: Sequence,
+    ) {
+        self.state
+            .acquire_write()
+            .packet_receipts
+            .insert((port_id, channel_id, sequence));
+    }
+
+    /// Store the acknowledgement written by this chain for a received packet.
+    pub fn insert_packet_acknowledgement(
+        &self,
+        port_id: PortId,
+        channel_id: ChannelId,
+        sequence: Sequence,
+        acknowledgement: Vec<u8>,
+    ) {
+        self.state
+            .acquire_write()
+            .packet_acknowledgements
+            .insert((port_id, channel_id, sequence), acknowledgement);
+    }
+
+    /// Queue the events returned by the next call to
+    /// [`ChainHandle::send_messages_and_wait_commit`].
+    ///
+    /// Without any queued events, transactions succeed without emitting any event.
+    pub fn push_tx_events(&self, events: Vec<IbcEventWithHeight>) {
+        self.state.acquire_write().tx_events.push_back(events);
+    }
+
+    /// The messages submitted to this chain so far, in submission order.
+    pub fn sent_messages(&self) -> Vec<TrackedMsgs> {
+        self.state.acquire_read().sent_messages.clone()
+    }
+
+    /// Make the next `times` calls to the given method of the handle fail,
+    /// eg. `fail_next("query_channel", 2)`.
+    pub fn fail_next(&self, method: &'static str, times: u64) {
+        self.state.acquire_write().failures.insert(method, times);
+    }
+
+    /// The number of times the given method of the handle was called,
+    /// including the calls which failed.
+    pub fn calls(&self, method: &str) -> u64 {
+        self.state
+            .acquire_read()
+            .calls
+            .get(method)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Set whether the node of the chain reports that it is still syncing.
+    pub fn set_syncing(&self, syncing: bool) {
+        self.state.acquire_write().syncing = syncing;
+    }
+
+    /// Produce a new block with the given events, and report
+    /// it to the subscribers of the handle.
+    ///
+    /// Returns the height of the new block.
+    pub fn produce_block(&self, events: Vec<IbcEventWithHeight>) -> Height {
+        let mut state = self.state.acquire_write();
+
+        let height = state.latest_height.increment();
+        state.latest_height = height;
+
+        let batch = Arc::new(Ok(EventBatch {
+            chain_id: self.chain_id.clone(),
+            tracking_id: TrackingId::new_static("mock"),
+            height,
+            events: events.clone(),
+        }));
+
+        state.block_events.insert(height, events);
+        state
+            .subscribers
+            .retain(|subscriber| subscriber.send(batch.clone()).is_ok());
+
+        height
+    }
+
+    /// Count a call to the given method, and fail it if a failure was injected.
+    fn enter(&self, method: &'static str) -> Result<(), Error> {
+        let mut state = self.state.acquire_write();
+
+        *state.calls.entry(method).or_default() += 1;
+
+        match state.failures.get_mut(method) {
+            Some(remaining) if *remaining > 0 => {
+                *remaining -= 1;
+                Err(Error::mock_chain_handle(format!(
+                    "injected failure of `{}`",
+                    method
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn enter_without_proof(
+        &self,
+        method: &'static str,
+        include_proof: IncludeProof,
+    ) -> Result<(), Error> {
+        self.enter(method)?;
+
+        match include_proof {
+            IncludeProof::Yes => Err(Error::mock_chain_handle(format!(
+                "`{}` cannot build proofs",
+                method
+            ))),
+            IncludeProof::No => Ok(()),
+        }
+    }
+
+    fn unsupported<T>(&self, method: &'static str) -> Result<T, Error> {
+        self.enter(method)?;
+
+        Err(Error::mock_chain_handle(format!(
+            "`{}` is not supported",
+            method
+        )))
+    }
+
+    fn not_found<T>(what: String) -> Result<T, Error> {
+        Err(Error::mock_chain_handle(format!("{} not found", what)))
+    }
+}
+
+impl Display for MockChainHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), FmtError> {
+        write!(f, "MockChainHandle {{ chain_id: {} }}", self.chain_id)
+    }
+}
+
+impl ChainHandle for MockChainHandle {
+    fn new(chain_id: ChainId, _sender: channel::Sender<(Span, ChainRequest)>) -> Self {
+        Self::new(chain_id)
+    }
+
+    fn id(&self) -> ChainId {
+        self.chain_id.clone()
+    }
+
+    fn shutdown(&self) -> Result<(), Error> {
+        self.enter("shutdown")?;
+        let mut state = self.state.acquire_write();
+        state.subscribers.clear();
+        state.monitoring = false;
+        Ok(())
+    }
+
+    fn health_check(&self) -> Result<HealthCheck, Error> {
+        self.enter("health_check")?;
+        Ok(HealthCheck::Healthy)
+    }
+
+    fn is_syncing(&self) -> Result<bool, Error> {
+        self.enter("is_syncing")?;
+        Ok(self.state.acquire_read().syncing)
+    }
+
+    fn subscribe(&self) -> Result<Subscription, Error> {
+        self.enter("subscribe")?;
+
+        let (sender, receiver) = channel::unbounded();
+
+        let mut state = self.state.acquire_write();
+        state.subscribers.push(sender);
+        state.monitoring = true;
+
+        Ok(receiver)
+    }
+
+    fn subscribe_if_running(&self) -> Result<Option<Subscription>, Error> {
+        self.enter("subscribe_if_running")?;
+
+        if !self.state.acquire_read().monitoring {
+            return Ok(None);
+        }
+
+        let (sender, receiver) = channel::unbounded();
+        self.state.acquire_write().subscribers.push(sender);
+
+        Ok(Some(receiver))
+    }
+
+    fn send_messages_and_wait_commit(
+        &self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.enter("send_messages_and_wait_commit")?;
+
+        let mut state = self.state.acquire_write();
+        state.sent_messages.push(tracked_msgs);
+
+        Ok(state.tx_events.pop_front().unwrap_or_default())
+    }
+
+    fn send_messages_and_wait_check_tx(
+        &self,
+        tracked_msgs: TrackedMsgs,
+    ) -> Result<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>, Error> {
+        self.enter("send_messages_and_wait_check_tx")?;
+        self.state.acquire_write().sent_messages.push(tracked_msgs);
+        Ok(vec![])
+    }
+
+    fn get_signer(&self) -> Result<Signer, Error> {
+        self.enter("get_signer")?;
+        Ok(Signer::dummy())
+    }
+
+    fn config(&self) -> Result<ChainConfig, Error> {
+        self.unsupported("config")
+    }
+
+    fn get_key(&self) -> Result<AnySigningKeyPair, Error> {
+        self.unsupported("get_key")
+    }
+
+    fn add_key(&self, _key_name: String, _key: AnySigningKeyPair) -> Result<(), Error> {
+        self.unsupported("add_key")
+    }
+
+    fn ibc_version(&self) -> Result<Option<semver::Version>, Error> {
+        self.enter("ibc_version")?;
+        Ok(None)
+    }
+
+    fn query_balance(
+        &self,
+        _key_name: Option<String>,
+        _denom: Option<String>,
+    ) -> Result<Balance, Error> {
+        self.unsupported("query_balance")
+    }
+
+    fn query_all_balances(&self, _key_name: Option<String>) -> Result<Vec<Balance>, Error> {
+        self.unsupported("query_all_balances")
+    }
+
+    fn query_address_balances(&self, _address: String) -> Result<Vec<Balance>, Error> {
+        self.unsupported("query_address_balances")
+    }
+
+    fn query_denom_trace(&self, _hash: String) -> Result<DenomTrace, Error> {
+        self.unsupported("query_denom_trace")
+    }
+
+    fn query_rpc_stats(&self) -> Result<Vec<QueryStatsSummary>, Error> {
+        self.enter("query_rpc_stats")?;
+        Ok(vec![])
+    }
+
+    fn query_application_status(&self) -> Result<ChainStatus, Error> {
+        self.enter("query_application_status")?;
+
+        Ok(ChainStatus {
+            height: self.state.acquire_read().latest_height,
+            timestamp: Timestamp::now(),
+        })
+    }
+
+    fn query_staking_params(&self) -> Result<ChainStakingParams, Error> {
+        self.unsupported("query_staking_params")
+    }
+
+    fn query_clients(
+        &self,
+        _request: QueryClientStatesRequest,
+    ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
+        self.enter("query_clients")?;
+
+        let clients = self
+            .state
+            .acquire_read()
+            .clients
+            .iter()
+            .map(|(client_id, client_state)| IdentifiedAnyClientState {
+                client_id: client_id.clone(),
+                client_state: client_state.clone(),
+            })
+            .collect();
+
+        Ok(clients)
+    }
+
+    fn query_client_state(
+        &self,
+        request: QueryClientStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyClientState, Option<MerkleProof>), Error> {
+        self.enter_without_proof("query_client_state", include_proof)?;
+
+        match self.state.acquire_read().clients.get(&request.client_id) {
+            Some(client_state) => Ok((client_state.clone(), None)),
+            None => Self::not_found(format!("client {}", request.client_id)),
+        }
+    }
+
+    fn query_client_connections(
+        &self,
+        request: QueryClientConnectionsRequest,
+    ) -> Result<Vec<ConnectionId>, Error> {
+        self.enter("query_client_connections")?;
+
+        let connection_ids = self
+            .state
+            .acquire_read()
+            .connections
+            .iter()
+            .filter(|(_, connection)| connection.client_id() == &request.client_id)
+            .map(|(connection_id, _)| connection_id.clone())
+            .collect();
+
+        Ok(connection_ids)
+    }
+
+    fn query_consensus_state_heights(
+        &self,
+        request: QueryConsensusStateHeightsRequest,
+    ) -> Result<Vec<Height>, Error> {
+        self.enter("query_consensus_state_heights")?;
+
+        let heights = self
+            .state
+            .acquire_read()
+            .consensus_states
+            .keys()
+            .filter(|(client_id, _)| client_id == &request.client_id)
+            .map(|(_, height)| *height)
+            .collect();
+
+        Ok(heights)
+    }
+
+    fn query_consensus_state(
+        &self,
+        request: QueryConsensusStateRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(AnyConsensusState, Option<MerkleProof>), Error> {
+        self.enter_without_proof("query_consensus_state", include_proof)?;
+
+        let key = (request.client_id, request.consensus_height);
+
+        match self.state.acquire_read().consensus_states.get(&key) {
+            Some(consensus_state) => Ok((consensus_state.clone(), None)),
+            None => Err(Error::consensus_state_not_found(key.0, key.1)),
+        }
+    }
+
+    fn query_upgraded_client_state(
+        &self,
+        _request: QueryUpgradedClientStateRequest,
+    ) -> Result<(AnyClientState, MerkleProof), Error> {
+        self.unsupported("query_upgraded_client_state")
+    }
+
+    fn query_upgraded_consensus_state(
+        &self,
+        _request: QueryUpgradedConsensusStateRequest,
+    ) -> Result<(AnyConsensusState, MerkleProof), Error> {
+        self.unsupported("query_upgraded_consensus_state")
+    }
+
+    fn query_commitment_prefix(&self) -> Result<CommitmentPrefix, Error> {
+        self.enter("query_commitment_prefix")?;
+        Ok(CommitmentPrefix::try_from(b"ibc".to_vec()).expect("non-empty prefix"))
+    }
+
+    fn query_compatible_versions(&self) -> Result<Vec<Version>, Error> {
+        self.enter("query_compatible_versions")?;
+        Ok(get_compatible_versions())
+    }
+
+    fn query_connection(
+        &self,
+        request: QueryConnectionRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ConnectionEnd, Option<MerkleProof>), Error> {
+        self.enter_without_proof("query_connection", include_proof)?;
+
+        match self
+            .state
+            .acquire_read()
+            .connections
+            .get(&request.connection_id)
+        {
+            Some(connection) => Ok((connection.clone(), None)),
+            None => Self::not_found(format!("connection {}", request.connection_id)),
+        }
+    }
+
+    fn query_connections(
+        &self,
+        _request: QueryConnectionsRequest,
+    ) -> Result<Vec<IdentifiedConnectionEnd>, Error> {
+        self.enter("query_connections")?;
+
+        let connections = self
+            .state
+            .acquire_read()
+            .connections
+            .iter()
+            .map(|(connection_id, connection)| {
+                IdentifiedConnectionEnd::new(connection_id.clone(), connection.clone())
+            })
+            .collect();
+
+        Ok(connections)
+    }
+
+    fn query_connection_channels(
+        &self,
+        request: QueryConnectionChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.enter("query_connection_channels")?;
+
+        let channels = self
+            .state
+            .acquire_read()
+            .channels
+            .iter()
+            .filter(|(_, channel)| {
+                channel.connection_hops().first() == Some(&request.connection_id)
+            })
+            .map(|((port_id, channel_id), channel)| {
+                IdentifiedChannelEnd::new(port_id.clone(), channel_id.clone(), channel.clone())
+            })
+            .collect();
+
+        Ok(channels)
+    }
+
+    fn query_next_sequence_receive(
+        &self,
+        request: QueryNextSequenceReceiveRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Sequence, Option<MerkleProof>), Error> {
+        self.enter_without_proof("query_next_sequence_receive", include_proof)?;
+
+        let next_sequence = self
+            .state
+            .acquire_read()
+            .packet_receipts
+            .iter()
+            .filter(|(port_id, channel_id, _)| {
+                port_id == &request.port_id && channel_id == &request.channel_id
+            })
+            .map(|(_, _, sequence)| sequence.increment())
+            .max()
+            .unwrap_or_else(|| Sequence::from(1));
+
+        Ok((next_sequence, None))
+    }
+
+    fn query_channels(
+        &self,
+        _request: QueryChannelsRequest,
+    ) -> Result<Vec<IdentifiedChannelEnd>, Error> {
+        self.enter("query_channels")?;
+
+        let channels = self
+            .state
+            .acquire_read()
+            .channels
+            .iter()
+            .map(|((port_id, channel_id), channel)| {
+                IdentifiedChannelEnd::new(port_id.clone(), channel_id.clone(), channel.clone())
+            })
+            .collect();
+
+        Ok(channels)
+    }
+
+    fn query_channel(
+        &self,
+        request: QueryChannelRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(ChannelEnd, Option<MerkleProof>), Error> {
+        self.enter_without_proof("query_channel", include_proof)?;
+
+        let key = (request.port_id, request.channel_id);
+
+        match self.state.acquire_read().channels.get(&key) {
+            Some(channel) => Ok((channel.clone(), None)),
+            None => Self::not_found(format!("channel {}/{}", key.0, key.1)),
+        }
+    }
+
+    fn query_channel_client_state(
+        &self,
+        request: QueryChannelClientStateRequest,
+    ) -> Result<Option<IdentifiedAnyClientState>, Error> {
+        self.enter("query_channel_client_state")?;
+
+        let state = self.state.acquire_read();
+
+        let client_state = state
+            .channels
+            .get(&(request.port_id, request.channel_id))
+            .and_then(|channel| channel.connection_hops().first())
+            .and_then(|connection_id| state.connections.get(connection_id))
+            .and_then(|connection| {
+                let client_id = connection.client_id();
+
+                state
+                    .clients
+                    .get(client_id)
+                    .map(|client_state| IdentifiedAnyClientState {
+                        client_id: client_id.clone(),
+                        client_state: client_state.clone(),
+                    })
+            });
+
+        Ok(client_state)
+    }
+
+    fn build_header(
+        &self,
+        _trusted_height: Height,
+        _target_height: Height,
+        _client_state: AnyClientState,
+    ) -> Result<(AnyHeader, Vec<AnyHeader>), Error> {
+        self.unsupported("build_header")
+    }
+
+    fn build_client_state(
+        &self,
+        _height: Height,
+        _options: ClientSettings,
+    ) -> Result<AnyClientState, Error> {
+        self.unsupported("build_client_state")
+    }
+
+    fn build_consensus_state(
+        &self,
+        _trusted: Height,
+        _target: Height,
+        _client_state: AnyClientState,
+    ) -> Result<AnyConsensusState, Error> {
+        self.unsupported("build_consensus_state")
+    }
+
+    fn check_misbehaviour(
+        &self,
+        _update: UpdateClient,
+        _client_state: AnyClientState,
+    ) -> Result<Option<MisbehaviourEvidence>, Error> {
+        self.enter("check_misbehaviour")?;
+        Ok(None)
+    }
+
+    fn build_connection_proofs_and_client_state(
+        &self,
+        _message_type: ConnectionMsgType,
+        _connection_id: &ConnectionId,
+        _client_id: &ClientId,
+        _height: Height,
+    ) -> Result<(Option<AnyClientState>, Proofs), Error> {
+        self.unsupported("build_connection_proofs_and_client_state")
+    }
+
+    fn build_channel_proofs(
+        &self,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _height: Height,
+    ) -> Result<Proofs, Error> {
+        self.unsupported("build_channel_proofs")
+    }
+
+    fn build_packet_proofs(
+        &self,
+        _packet_type: PacketMsgType,
+        _port_id: &PortId,
+        _channel_id: &ChannelId,
+        _sequence: Sequence,
+        _height: Height,
+    ) -> Result<Proofs, Error> {
+        self.unsupported("build_packet_proofs")
+    }
+
+    fn query_packet_commitment(
+        &self,
+        request: QueryPacketCommitmentRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.enter_without_proof("query_packet_commitment", include_proof)?;
+
+        let key = (request.port_id, request.channel_id, request.sequence);
+
+        // Like a full node, return an empty commitment for a packet without commitment
+        let commitment = self
+            .state
+            .acquire_read()
+            .packet_commitments
+            .get(&key)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok((commitment, None))
+    }
+
+    fn query_packet_commitments(
+        &self,
+        request: QueryPacketCommitmentsRequest,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.enter("query_packet_commitments")?;
+
+        let state = self.state.acquire_read();
+
+        let sequences = state
+            .packet_commitments
+            .keys()
+            .filter(|(port_id, channel_id, _)| {
+                port_id == &request.port_id && channel_id == &request.channel_id
+            })
+            .map(|(_, _, sequence)| *sequence)
+            .collect();
+
+        Ok((sequences, state.latest_height))
+    }
+
+    fn query_packet_receipt(
+        &self,
+        request: QueryPacketReceiptRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.enter_without_proof("query_packet_receipt", include_proof)?;
+
+        let key = (request.port_id, request.channel_id, request.sequence);
+
+        let receipt = if self.state.acquire_read().packet_receipts.contains(&key) {
+            vec![1]
+        } else {
+            vec![]
+        };
+
+        Ok((receipt, None))
+    }
+
+    fn query_unreceived_packets(
+        &self,
+        request: QueryUnreceivedPacketsRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.enter("query_unreceived_packets")?;
+
+        let state = self.state.acquire_read();
+
+        let sequences = request
+            .packet_commitment_sequences
+            .into_iter()
+            .filter(|sequence| {
+                !state.packet_receipts.contains(&(
+                    request.port_id.clone(),
+                    request.channel_id.clone(),
+                    *sequence,
+                ))
+            })
+            .collect();
+
+        Ok(sequences)
+    }
+
+    fn query_packet_acknowledgement(
+        &self,
+        request: QueryPacketAcknowledgementRequest,
+        include_proof: IncludeProof,
+    ) -> Result<(Vec<u8>, Option<MerkleProof>), Error> {
+        self.enter_without_proof("query_packet_acknowledgement", include_proof)?;
+
+        let key = (request.port_id, request.channel_id, request.sequence);
+
+        let acknowledgement = self
+            .state
+            .acquire_read()
+            .packet_acknowledgements
+            .get(&key)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok((acknowledgement, None))
+    }
+
+    fn query_packet_acknowledgements(
+        &self,
+        request: QueryPacketAcknowledgementsRequest,
+    ) -> Result<(Vec<Sequence>, Height), Error> {
+        self.enter("query_packet_acknowledgements")?;
+
+        let state = self.state.acquire_read();
+
+        let sequences = state
+            .packet_acknowledgements
+            .keys()
+            .filter(|(port_id, channel_id, sequence)| {
+                port_id == &request.port_id
+                    && channel_id == &request.channel_id
+                    && (request.packet_commitment_sequences.is_empty()
+                        || request.packet_commitment_sequences.contains(sequence))
+            })
+            .map(|(_, _, sequence)| *sequence)
+            .collect();
+
+        Ok((sequences, state.latest_height))
+    }
+
+    fn query_unreceived_acknowledgements(
+        &self,
+        request: QueryUnreceivedAcksRequest,
+    ) -> Result<Vec<Sequence>, Error> {
+        self.enter("query_unreceived_acknowledgements")?;
+
+        let state = self.state.acquire_read();
+
+        // The acknowledgement of a packet is pending as long as its commitment is stored
+        let sequences = request
+            .packet_ack_sequences
+            .into_iter()
+            .filter(|sequence| {
+                state.packet_commitments.contains_key(&(
+                    request.port_id.clone(),
+                    request.channel_id.clone(),
+                    *sequence,
+                ))
+            })
+            .collect();
+
+        Ok(sequences)
+    }
+
+    fn query_txs(&self, _request: QueryTxRequest) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.enter("query_txs")?;
+        Ok(vec![])
+    }
+
+    fn query_packet_events(
+        &self,
+        _request: QueryPacketEventDataRequest,
+    ) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.enter("query_packet_events")?;
+        Ok(vec![])
+    }
+
+    fn query_block_events(&self, height: Height) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.enter("query_block_events")?;
+
+        let events = self
+            .state
+            .acquire_read()
+            .block_events
+            .get(&height)
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(events)
+    }
+
+    fn query_host_consensus_state(
+        &self,
+        _request: QueryHostConsensusStateRequest,
+    ) -> Result<AnyConsensusState, Error> {
+        self.unsupported("query_host_consensus_state")
+    }
+
+    fn maybe_register_counterparty_payee(
+        &self,
+        _channel_id: ChannelId,
+        _port_id: PortId,
+        _counterparty_payee: Signer,
+    ) -> Result<(), Error> {
+        self.enter("maybe_register_counterparty_payee")
+    }
+
+    fn cross_chain_query(
+        &self,
+        _request: Vec<CrossChainQueryRequest>,
+    ) -> Result<Vec<CrossChainQueryResponse>, Error> {
+        self.unsupported("cross_chain_query")
+    }
+
+    fn query_incentivized_packet(
+        &self,
+        _request: QueryIncentivizedPacketRequest,
+    ) -> Result<QueryIncentivizedPacketResponse, Error> {
+        self.unsupported("query_incentivized_packet")
+    }
+
+    fn query_incentivized_packets(
+        &self,
+        _channel_id: ChannelId,
+        _port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.enter("query_incentivized_packets")?;
+        Ok(vec![])
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        _channel_id: ChannelId,
+        _address: Signer,
+    ) -> Result<Option<String>, Error> {
+        self.enter("query_counterparty_payee")?;
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+
+    use ibc_relayer_types::core::ics03_connection::connection::{
+        Counterparty as ConnectionCounterparty, State as ConnectionState,
+    };
+    use ibc_relayer_types::core::ics04_channel::channel::{
+        Counterparty as ChannelCounterparty, Ordering, State as ChannelState,
+    };
+    use ibc_relayer_types::core::ics04_channel::version::Version as ChannelVersion;
+
+    use crate::chain::counterparty::{client_channels, pending_packet_summary};
+
+    fn open_channel(connection_id: ConnectionId, counterparty_channel_id: ChannelId) -> ChannelEnd {
+        ChannelEnd::new(
+            ChannelState::Open,
+            Ordering::Unordered,
+            ChannelCounterparty::new(PortId::transfer(), Some(counterparty_channel_id)),
+            vec![connection_id],
+            ChannelVersion::ics20(),
+        )
+    }
+
+    #[test]
+    fn client_channels_lookup() {
+        let chain = MockChainHandle::new(ChainId::from_string("ibc-0"));
+
+        let client_id = ClientId::default();
+
+        chain.insert_connection(
+            ConnectionId::new(0),
+            ConnectionEnd::new(
+                ConnectionState::Open,
+                client_id.clone(),
+                ConnectionCounterparty::default(),
+                get_compatible_versions(),
+                Duration::ZERO,
+            ),
+        );
+
+        chain.insert_channel(
+            PortId::transfer(),
+            ChannelId::new(0),
+            open_channel(ConnectionId::new(0), ChannelId::new(0)),
+        );
+
+        chain.insert_channel(
+            PortId::transfer(),
+            ChannelId::new(1),
+            open_channel(ConnectionId::new(1), ChannelId::new(1)),
+        );
+
+        let channels = client_channels(&chain, &client_id).unwrap();
+
+        assert_eq!(channels.len(), 1);
+        assert_eq!(channels[0].channel_id, ChannelId::new(0));
+    }
+
+    #[test]
+    fn pending_packets() {
+        let chain_a = MockChainHandle::new(ChainId::from_string("ibc-0"));
+        let chain_b = MockChainHandle::new(ChainId::from_string("ibc-1"));
+
+        let channel = IdentifiedChannelEnd::new(
+            PortId::transfer(),
+            ChannelId::new(0),
+            open_channel(ConnectionId::new(0), ChannelId::new(0)),
+        );
+
+        for sequence in 1..=3 {
+            chain_a.insert_packet_commitment(
+                PortId::transfer(),
+                ChannelId::new(0),
+                Sequence::from(sequence),
+                vec![sequence as u8],
+            );
+        }
+
+        // Packet 1 was received on B and its acknowledgement is pending on A
+        chain_b.insert_packet_receipt(PortId::transfer(), ChannelId::new(0), Sequence::from(1));
+        chain_b.insert_packet_acknowledgement(
+            PortId::transfer(),
+            ChannelId::new(0),
+            Sequence::from(1),
+            vec![1],
+        );
+
+        let pending = pending_packet_summary(&chain_a, &chain_b, &channel).unwrap();
+
+        assert_eq!(
+            pending.unreceived_packets,
+            vec![Sequence::from(2), Sequence::from(3)]
+        );
+        assert_eq!(pending.unreceived_acks, vec![Sequence::from(1)]);
+    }
+
+    #[test]
+    fn injected_failures() {
+        let chain = MockChainHandle::new(ChainId::from_string("ibc-0"));
+
+        chain.fail_next("query_application_status", 2);
+
+        assert!(chain.query_latest_height().is_err());
+        assert!(chain.query_latest_height().is_err());
+        assert!(chain.query_latest_height().is_ok());
+
+        assert_eq!(chain.calls("query_application_status"), 3);
+    }
+
+    #[test]
+    fn wait_for_produced_block() {
+        let chain = MockChainHandle::new(ChainId::from_string("ibc-0"));
+
+        let target = chain.query_latest_height().unwrap().increment();
+
+        let producer = chain.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            producer.produce_block(vec![])
+        });
+
+        chain
+            .wait_for_height(target, Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(handle.join().unwrap(), target);
+        assert!(chain
+            .wait_for_height(target.increment(), Duration::from_millis(10))
+            .is_err());
+    }
+
+    #[test]
+    fn wait_for_height_follows_the_event_monitor() {
+        let chain = MockChainHandle::new(ChainId::from_string("ibc-0"));
+        let _subscription = chain.subscribe().unwrap();
+
+        let target = chain.query_latest_height().unwrap().increment().increment();
+
+        let producer = chain.clone();
+        let handle = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(50));
+            producer.produce_block(vec![]);
+            producer.produce_block(vec![])
+        });
+
+        chain
+            .wait_for_height(target, Duration::from_secs(5))
+            .unwrap();
+
+        assert_eq!(handle.join().unwrap(), target);
+
+        // The latest height is only queried once, before waiting for the blocks
+        assert_eq!(chain.calls("query_application_status"), 2);
+    }
+}
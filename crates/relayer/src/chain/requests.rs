@@ -137,6 +137,7 @@ pub struct QueryClientStateRequest {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueryClientStatesRequest {
     pub pagination: Option<PageRequest>,
+    pub query_height: QueryHeight,
 }
 
 impl From<QueryClientStatesRequest> for RawQueryClientStatesRequest {
@@ -170,6 +171,7 @@ pub struct QueryUpgradedConsensusStateRequest {
 pub struct QueryConsensusStatesRequest {
     pub client_id: ClientId,
     pub pagination: Option<PageRequest>,
+    pub query_height: QueryHeight,
 }
 
 impl From<QueryConsensusStatesRequest> for RawQueryConsensusStatesRequest {
@@ -185,6 +187,7 @@ impl From<QueryConsensusStatesRequest> for RawQueryConsensusStatesRequest {
 pub struct QueryConsensusStateHeightsRequest {
     pub client_id: ClientId,
     pub pagination: Option<PageRequest>,
+    pub query_height: QueryHeight,
 }
 
 impl From<QueryConsensusStateHeightsRequest> for RawQueryConsensusStateHeightsRequest {
@@ -199,6 +202,7 @@ impl From<QueryConsensusStateHeightsRequest> for RawQueryConsensusStateHeightsRe
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueryConnectionsRequest {
     pub pagination: Option<PageRequest>,
+    pub query_height: QueryHeight,
 }
 
 impl From<QueryConnectionsRequest> for RawQueryConnectionsRequest {
@@ -246,6 +250,7 @@ impl From<QueryConnectionChannelsRequest> for RawQueryConnectionChannelsRequest
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct QueryChannelsRequest {
     pub pagination: Option<PageRequest>,
+    pub query_height: QueryHeight,
 }
 
 impl From<QueryChannelsRequest> for RawQueryChannelsRequest {
@@ -291,6 +296,7 @@ pub struct QueryPacketCommitmentsRequest {
     pub port_id: PortId,
     pub channel_id: ChannelId,
     pub pagination: Option<PageRequest>,
+    pub query_height: QueryHeight,
 }
 
 impl From<QueryPacketCommitmentsRequest> for RawQueryPacketCommitmentsRequest {
@@ -345,6 +351,7 @@ pub struct QueryPacketAcknowledgementsRequest {
     pub port_id: PortId,
     pub channel_id: ChannelId,
     pub pagination: Option<PageRequest>,
+    pub query_height: QueryHeight,
     pub packet_commitment_sequences: Vec<Sequence>,
 }
 
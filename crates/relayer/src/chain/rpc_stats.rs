@@ -0,0 +1,138 @@
+//! Latency and error statistics of the queries a chain runtime submits to its node.
+
+use alloc::collections::{BTreeMap, VecDeque};
+use core::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+/// Number of most recent latencies kept per query type to compute the percentiles.
+const LATENCY_WINDOW: usize = 1000;
+
+/// Statistics of the queries submitted by a chain runtime, per query type.
+#[derive(Debug, Default)]
+pub struct RpcStats {
+    queries: BTreeMap<&'static str, QueryStats>,
+}
+
+#[derive(Debug, Default)]
+struct QueryStats {
+    calls: u64,
+    errors: u64,
+    latencies: VecDeque<Duration>,
+}
+
+impl RpcStats {
+    /// Record the outcome of a query of the given type which took `latency` to complete.
+    pub fn record(&mut self, query_type: &'static str, latency: Duration, success: bool) {
+        let stats = self.queries.entry(query_type).or_default();
+
+        stats.calls += 1;
+
+        if !success {
+            stats.errors += 1;
+        }
+
+        if stats.latencies.len() == LATENCY_WINDOW {
+            stats.latencies.pop_front();
+        }
+
+        stats.latencies.push_back(latency);
+    }
+
+    /// The statistics of every query type submitted so far, by query type.
+    pub fn summary(&self) -> Vec<QueryStatsSummary> {
+        self.queries
+            .iter()
+            .map(|(query_type, stats)| {
+                let mut latencies = stats.latencies.iter().copied().collect::<Vec<_>>();
+                latencies.sort();
+
+                QueryStatsSummary {
+                    query_type: query_type.to_string(),
+                    calls: stats.calls,
+                    errors: stats.errors,
+                    error_rate: stats.errors as f64 / stats.calls as f64,
+                    p50_ms: percentile(&latencies, 50),
+                    p90_ms: percentile(&latencies, 90),
+                    p99_ms: percentile(&latencies, 99),
+                }
+            })
+            .collect()
+    }
+}
+
+/// The statistics of the queries of a given type, with the latency
+/// percentiles computed over the most recent queries.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct QueryStatsSummary {
+    pub query_type: String,
+    pub calls: u64,
+    pub errors: u64,
+    pub error_rate: f64,
+    pub p50_ms: u64,
+    pub p90_ms: u64,
+    pub p99_ms: u64,
+}
+
+/// Nearest-rank percentile of the given sorted latencies, in milliseconds.
+fn percentile(sorted: &[Duration], percent: usize) -> u64 {
+    if sorted.is_empty() {
+        return 0;
+    }
+
+    let rank = (percent * sorted.len() + 99) / 100;
+
+    sorted[rank.saturating_sub(1)].as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn summary() {
+        let mut stats = RpcStats::default();
+
+        for ms in 1..=100 {
+            stats.record("query_channel", Duration::from_millis(ms), ms % 10 != 0);
+        }
+
+        stats.record("query_client_state", Duration::from_millis(7), true);
+
+        let summary = stats.summary();
+        assert_eq!(summary.len(), 2);
+
+        assert_eq!(
+            summary[0],
+            QueryStatsSummary {
+                query_type: "query_channel".to_string(),
+                calls: 100,
+                errors: 10,
+                error_rate: 0.1,
+                p50_ms: 50,
+                p90_ms: 90,
+                p99_ms: 99,
+            }
+        );
+
+        assert_eq!(summary[1].p50_ms, 7);
+        assert_eq!(summary[1].p99_ms, 7);
+    }
+
+    #[test]
+    fn latency_window() {
+        let mut stats = RpcStats::default();
+
+        for _ in 0..LATENCY_WINDOW {
+            stats.record("query_txs", Duration::from_secs(1), true);
+        }
+
+        for _ in 0..LATENCY_WINDOW {
+            stats.record("query_txs", Duration::from_millis(1), true);
+        }
+
+        let summary = stats.summary();
+        assert_eq!(summary[0].calls, 2 * LATENCY_WINDOW as u64);
+        assert_eq!(summary[0].p99_ms, 1);
+    }
+}
@@ -1,14 +1,19 @@
+use alloc::collections::VecDeque;
 use alloc::sync::Arc;
+use core::cell::{Cell, RefCell};
+use core::time::Duration;
 use std::thread;
+use std::time::Instant;
 
 use crossbeam_channel as channel;
 use tokio::runtime::Runtime as TokioRuntime;
-use tracing::{error, Span};
+use tracing::{debug, error, Span};
 
 use ibc_proto::ibc::apps::fee::v1::{
     QueryIncentivizedPacketRequest, QueryIncentivizedPacketResponse,
 };
 use ibc_relayer_types::{
+    applications::ics29_fee::packet_fee::IdentifiedPacketFees,
     applications::ics31_icq::response::CrossChainQueryResponse,
     core::{
         ics02_client::events::UpdateClient,
@@ -41,13 +46,17 @@ use crate::{
     keyring::AnySigningKeyPair,
     light_client::AnyHeader,
     misbehaviour::MisbehaviourEvidence,
+    sink, telemetry,
+    util::rate_limit::TokenBucket,
+    webhook,
 };
 
 use super::{
     client::ClientSettings,
-    endpoint::{ChainEndpoint, ChainStatus, HealthCheck},
+    endpoint::{ChainEndpoint, ChainStakingParams, ChainStatus, HealthCheck},
     handle::{ChainHandle, ChainRequest, ReplyTo, Subscription},
     requests::*,
+    rpc_stats::{QueryStatsSummary, RpcStats},
     tracking::TrackedMsgs,
 };
 
@@ -56,6 +65,18 @@ pub struct Threads {
     pub event_monitor: Option<thread::JoinHandle<()>>,
 }
 
+/// A request to submit a transaction, which may be held back by the rate limiter.
+enum TxRequest {
+    WaitCommit {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    },
+    WaitCheckTx {
+        tracked_msgs: TrackedMsgs,
+        reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
+    },
+}
+
 pub struct ChainRuntime<Endpoint: ChainEndpoint> {
     /// The specific chain this runtime runs against
     chain: Endpoint,
@@ -68,6 +89,18 @@ pub struct ChainRuntime<Endpoint: ChainEndpoint> {
     /// in through this channel.
     request_receiver: channel::Receiver<(Span, ChainRequest)>,
 
+    /// Limits the rate of transaction submissions to the chain, if configured
+    tx_rate_limiter: Option<TokenBucket>,
+
+    /// The transaction submissions held back by the rate limiter, in the order they were requested
+    throttled_txs: VecDeque<(Span, TxRequest)>,
+
+    /// Latency and error statistics of the requests served by the runtime
+    rpc_stats: RefCell<RpcStats>,
+
+    /// Whether the last reply sent by the runtime was an error
+    last_reply_failed: Cell<bool>,
+
     #[allow(dead_code)]
     rt: Arc<TokioRuntime>, // Making this future-proof, so we keep the runtime around.
 }
@@ -115,11 +148,20 @@ where
     fn new(chain: Endpoint, rt: Arc<TokioRuntime>) -> Self {
         let (request_sender, request_receiver) = channel::unbounded();
 
+        let tx_rate_limiter = chain
+            .config()
+            .tx_rate_limit
+            .map(|limit| TokenBucket::new(limit.burst, limit.per_second));
+
         Self {
             rt,
             chain,
             request_sender,
             request_receiver,
+            tx_rate_limiter,
+            throttled_txs: VecDeque::new(),
+            rpc_stats: RefCell::default(),
+            last_reply_failed: Cell::new(false),
         }
     }
 
@@ -130,9 +172,42 @@ where
         Handle::new(chain_id, sender)
     }
 
+    /// Send the result of a request to the handle which submitted it,
+    /// noting whether it failed so that the outcome can be recorded.
+    fn reply<T>(&self, reply_to: ReplyTo<T>, result: Result<T, Error>) -> Result<(), Error> {
+        self.last_reply_failed.set(result.is_err());
+        reply_to.send(result).map_err(Error::send)
+    }
+
+    /// Record the latency and outcome of a request of the given type,
+    /// served since `start`, according to the last reply sent.
+    fn record(&self, request_type: &'static str, start: Instant) {
+        let latency = start.elapsed();
+        let failed = self.last_reply_failed.replace(false);
+
+        self.rpc_stats
+            .borrow_mut()
+            .record(request_type, latency, !failed);
+
+        telemetry!(query_latency, self.chain.id(), request_type, latency);
+
+        if failed {
+            telemetry!(query_error, self.chain.id(), request_type);
+        }
+    }
+
     fn run(mut self) -> Result<(), Error> {
         loop {
+            // Wake up when the next throttled transaction can be submitted,
+            // while serving the other requests meanwhile.
+            let tx_timer = match self.submit_throttled_txs()? {
+                Some(delay) => channel::after(delay),
+                None => channel::never(),
+            };
+
             channel::select! {
+                recv(tx_timer) -> _ => {},
+
                 recv(self.request_receiver) -> event => {
                     let (span, event) = match event {
                         Ok((span, event)) => (span, event),
@@ -144,6 +219,15 @@ where
 
                     let _span = span.entered();
 
+                    // The transactions are recorded once submitted, as the rate limiter may hold them back
+                    let request_type = request_type(&event);
+                    let is_tx = matches!(
+                        event,
+                        ChainRequest::SendMessagesAndWaitCommit { .. }
+                            | ChainRequest::SendMessagesAndWaitCheckTx { .. }
+                    );
+                    let start = Instant::now();
+
                     match event {
                         ChainRequest::Shutdown { reply_to } => {
                             let res = self.chain.shutdown();
@@ -158,16 +242,24 @@ where
                             self.health_check(reply_to)?
                         },
 
+                        ChainRequest::IsSyncing { reply_to } => {
+                            self.is_syncing(reply_to)?
+                        },
+
                         ChainRequest::Subscribe { reply_to } => {
                             self.subscribe(reply_to)?
                         },
 
+                        ChainRequest::SubscribeIfRunning { reply_to } => {
+                            self.subscribe_if_running(reply_to)?
+                        },
+
                         ChainRequest::SendMessagesAndWaitCommit { tracked_msgs, reply_to } => {
-                            self.send_messages_and_wait_commit(tracked_msgs, reply_to)?
+                            self.submit_tx(TxRequest::WaitCommit { tracked_msgs, reply_to })?
                         },
 
                         ChainRequest::SendMessagesAndWaitCheckTx { tracked_msgs, reply_to } => {
-                            self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?
+                            self.submit_tx(TxRequest::WaitCheckTx { tracked_msgs, reply_to })?
                         },
 
                         ChainRequest::Signer { reply_to } => {
@@ -222,14 +314,26 @@ where
                             self.query_all_balances(key_name, reply_to)?
                         },
 
+                        ChainRequest::QueryAddressBalances { address, reply_to } => {
+                            self.query_address_balances(address, reply_to)?
+                        },
+
                         ChainRequest::QueryDenomTrace { hash, reply_to } => {
                             self.query_denom_trace(hash, reply_to)?
                         },
 
+                        ChainRequest::QueryRpcStats { reply_to } => {
+                            self.query_rpc_stats(reply_to)?
+                        },
+
                         ChainRequest::QueryApplicationStatus { reply_to } => {
                             self.query_application_status(reply_to)?
                         },
 
+                        ChainRequest::QueryStakingParams { reply_to } => {
+                            self.query_staking_params(reply_to)?
+                        },
+
                         ChainRequest::QueryClients { request, reply_to } => {
                             self.query_clients(request, reply_to)?
                         },
@@ -334,6 +438,10 @@ where
                             self.query_packet_events(request, reply_to)?
                         },
 
+                        ChainRequest::QueryBlockEvents { height, reply_to } => {
+                            self.query_block_events(height, reply_to)?
+                        },
+
                         ChainRequest::QueryHostConsensusState { request, reply_to } => {
                             self.query_host_consensus_state(request, reply_to)?
                         },
@@ -349,6 +457,18 @@ where
                         ChainRequest::QueryIncentivizedPacket { request, reply_to } => {
                             self.query_incentivized_packet(request, reply_to)?
                         },
+
+                        ChainRequest::QueryIncentivizedPackets { channel_id, port_id, reply_to } => {
+                            self.query_incentivized_packets(&channel_id, &port_id, reply_to)?
+                        },
+
+                        ChainRequest::QueryCounterpartyPayee { channel_id, address, reply_to } => {
+                            self.query_counterparty_payee(&channel_id, &address, reply_to)?
+                        },
+                    }
+
+                    if !is_tx {
+                        self.record(request_type, start);
                     }
                 },
             }
@@ -359,12 +479,78 @@ where
 
     fn health_check(&mut self, reply_to: ReplyTo<HealthCheck>) -> Result<(), Error> {
         let result = self.chain.health_check();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
+    }
+
+    fn is_syncing(&mut self, reply_to: ReplyTo<bool>) -> Result<(), Error> {
+        let result = self.chain.is_syncing();
+        self.reply(reply_to, result)
     }
 
     fn subscribe(&mut self, reply_to: ReplyTo<Subscription>) -> Result<(), Error> {
         let subscription = self.chain.subscribe();
-        reply_to.send(subscription).map_err(Error::send)
+        self.reply(reply_to, subscription)
+    }
+
+    fn subscribe_if_running(
+        &mut self,
+        reply_to: ReplyTo<Option<Subscription>>,
+    ) -> Result<(), Error> {
+        let subscription = self.chain.subscribe_if_running();
+        self.reply(reply_to, subscription)
+    }
+
+    /// Submit the transaction right away, unless the rate limiter holds it back,
+    /// in which case it is submitted once the previously throttled ones are.
+    fn submit_tx(&mut self, request: TxRequest) -> Result<(), Error> {
+        self.throttled_txs.push_back((Span::current(), request));
+
+        if self.submit_throttled_txs()?.is_some() {
+            let chain_id = self.chain.id();
+
+            debug!(chain = %chain_id, "transaction submission throttled");
+
+            telemetry!(tx_throttled, chain_id, 1);
+        }
+
+        Ok(())
+    }
+
+    /// Submit the throttled transactions which the rate limiter, if any, allows.
+    ///
+    /// Returns how long to wait until the next throttled transaction can be submitted, if any.
+    fn submit_throttled_txs(&mut self) -> Result<Option<Duration>, Error> {
+        while !self.throttled_txs.is_empty() {
+            if let Some(limiter) = self.tx_rate_limiter.as_mut() {
+                if let Err(delay) = limiter.try_acquire() {
+                    return Ok(Some(delay));
+                }
+            }
+
+            if let Some((span, request)) = self.throttled_txs.pop_front() {
+                let _span = span.entered();
+                let start = Instant::now();
+
+                match request {
+                    TxRequest::WaitCommit {
+                        tracked_msgs,
+                        reply_to,
+                    } => {
+                        self.send_messages_and_wait_commit(tracked_msgs, reply_to)?;
+                        self.record("send_messages_and_wait_commit", start);
+                    }
+                    TxRequest::WaitCheckTx {
+                        tracked_msgs,
+                        reply_to,
+                    } => {
+                        self.send_messages_and_wait_check_tx(tracked_msgs, reply_to)?;
+                        self.record("send_messages_and_wait_check_tx", start);
+                    }
+                }
+            }
+        }
+
+        Ok(None)
     }
 
     fn send_messages_and_wait_commit(
@@ -372,8 +558,15 @@ where
         tracked_msgs: TrackedMsgs,
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     ) -> Result<(), Error> {
+        let tracking_id = tracked_msgs.tracking_id();
         let result = self.chain.send_messages_and_wait_commit(tracked_msgs);
-        reply_to.send(result).map_err(Error::send)
+
+        if let Ok(events) = &result {
+            sink::publish_tx_result(&self.chain.id(), tracking_id, events);
+            webhook::notify(&self.chain.id(), events);
+        }
+
+        self.reply(reply_to, result)
     }
 
     fn send_messages_and_wait_check_tx(
@@ -382,7 +575,7 @@ where
         reply_to: ReplyTo<Vec<tendermint_rpc::endpoint::broadcast::tx_sync::Response>>,
     ) -> Result<(), Error> {
         let result = self.chain.send_messages_and_wait_check_tx(tracked_msgs);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_balance(
@@ -395,7 +588,7 @@ where
             .chain
             .query_balance(key_name.as_deref(), denom.as_deref());
 
-        reply_to.send(balance).map_err(Error::send)
+        self.reply(reply_to, balance)
     }
 
     fn query_all_balances(
@@ -404,32 +597,51 @@ where
         reply_to: ReplyTo<Vec<Balance>>,
     ) -> Result<(), Error> {
         let balances = self.chain.query_all_balances(key_name.as_deref());
-        reply_to.send(balances).map_err(Error::send)
+        self.reply(reply_to, balances)
+    }
+
+    fn query_address_balances(
+        &self,
+        address: String,
+        reply_to: ReplyTo<Vec<Balance>>,
+    ) -> Result<(), Error> {
+        let balances = self.chain.query_address_balances(&address);
+        self.reply(reply_to, balances)
     }
 
     fn query_denom_trace(&self, hash: String, reply_to: ReplyTo<DenomTrace>) -> Result<(), Error> {
         let denom_trace = self.chain.query_denom_trace(hash);
-        reply_to.send(denom_trace).map_err(Error::send)
+        self.reply(reply_to, denom_trace)
+    }
+
+    fn query_rpc_stats(&self, reply_to: ReplyTo<Vec<QueryStatsSummary>>) -> Result<(), Error> {
+        let summary = self.rpc_stats.borrow().summary();
+        self.reply(reply_to, Ok(summary))
     }
 
     fn query_application_status(&self, reply_to: ReplyTo<ChainStatus>) -> Result<(), Error> {
         let latest_timestamp = self.chain.query_application_status();
-        reply_to.send(latest_timestamp).map_err(Error::send)
+        self.reply(reply_to, latest_timestamp)
+    }
+
+    fn query_staking_params(&self, reply_to: ReplyTo<ChainStakingParams>) -> Result<(), Error> {
+        let result = self.chain.query_staking_params();
+        self.reply(reply_to, result)
     }
 
     fn get_signer(&mut self, reply_to: ReplyTo<Signer>) -> Result<(), Error> {
         let result = self.chain.get_signer();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn get_config(&self, reply_to: ReplyTo<ChainConfig>) -> Result<(), Error> {
         let result = Ok(self.chain.config().clone());
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn get_key(&mut self, reply_to: ReplyTo<AnySigningKeyPair>) -> Result<(), Error> {
         let result = self.chain.get_key().map(Into::into);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn add_key(
@@ -442,12 +654,12 @@ where
             .downcast()
             .ok_or_else(|| Error::invalid_key_type(key.key_type()))?;
         let result = self.chain.add_key(&key_name, key);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn ibc_version(&mut self, reply_to: ReplyTo<Option<semver::Version>>) -> Result<(), Error> {
         let result = self.chain.ibc_version();
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_header(
@@ -466,7 +678,7 @@ where
                 (header, support)
             });
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     /// Constructs a client state for the given height
@@ -481,7 +693,7 @@ where
             .build_client_state(height, settings)
             .map(|cs| cs.into());
 
-        reply_to.send(client_state).map_err(Error::send)
+        self.reply(reply_to, client_state)
     }
 
     /// Constructs a consensus state for the given height
@@ -499,7 +711,7 @@ where
             .build_consensus_state(verified)
             .map(|cs| cs.into());
 
-        reply_to.send(consensus_state).map_err(Error::send)
+        self.reply(reply_to, consensus_state)
     }
 
     /// Constructs AnyMisbehaviour for the update event
@@ -511,7 +723,7 @@ where
     ) -> Result<(), Error> {
         let misbehaviour = self.chain.check_misbehaviour(&update_event, &client_state);
 
-        reply_to.send(misbehaviour).map_err(Error::send)
+        self.reply(reply_to, misbehaviour)
     }
 
     fn build_connection_proofs_and_client_state(
@@ -529,7 +741,7 @@ where
             height,
         );
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_clients(
@@ -538,7 +750,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedAnyClientState>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_clients(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_client_connections(
@@ -547,7 +759,7 @@ where
         reply_to: ReplyTo<Vec<ConnectionId>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_client_connections(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_client_state(
@@ -558,7 +770,7 @@ where
     ) -> Result<(), Error> {
         let res = self.chain.query_client_state(request, include_proof);
 
-        reply_to.send(res).map_err(Error::send)
+        self.reply(reply_to, res)
     }
 
     fn query_upgraded_client_state(
@@ -568,7 +780,7 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_upgraded_client_state(request);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_consensus_state_heights(
@@ -577,7 +789,7 @@ where
         reply_to: ReplyTo<Vec<Height>>,
     ) -> Result<(), Error> {
         let heights = self.chain.query_consensus_state_heights(request);
-        reply_to.send(heights).map_err(Error::send)
+        self.reply(reply_to, heights)
     }
 
     fn query_consensus_state(
@@ -588,7 +800,7 @@ where
     ) -> Result<(), Error> {
         let res = self.chain.query_consensus_state(request, include_proof);
 
-        reply_to.send(res).map_err(Error::send)
+        self.reply(reply_to, res)
     }
 
     fn query_upgraded_consensus_state(
@@ -598,17 +810,17 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_upgraded_consensus_state(request);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_commitment_prefix(&self, reply_to: ReplyTo<CommitmentPrefix>) -> Result<(), Error> {
         let prefix = self.chain.query_commitment_prefix();
-        reply_to.send(prefix).map_err(Error::send)
+        self.reply(reply_to, prefix)
     }
 
     fn query_compatible_versions(&self, reply_to: ReplyTo<Vec<Version>>) -> Result<(), Error> {
         let versions = self.chain.query_compatible_versions();
-        reply_to.send(versions).map_err(Error::send)
+        self.reply(reply_to, versions)
     }
 
     fn query_connection(
@@ -618,7 +830,7 @@ where
         reply_to: ReplyTo<(ConnectionEnd, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let connection_end = self.chain.query_connection(request, include_proof);
-        reply_to.send(connection_end).map_err(Error::send)
+        self.reply(reply_to, connection_end)
     }
 
     fn query_connections(
@@ -627,7 +839,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedConnectionEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_connections(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_connection_channels(
@@ -636,7 +848,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_connection_channels(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channels(
@@ -645,7 +857,7 @@ where
         reply_to: ReplyTo<Vec<IdentifiedChannelEnd>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channels(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channel(
@@ -655,7 +867,7 @@ where
         reply_to: ReplyTo<(ChannelEnd, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channel(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_channel_client_state(
@@ -664,7 +876,7 @@ where
         reply_to: ReplyTo<Option<IdentifiedAnyClientState>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_channel_client_state(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_channel_proofs(
@@ -678,7 +890,7 @@ where
             .chain
             .build_channel_proofs(&port_id, &channel_id, height);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn build_packet_proofs(
@@ -694,7 +906,7 @@ where
             self.chain
                 .build_packet_proofs(packet_type, port_id, channel_id, sequence, height);
 
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_commitment(
@@ -704,7 +916,7 @@ where
         reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_commitment(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_commitments(
@@ -713,7 +925,7 @@ where
         reply_to: ReplyTo<(Vec<Sequence>, Height)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_commitments(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_receipt(
@@ -723,7 +935,7 @@ where
         reply_to: ReplyTo<(Vec<u8>, Option<MerkleProof>)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_receipt(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_unreceived_packets(
@@ -732,7 +944,7 @@ where
         reply_to: ReplyTo<Vec<Sequence>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_unreceived_packets(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_acknowledgement(
@@ -744,7 +956,7 @@ where
         let result = self
             .chain
             .query_packet_acknowledgement(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_packet_acknowledgements(
@@ -753,7 +965,7 @@ where
         reply_to: ReplyTo<(Vec<Sequence>, Height)>,
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_acknowledgements(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_unreceived_acknowledgement(
@@ -762,7 +974,7 @@ where
         reply_to: ReplyTo<Vec<Sequence>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_unreceived_acknowledgements(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_next_sequence_receive(
@@ -774,7 +986,7 @@ where
         let result = self
             .chain
             .query_next_sequence_receive(request, include_proof);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
     }
 
     fn query_txs(
@@ -783,7 +995,16 @@ where
         reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
     ) -> Result<(), Error> {
         let result = self.chain.query_txs(request);
-        reply_to.send(result).map_err(Error::send)
+        self.reply(reply_to, result)
+    }
+
+    fn query_block_events(
+        &self,
+        height: Height,
+        reply_to: ReplyTo<Vec<IbcEventWithHeight>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_block_events(height);
+        self.reply(reply_to, result)
     }
 
     fn query_packet_events(
@@ -793,7 +1014,7 @@ where
     ) -> Result<(), Error> {
         let result = self.chain.query_packet_events(request);
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -808,7 +1029,7 @@ where
             .query_host_consensus_state(request)
             .map(|h| h.into());
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -824,7 +1045,7 @@ where
             self.chain
                 .maybe_register_counterparty_payee(channel_id, port_id, counterparty_payee);
 
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -835,7 +1056,7 @@ where
         reply_to: ReplyTo<Vec<CrossChainQueryResponse>>,
     ) -> Result<(), Error> {
         let result = self.chain.cross_chain_query(request);
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
@@ -846,8 +1067,98 @@ where
         reply_to: ReplyTo<QueryIncentivizedPacketResponse>,
     ) -> Result<(), Error> {
         let result = self.chain.query_incentivized_packet(request);
-        reply_to.send(result).map_err(Error::send)?;
+        self.reply(reply_to, result)?;
 
         Ok(())
     }
+
+    fn query_incentivized_packets(
+        &self,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        reply_to: ReplyTo<Vec<IdentifiedPacketFees>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_incentivized_packets(channel_id, port_id);
+        self.reply(reply_to, result)?;
+
+        Ok(())
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        channel_id: &ChannelId,
+        address: &Signer,
+        reply_to: ReplyTo<Option<String>>,
+    ) -> Result<(), Error> {
+        let result = self.chain.query_counterparty_payee(channel_id, address);
+        self.reply(reply_to, result)?;
+
+        Ok(())
+    }
+}
+
+/// The type of the given request, under which its latency and outcome are recorded.
+fn request_type(request: &ChainRequest) -> &'static str {
+    match request {
+        ChainRequest::Shutdown { .. } => "shutdown",
+        ChainRequest::HealthCheck { .. } => "health_check",
+        ChainRequest::IsSyncing { .. } => "is_syncing",
+        ChainRequest::Subscribe { .. } => "subscribe",
+        ChainRequest::SubscribeIfRunning { .. } => "subscribe_if_running",
+        ChainRequest::SendMessagesAndWaitCommit { .. } => "send_messages_and_wait_commit",
+        ChainRequest::SendMessagesAndWaitCheckTx { .. } => "send_messages_and_wait_check_tx",
+        ChainRequest::Config { .. } => "get_config",
+        ChainRequest::Signer { .. } => "get_signer",
+        ChainRequest::GetKey { .. } => "get_key",
+        ChainRequest::AddKey { .. } => "add_key",
+        ChainRequest::IbcVersion { .. } => "ibc_version",
+        ChainRequest::QueryBalance { .. } => "query_balance",
+        ChainRequest::QueryAllBalances { .. } => "query_all_balances",
+        ChainRequest::QueryAddressBalances { .. } => "query_address_balances",
+        ChainRequest::QueryDenomTrace { .. } => "query_denom_trace",
+        ChainRequest::QueryRpcStats { .. } => "query_rpc_stats",
+        ChainRequest::QueryApplicationStatus { .. } => "query_application_status",
+        ChainRequest::QueryStakingParams { .. } => "query_staking_params",
+        ChainRequest::QueryClients { .. } => "query_clients",
+        ChainRequest::BuildHeader { .. } => "build_header",
+        ChainRequest::BuildClientState { .. } => "build_client_state",
+        ChainRequest::BuildConsensusState { .. } => "build_consensus_state",
+        ChainRequest::BuildMisbehaviour { .. } => "check_misbehaviour",
+        ChainRequest::BuildConnectionProofsAndClientState { .. } => {
+            "build_connection_proofs_and_client_state"
+        }
+        ChainRequest::QueryClientState { .. } => "query_client_state",
+        ChainRequest::QueryClientConnections { .. } => "query_client_connections",
+        ChainRequest::QueryConsensusState { .. } => "query_consensus_state",
+        ChainRequest::QueryConsensusStateHeights { .. } => "query_consensus_state_heights",
+        ChainRequest::QueryUpgradedClientState { .. } => "query_upgraded_client_state",
+        ChainRequest::QueryUpgradedConsensusState { .. } => "query_upgraded_consensus_state",
+        ChainRequest::QueryCommitmentPrefix { .. } => "query_commitment_prefix",
+        ChainRequest::QueryCompatibleVersions { .. } => "query_compatible_versions",
+        ChainRequest::QueryConnection { .. } => "query_connection",
+        ChainRequest::QueryConnections { .. } => "query_connections",
+        ChainRequest::QueryConnectionChannels { .. } => "query_connection_channels",
+        ChainRequest::QueryChannels { .. } => "query_channels",
+        ChainRequest::QueryChannel { .. } => "query_channel",
+        ChainRequest::QueryChannelClientState { .. } => "query_channel_client_state",
+        ChainRequest::QueryNextSequenceReceive { .. } => "query_next_sequence_receive",
+        ChainRequest::BuildChannelProofs { .. } => "build_channel_proofs",
+        ChainRequest::BuildPacketProofs { .. } => "build_packet_proofs",
+        ChainRequest::QueryPacketCommitment { .. } => "query_packet_commitment",
+        ChainRequest::QueryPacketCommitments { .. } => "query_packet_commitments",
+        ChainRequest::QueryPacketReceipt { .. } => "query_packet_receipt",
+        ChainRequest::QueryUnreceivedPackets { .. } => "query_unreceived_packets",
+        ChainRequest::QueryPacketAcknowledgement { .. } => "query_packet_acknowledgement",
+        ChainRequest::QueryPacketAcknowledgements { .. } => "query_packet_acknowledgements",
+        ChainRequest::QueryUnreceivedAcknowledgement { .. } => "query_unreceived_acknowledgements",
+        ChainRequest::QueryPacketEventDataFromTxs { .. } => "query_txs",
+        ChainRequest::QueryPacketEventData { .. } => "query_packet_events",
+        ChainRequest::QueryBlockEvents { .. } => "query_block_events",
+        ChainRequest::QueryHostConsensusState { .. } => "query_host_consensus_state",
+        ChainRequest::MaybeRegisterCounterpartyPayee { .. } => "maybe_register_counterparty_payee",
+        ChainRequest::CrossChainQuery { .. } => "cross_chain_query",
+        ChainRequest::QueryIncentivizedPacket { .. } => "query_incentivized_packet",
+        ChainRequest::QueryIncentivizedPackets { .. } => "query_incentivized_packets",
+        ChainRequest::QueryCounterpartyPayee { .. } => "query_counterparty_payee",
+    }
 }
@@ -40,7 +40,7 @@ use crate::keyring::Store;
 pub use crate::config::Error as ConfigError;
 pub use error::Error;
 
-pub use filter::PacketFilter;
+pub use filter::{PacketDirection, PacketFilter};
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct GasPrice {
@@ -177,6 +177,14 @@ pub mod default {
         Duration::from_millis(500)
     }
 
+    pub fn event_rpc_fallback() -> bool {
+        true
+    }
+
+    pub fn event_poll_interval() -> Duration {
+        Duration::from_secs(1)
+    }
+
     pub fn clock_drift() -> Duration {
         Duration::from_secs(5)
     }
@@ -197,9 +205,29 @@ pub mod default {
         false
     }
 
+    pub fn max_queued_operational_data() -> usize {
+        1000
+    }
+
+    pub fn sequence_ordering() -> bool {
+        false
+    }
+
+    pub fn sequence_gap_tolerance() -> u64 {
+        5
+    }
+
     pub fn max_grpc_decoding_size() -> Byte {
         Byte::from_bytes(33554432)
     }
+
+    pub fn max_ws_message_size() -> Byte {
+        Byte::from_bytes(64 << 20)
+    }
+
+    pub fn max_ws_frame_size() -> Byte {
+        Byte::from_bytes(16 << 20)
+    }
 }
 
 #[derive(Clone, Debug, Default, Deserialize, Serialize)]
@@ -213,6 +241,14 @@ pub struct Config {
     pub rest: RestConfig,
     #[serde(default)]
     pub telemetry: TelemetryConfig,
+    #[serde(default)]
+    pub event_sink: EventSinkConfig,
+    #[serde(default)]
+    pub checkpoint: CheckpointConfig,
+    #[serde(default)]
+    pub batch_retry: BatchRetryConfig,
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub webhooks: Vec<WebhookConfig>,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub chains: Vec<ChainConfig>,
 }
@@ -248,6 +284,23 @@ impl Config {
         }
     }
 
+    /// Returns the direction in which packets are relayed on the channel
+    /// [`ChannelId`] on [`ChainId`], as configured in the chain's packet filter.
+    pub fn packet_direction(&self, chain_id: &ChainId, channel_id: &ChannelId) -> PacketDirection {
+        self.find_chain(chain_id)
+            .map(|chain_config| chain_config.packet_filter.direction(channel_id))
+            .unwrap_or_default()
+    }
+
+    /// Returns the interval at which packets are cleared on the channel [`ChannelId`]
+    /// on [`ChainId`], as configured in the chain's packet filter, falling back to
+    /// the global `clear_interval` of the packets mode.
+    pub fn clear_interval(&self, chain_id: &ChainId, channel_id: &ChannelId) -> u64 {
+        self.find_chain(chain_id)
+            .and_then(|chain_config| chain_config.packet_filter.clear_interval(channel_id))
+            .unwrap_or(self.mode.packets.clear_interval)
+    }
+
     pub fn chains_map(&self) -> BTreeMap<&ChainId, &ChainConfig> {
         self.chains.iter().map(|c| (&c.id, c)).collect()
     }
@@ -324,6 +377,12 @@ pub struct Packets {
     pub tx_confirmation: bool,
     #[serde(default = "default::auto_register_counterparty_payee")]
     pub auto_register_counterparty_payee: bool,
+    #[serde(default = "default::max_queued_operational_data")]
+    pub max_queued_operational_data: usize,
+    #[serde(default = "default::sequence_ordering")]
+    pub sequence_ordering: bool,
+    #[serde(default = "default::sequence_gap_tolerance")]
+    pub sequence_gap_tolerance: u64,
 }
 
 impl Default for Packets {
@@ -334,6 +393,9 @@ impl Default for Packets {
             clear_on_start: default::clear_on_start(),
             tx_confirmation: default::tx_confirmation(),
             auto_register_counterparty_payee: default::auto_register_counterparty_payee(),
+            max_queued_operational_data: default::max_queued_operational_data(),
+            sequence_ordering: default::sequence_ordering(),
+            sequence_gap_tolerance: default::sequence_gap_tolerance(),
         }
     }
 }
@@ -373,6 +435,8 @@ impl Display for LogLevel {
 #[serde(default, deny_unknown_fields)]
 pub struct GlobalConfig {
     pub log_level: LogLevel,
+    /// Whether to replace the packet data with its length and hash in the logs and in the output
+    pub redact_packet_data: bool,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -396,6 +460,118 @@ impl Default for TelemetryConfig {
     }
 }
 
+/// The kind of message bus to which the event sink publishes.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventSinkKind {
+    #[default]
+    Nats,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct EventSinkConfig {
+    pub enabled: bool,
+    #[serde(default)]
+    pub kind: EventSinkKind,
+    pub address: String,
+    pub subject: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    #[serde(default)]
+    pub tls_required: bool,
+    /// PEM file of the root certificates used to verify the server, in addition to the system ones
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls_root_certificate: Option<PathBuf>,
+}
+
+impl Default for EventSinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kind: EventSinkKind::Nats,
+            address: "127.0.0.1:4222".to_string(),
+            subject: "hermes.events".to_string(),
+            user: None,
+            password: None,
+            token: None,
+            tls_required: false,
+            tls_root_certificate: None,
+        }
+    }
+}
+
+/// Display metadata of a denomination, eg. `uatom` displayed as `ATOM` with 6 decimals.
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct DenomMetadata {
+    pub denom: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct WebhookConfig {
+    /// Either an `http` or an `https` URL
+    pub url: String,
+    /// Types of the events to notify, eg. `chain_error` or `timeout_packet`
+    pub events: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain: Option<ChainId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub channel: Option<ChannelId>,
+    /// Key used to sign the payloads with HMAC-SHA256
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub secret: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CheckpointConfig {
+    pub enabled: bool,
+    /// Defaults to `$HOME/.hermes/checkpoints.json` when unset
+    pub path: Option<PathBuf>,
+    pub max_catch_up_blocks: u64,
+}
+
+impl Default for CheckpointConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            max_catch_up_blocks: 1000,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchRetryConfig {
+    pub enabled: bool,
+    pub max_attempts: u32,
+    #[serde(with = "humantime_serde")]
+    pub backoff: Duration,
+    pub capacity: usize,
+    pub max_dead_letters: usize,
+}
+
+impl Default for BatchRetryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_attempts: 5,
+            backoff: Duration::from_secs(1),
+            capacity: 100,
+            max_dead_letters: 100,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct RestConfig {
@@ -443,6 +619,46 @@ impl Display for AddressType {
     }
 }
 
+/// Limits the rate at which transactions are submitted to a chain,
+/// across all the workers relaying to that chain.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TxRateLimit {
+    /// Maximum number of transactions which can be submitted in a burst
+    pub burst: u32,
+    /// Sustained number of transactions which can be submitted per second
+    pub per_second: f64,
+}
+
+/// Bounds within which the gas multiplier of a chain is tuned, based on
+/// the gas used by the transactions submitted to that chain.
+#[derive(Copy, Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GasMultiplierTuning {
+    /// Smallest gas multiplier which can be used
+    pub min: GasMultiplier,
+    /// Largest gas multiplier which can be used
+    pub max: GasMultiplier,
+}
+
+/// How the events emitted by a chain are received.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "mode", rename_all = "lowercase", deny_unknown_fields)]
+pub enum EventSourceMode {
+    /// Subscribe to the events pushed over the WebSocket endpoint
+    #[default]
+    Push,
+    /// Poll the RPC endpoint for the events of every new block
+    Pull {
+        #[serde(default = "default::event_poll_interval", with = "humantime_serde")]
+        interval: Duration,
+        /// Poll around the expected time of the next block, based on the observed
+        /// block time, and at least every `interval`
+        #[serde(default)]
+        adaptive: bool,
+    },
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct GenesisRestart {
@@ -463,6 +679,16 @@ pub struct ChainConfig {
     pub rpc_timeout: Duration,
     #[serde(default = "default::batch_delay", with = "humantime_serde")]
     pub batch_delay: Duration,
+    #[serde(default = "default::event_rpc_fallback")]
+    pub event_rpc_fallback: bool,
+    #[serde(default)]
+    pub event_source: EventSourceMode,
+
+    /// Maximum number of blocks the event monitor may fall behind the latest height
+    /// of the node before it is considered stalled and subscribes again.
+    /// The lag is not checked if unset.
+    pub max_block_lag: Option<u64>,
+
     #[serde(default = "default::trusted_node")]
     pub trusted_node: bool,
     pub account_prefix: String,
@@ -474,6 +700,10 @@ pub struct ChainConfig {
     pub default_gas: Option<u64>,
     pub max_gas: Option<u64>,
 
+    /// Directory where to export the evidence of light client attacks
+    /// detected on this chain, if any.
+    pub evidence_dir: Option<PathBuf>,
+
     // This field is only meant to be set via the `update client` command,
     // for when we need to ugprade a client across a genesis restart and
     // therefore need and archive node to fetch blocks from.
@@ -482,14 +712,20 @@ pub struct ChainConfig {
     // This field is deprecated, use `gas_multiplier` instead
     pub gas_adjustment: Option<f64>,
     pub gas_multiplier: Option<GasMultiplier>,
+    pub gas_multiplier_tuning: Option<GasMultiplierTuning>,
 
     pub fee_granter: Option<String>,
     #[serde(default)]
     pub max_msg_num: MaxMsgNum,
     #[serde(default)]
     pub max_tx_size: MaxTxSize,
+    pub tx_rate_limit: Option<TxRateLimit>,
     #[serde(default = "default::max_grpc_decoding_size")]
     pub max_grpc_decoding_size: Byte,
+    #[serde(default = "default::max_ws_message_size")]
+    pub max_ws_message_size: Byte,
+    #[serde(default = "default::max_ws_frame_size")]
+    pub max_ws_frame_size: Byte,
 
     /// A correction parameter that helps deal with clocks that are only approximately synchronized
     /// between the source and destination chains for a client.
@@ -547,6 +783,16 @@ pub struct ChainConfig {
     pub address_type: AddressType,
     #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
     pub extension_options: Vec<ExtensionOption>,
+    #[serde(default = "Vec::new", skip_serializing_if = "Vec::is_empty")]
+    pub denom_metadata: Vec<DenomMetadata>,
+}
+
+impl ChainConfig {
+    /// Format an amount of the given denomination for display, in whole units
+    /// of its symbol if its metadata is configured.
+    pub fn format_amount(&self, amount: &str, denom: &str) -> String {
+        crate::denom::format_amount(&self.denom_metadata, amount, denom)
+    }
 }
 
 /// Attempt to load and parse the TOML config file as a `Config`.
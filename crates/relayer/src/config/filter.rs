@@ -19,6 +19,10 @@ pub struct PacketFilter {
     pub channel_policy: ChannelPolicy,
     #[serde(default)]
     pub min_fees: HashMap<ChannelFilterMatch, FeePolicy>,
+    #[serde(default)]
+    pub directions: HashMap<ChannelFilterMatch, PacketDirection>,
+    #[serde(default)]
+    pub clear_intervals: HashMap<ChannelFilterMatch, u64>,
 }
 
 impl Default for PacketFilter {
@@ -27,6 +31,8 @@ impl Default for PacketFilter {
         Self {
             channel_policy: ChannelPolicy::default(),
             min_fees: HashMap::new(),
+            directions: HashMap::new(),
+            clear_intervals: HashMap::new(),
         }
     }
 }
@@ -39,6 +45,8 @@ impl PacketFilter {
         Self {
             channel_policy,
             min_fees,
+            directions: HashMap::new(),
+            clear_intervals: HashMap::new(),
         }
     }
 
@@ -48,6 +56,75 @@ impl PacketFilter {
             HashMap::new(),
         )
     }
+
+    /// Returns the direction in which packets are relayed on the channel with [`ChannelId`],
+    /// as configured by its best matching entry (see `best_match`).
+    pub fn direction(&self, channel_id: &ChannelId) -> PacketDirection {
+        best_match(&self.directions, channel_id)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Returns the packet clearing interval configured for the channel with [`ChannelId`]
+    /// by its best matching entry (see `best_match`), if it overrides the global one.
+    pub fn clear_interval(&self, channel_id: &ChannelId) -> Option<u64> {
+        best_match(&self.clear_intervals, channel_id).copied()
+    }
+}
+
+/// Returns the value of the entry which best matches the channel with [`ChannelId`]:
+/// the entry for this exact channel if any, or else the matching wildcard with the most
+/// characters besides `*`, eg. `channel-1*` rather than `channel-*`. Among equally
+/// specific wildcards, the first pattern in lexicographic order wins, so that the result
+/// does not depend on the iteration order of the map.
+fn best_match<'a, V>(
+    entries: &'a HashMap<ChannelFilterMatch, V>,
+    channel_id: &ChannelId,
+) -> Option<&'a V> {
+    if let Some(value) = entries.get(&FilterPattern::Exact(channel_id.clone())) {
+        return Some(value);
+    }
+
+    entries
+        .iter()
+        .filter_map(|(channel, value)| match channel {
+            FilterPattern::Wildcard(wildcard) if wildcard.is_match(channel_id.as_str()) => {
+                Some((wildcard, value))
+            }
+            _ => None,
+        })
+        .max_by(|(a, _), (b, _)| {
+            a.specificity()
+                .cmp(&b.specificity())
+                .then_with(|| b.pattern.cmp(&a.pattern))
+        })
+        .map(|(_, value)| value)
+}
+
+/// Represents the direction(s) in which packets are relayed on a channel,
+/// from the point of view of the chain whose configuration specifies it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PacketDirection {
+    /// Relay packets in both directions.
+    #[default]
+    Both,
+    /// Only relay the packets sent from this chain, and their acknowledgements.
+    Outbound,
+    /// Only relay the packets sent to this chain, and their acknowledgements.
+    Inbound,
+}
+
+impl PacketDirection {
+    /// Whether the packets sent from this chain are relayed.
+    pub fn relays_outbound(&self) -> bool {
+        !matches!(self, Self::Inbound)
+    }
+
+    /// Whether the packets sent to this chain are relayed.
+    pub fn relays_inbound(&self) -> bool {
+        !matches!(self, Self::Outbound)
+    }
 }
 
 /// Represents the ways in which packets can be filtered.
@@ -249,6 +326,12 @@ impl Wildcard {
     pub fn is_match(&self, text: &str) -> bool {
         self.regex.is_match(text)
     }
+
+    /// The number of characters of the pattern besides `*`,
+    /// the more of which the fewer values the pattern matches.
+    fn specificity(&self) -> usize {
+        self.pattern.chars().filter(|c| *c != '*').count()
+    }
 }
 
 impl FromStr for Wildcard {
@@ -471,6 +554,105 @@ mod tests {
         println!("{toml_str}");
     }
 
+    #[test]
+    fn packet_filter_directions() {
+        use std::str::FromStr;
+
+        let toml_content = r#"
+            policy = 'allowall'
+
+            [directions]
+            'channel-0' = 'outbound'
+            'channel-1*' = 'inbound'
+            "#;
+
+        let pf: PacketFilter = toml::from_str(toml_content).expect("could not parse packet filter");
+
+        let direction = |id: &str| pf.direction(&ChannelId::from_str(id).unwrap());
+
+        assert_eq!(direction("channel-0"), PacketDirection::Outbound);
+        assert_eq!(direction("channel-12"), PacketDirection::Inbound);
+        assert_eq!(direction("channel-2"), PacketDirection::Both);
+
+        assert!(PacketDirection::Outbound.relays_outbound());
+        assert!(!PacketDirection::Outbound.relays_inbound());
+        assert!(!PacketDirection::Inbound.relays_outbound());
+        assert!(PacketDirection::Both.relays_inbound());
+    }
+
+    #[test]
+    fn packet_filter_directions_overlapping() {
+        use std::str::FromStr;
+
+        let toml_content = r#"
+            policy = 'allowall'
+
+            [directions]
+            'channel-*' = 'inbound'
+            'channel-1*' = 'outbound'
+            '*-12' = 'both'
+            'channel-12' = 'inbound'
+            "#;
+
+        let pf: PacketFilter = toml::from_str(toml_content).expect("could not parse packet filter");
+
+        let direction = |id: &str| pf.direction(&ChannelId::from_str(id).unwrap());
+
+        // The exact entry wins over every wildcard
+        assert_eq!(direction("channel-12"), PacketDirection::Inbound);
+        // `channel-1*` is more specific than `channel-*`
+        assert_eq!(direction("channel-13"), PacketDirection::Outbound);
+        assert_eq!(direction("channel-2"), PacketDirection::Inbound);
+        assert_eq!(direction("other-12"), PacketDirection::Both);
+    }
+
+    #[test]
+    fn packet_filter_clear_intervals() {
+        use std::str::FromStr;
+
+        let toml_content = r#"
+            policy = 'allowall'
+
+            [clear_intervals]
+            'channel-0' = 0
+            'channel-1*' = 50
+            "#;
+
+        let pf: PacketFilter = toml::from_str(toml_content).expect("could not parse packet filter");
+
+        let clear_interval = |id: &str| pf.clear_interval(&ChannelId::from_str(id).unwrap());
+
+        assert_eq!(clear_interval("channel-0"), Some(0));
+        assert_eq!(clear_interval("channel-12"), Some(50));
+        assert_eq!(clear_interval("channel-2"), None);
+    }
+
+    #[test]
+    fn packet_filter_clear_intervals_overlapping() {
+        use std::str::FromStr;
+
+        let toml_content = r#"
+            policy = 'allowall'
+
+            [clear_intervals]
+            'channel-*' = 100
+            'channel-1*' = 50
+            '*-12' = 10
+            'channel-12' = 0
+            "#;
+
+        let pf: PacketFilter = toml::from_str(toml_content).expect("could not parse packet filter");
+
+        let clear_interval = |id: &str| pf.clear_interval(&ChannelId::from_str(id).unwrap());
+
+        // The exact entry wins over every wildcard
+        assert_eq!(clear_interval("channel-12"), Some(0));
+        // `channel-1*` is more specific than `channel-*`
+        assert_eq!(clear_interval("channel-13"), Some(50));
+        assert_eq!(clear_interval("channel-2"), Some(100));
+        assert_eq!(clear_interval("other-12"), Some(10));
+    }
+
     #[test]
     fn channel_filter_iter_exact() {
         let toml_content = r#"
@@ -259,6 +259,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
             let connections: Vec<IdentifiedConnectionEnd> = counterparty_chain
                 .query_connections(QueryConnectionsRequest {
                     pagination: Some(PageRequest::all()),
+                    query_height: QueryHeight::Latest,
                 })
                 .map_err(ConnectionError::relayer)?;
 
@@ -978,6 +979,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
 
         // TODO - check that the src connection is consistent with the try options
 
+        self.validate_counterparty_prefix(src_connection_id, &src_connection)?;
+
         // Cross-check the delay_period
         let delay = if src_connection.delay_period() != self.delay_period {
             warn!("`delay_period` for ConnectionEnd @{} is {}s; delay period on local Connection object is set to {}s",
@@ -1104,6 +1107,33 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
         }
     }
 
+    /// Check that the commitment prefix which the given connection end on the source chain
+    /// expects for the destination chain is the actual prefix of the destination chain, since
+    /// the proofs of the destination chain would otherwise never verify on the source chain.
+    fn validate_counterparty_prefix(
+        &self,
+        src_connection_id: &ConnectionId,
+        src_connection: &ConnectionEnd,
+    ) -> Result<(), ConnectionError> {
+        let dst_prefix = self
+            .dst_chain()
+            .query_commitment_prefix()
+            .map_err(|e| ConnectionError::chain_query(self.dst_chain().id(), e))?;
+
+        let stored_prefix = src_connection.counterparty().prefix();
+
+        if stored_prefix != &dst_prefix {
+            return Err(ConnectionError::commitment_prefix_mismatch(
+                self.src_chain().id(),
+                src_connection_id.clone(),
+                stored_prefix.clone(),
+                dst_prefix,
+            ));
+        }
+
+        Ok(())
+    }
+
     /// Attempts to build a MsgConnOpenAck.
     ///
     /// Return the messages and the app height the destination chain must reach
@@ -1132,6 +1162,8 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> Connection<ChainA, ChainB> {
 
         // TODO - check that the src connection is consistent with the ack options
 
+        self.validate_counterparty_prefix(src_connection_id, &src_connection)?;
+
         // Build add **send** the message(s) for updating client on source.
         // TODO - add check if it is required
         let src_client_target_height = self
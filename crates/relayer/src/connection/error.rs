@@ -3,6 +3,7 @@ use core::time::Duration;
 use flex_error::{define_error, ErrorMessageTracer};
 
 use ibc_relayer_types::core::ics03_connection::connection::{Counterparty, State};
+use ibc_relayer_types::core::ics23_commitment::commitment::CommitmentPrefix;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId, ConnectionId};
 use ibc_relayer_types::events::IbcEvent;
 
@@ -116,6 +117,18 @@ define_error! {
                     e.source_chain_id, e.destination_chain_id)
             },
 
+        CommitmentPrefixMismatch
+            {
+                chain_id: ChainId,
+                connection_id: ConnectionId,
+                stored: CommitmentPrefix,
+                actual: CommitmentPrefix,
+            }
+            |e| {
+                format!("connection {} on chain {} expects its counterparty to use the commitment prefix {:?}, but the counterparty uses {:?}",
+                    e.connection_id, e.chain_id, e.stored, e.actual)
+            },
+
         ConnectionNotOpen
             {
                 state: State,
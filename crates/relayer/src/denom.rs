@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use crate::config::DenomMetadata;
+
 /// The denom trace
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DenomTrace {
@@ -10,3 +12,104 @@ pub struct DenomTrace {
     /// The base denomination for that coin
     pub base_denom: String,
 }
+
+impl DenomTrace {
+    /// The full denomination of the coin, ie. its trace path followed by its base denomination.
+    pub fn full_denom(&self) -> String {
+        if self.path.is_empty() {
+            self.base_denom.clone()
+        } else {
+            format!("{}/{}", self.path, self.base_denom)
+        }
+    }
+
+    /// The hash of the full denomination, as found in the `ibc/<HASH>` denomination
+    /// of the vouchers: the upper-case hex encoding of `sha256(full_denom)`.
+    pub fn hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        hex::encode_upper(Sha256::digest(self.full_denom().as_bytes()))
+    }
+
+    /// The `ibc/<HASH>` denomination of the vouchers for this coin.
+    pub fn ibc_denom(&self) -> String {
+        format!("ibc/{}", self.hash())
+    }
+}
+
+/// Format an amount of the given denomination in whole units of its symbol, eg.
+/// `12.5 ATOM` for `12500000 uatom` with 6 decimals, if the metadata of the denomination
+/// is known and the amount is an integer, or as `<amount> <denom>` otherwise.
+///
+/// The conversion is done on the decimal digits of the amount, so that it is exact.
+pub fn format_amount(metadata: &[DenomMetadata], amount: &str, denom: &str) -> String {
+    metadata
+        .iter()
+        .find(|metadata| metadata.denom == denom)
+        .and_then(|metadata| {
+            let units = scale_down(amount, metadata.decimals)?;
+            Some(format!("{} {}", units, metadata.symbol))
+        })
+        .unwrap_or_else(|| format!("{amount} {denom}"))
+}
+
+/// Divide the given integer amount by `10^decimals`, without trailing zeros.
+fn scale_down(amount: &str, decimals: u8) -> Option<String> {
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    let decimals = usize::from(decimals);
+    let padded = format!("{:0>width$}", amount, width = decimals + 1);
+    let (integer, fraction) = padded.split_at(padded.len() - decimals);
+
+    let integer = integer.trim_start_matches('0');
+    let integer = if integer.is_empty() { "0" } else { integer };
+    let fraction = fraction.trim_end_matches('0');
+
+    if fraction.is_empty() {
+        Some(integer.to_string())
+    } else {
+        Some(format!("{integer}.{fraction}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_amount, DenomMetadata, DenomTrace};
+
+    #[test]
+    fn ibc_denom() {
+        let trace = DenomTrace {
+            path: "transfer/channel-0".to_string(),
+            base_denom: "uatom".to_string(),
+        };
+
+        assert_eq!(trace.full_denom(), "transfer/channel-0/uatom");
+        assert_eq!(
+            trace.ibc_denom(),
+            "ibc/27394FB092D2ECCD56123C74F36E4C1F926001CEADA9CA97EA622B25F41E5EB2"
+        );
+    }
+
+    #[test]
+    fn format_with_metadata() {
+        let metadata = vec![DenomMetadata {
+            denom: "uatom".to_string(),
+            symbol: "ATOM".to_string(),
+            decimals: 6,
+        }];
+
+        assert_eq!(format_amount(&metadata, "12500000", "uatom"), "12.5 ATOM");
+        assert_eq!(format_amount(&metadata, "3000000", "uatom"), "3 ATOM");
+        assert_eq!(format_amount(&metadata, "42", "uatom"), "0.000042 ATOM");
+        assert_eq!(format_amount(&metadata, "0", "uatom"), "0 ATOM");
+        assert_eq!(
+            format_amount(&metadata, "123456789012345678901234567890", "uatom"),
+            "123456789012345678901234.56789 ATOM"
+        );
+
+        assert_eq!(format_amount(&metadata, "42", "stake"), "42 stake");
+        assert_eq!(format_amount(&metadata, "1.5", "uatom"), "1.5 uatom");
+    }
+}
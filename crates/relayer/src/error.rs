@@ -32,10 +32,11 @@ use ibc_relayer_types::{
         ics02_client::{client_type::ClientType, error as client_error},
         ics03_connection::error as connection_error,
         ics23_commitment::error as commitment_error,
-        ics24_host::identifier::{ChainId, ChannelId, ConnectionId},
+        ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId},
     },
     proofs::ProofError,
     relayer::ics18_relayer::error as relayer_error,
+    Height,
 };
 
 use crate::chain::cosmos::version;
@@ -158,6 +159,13 @@ define_error! {
         EmptyResponseValue
             |_| { "empty response value" },
 
+        ConsensusStateNotFound
+            {
+                client_id: ClientId,
+                height: Height,
+            }
+            |e| { format!("consensus state of client {0} at height {1} not found", e.client_id, e.height) },
+
         EmptyResponseProof
             |_| { "empty response proof" },
 
@@ -320,6 +328,21 @@ define_error! {
             [ TraceError<crossbeam_channel::RecvTimeoutError> ]
             |_| { "timeout when waiting for reponse over inter-thread channel" },
 
+        WaitForHeightTimeout
+            {
+                chain_id: ChainId,
+                height: Height,
+                timeout: Duration,
+            }
+            |e| {
+                format!("chain {} did not reach height {} within {}",
+                    e.chain_id, e.height, format_duration(e.timeout))
+            },
+
+        MockChainHandle
+            { reason: String }
+            |e| { format!("mock chain handle: {}", e.reason) },
+
         InvalidInputHeader
             |_| { "the input header is not recognized as a header for this chain" },
 
@@ -503,6 +526,16 @@ define_error! {
                 format!("semantic config validation failed for option `gas_multiplier` of chain '{}', reason: gas multiplier ({}) is smaller than `1.1`, which could trigger gas fee errors in production", e.chain_id, e.gas_multiplier)
             },
 
+        ConfigValidationGasMultiplierTuningBounds
+            {
+                chain_id: ChainId,
+                min: f64,
+                max: f64,
+            }
+            |e| {
+                format!("semantic config validation failed for option `gas_multiplier_tuning` of chain '{}', reason: the lower bound ({}) must not be greater than the upper bound ({})", e.chain_id, e.min, e.max)
+            },
+
         SdkModuleVersion
             {
                 chain_id: ChainId,
@@ -599,6 +632,27 @@ impl Error {
         Error::channel_send()
     }
 
+    /// Whether this error is due to the configuration of the chain, eg. an RPC endpoint
+    /// with an unsupported scheme or version, and thus would not go away by retrying.
+    pub fn is_config_error(&self) -> bool {
+        use tendermint_rpc::error::ErrorDetail as RpcErrorDetail;
+
+        match self.detail() {
+            ErrorDetail::Config(_) => true,
+            ErrorDetail::Rpc(e) => matches!(
+                e.source,
+                RpcErrorDetail::InvalidUrl(_)
+                    | RpcErrorDetail::UnsupportedScheme(_)
+                    | RpcErrorDetail::UnsupportedRpcVersion(_)
+            ),
+            _ => false,
+        }
+    }
+
+    pub fn is_consensus_state_not_found(&self) -> bool {
+        matches!(self.detail(), ErrorDetail::ConsensusStateNotFound(_))
+    }
+
     pub fn is_trusted_state_outside_trusting_period_error(&self) -> bool {
         match self.detail() {
             ErrorDetail::LightClientVerification(e) => matches!(
@@ -397,11 +397,25 @@ fn channel_extract_attributes_from_tx(
     Ok(attr)
 }
 
+/// Decode the value of a hex-encoded packet attribute, eg. `packet_data_hex`.
+pub fn decode_hex_packet_attribute(key: &str, value: &str) -> Result<Vec<u8>, ChannelError> {
+    hex::decode(value)
+        .map_err(|_| ChannelError::invalid_hex_packet_attribute(key.to_string(), value.to_string()))
+}
+
+/// Extract the packet and the acknowledgement, if any, from a packet event.
+///
+/// Depending on their version, ibc-go chains emit the packet data and acknowledgement
+/// either as raw strings (`packet_data`, `packet_ack`), hex-encoded (`packet_data_hex`,
+/// `packet_ack_hex`), or both. The hex-encoded attributes are preferred when present,
+/// since the raw ones cannot represent non UTF-8 data.
 pub fn extract_packet_and_write_ack_from_tx(
     event: &AbciEvent,
 ) -> Result<(Packet, Vec<u8>), ChannelError> {
     let mut packet = Packet::default();
     let mut write_ack: Vec<u8> = Vec::new();
+    let mut data_hex = None;
+    let mut ack_hex = None;
     for tag in &event.attributes {
         let key = tag.key.as_str();
         let value = tag.value.as_str();
@@ -436,10 +450,24 @@ pub fn extract_packet_and_write_ack_from_tx(
             channel_events::PKT_ACK_ATTRIBUTE_KEY => {
                 write_ack = Vec::from(value.as_bytes());
             }
+            channel_events::PKT_DATA_HEX_ATTRIBUTE_KEY => {
+                data_hex = Some(decode_hex_packet_attribute(key, value)?);
+            }
+            channel_events::PKT_ACK_HEX_ATTRIBUTE_KEY => {
+                ack_hex = Some(decode_hex_packet_attribute(key, value)?);
+            }
             _ => {}
         }
     }
 
+    if let Some(data) = data_hex {
+        packet.data = data;
+    }
+
+    if let Some(ack) = ack_hex {
+        write_ack = ack;
+    }
+
     Ok((packet, write_ack))
 }
 
@@ -609,4 +637,75 @@ mod tests {
             }
         }
     }
+
+    fn write_ack_event(attributes: &[(&str, &str)]) -> AbciEvent {
+        let common = [
+            ("packet_src_port", "transfer"),
+            ("packet_src_channel", "channel-0"),
+            ("packet_dst_port", "transfer"),
+            ("packet_dst_channel", "channel-1"),
+            ("packet_sequence", "42"),
+            ("packet_timeout_height", "1-1000"),
+            ("packet_timeout_timestamp", "0"),
+        ];
+
+        AbciEvent::new(
+            "write_acknowledgement",
+            common.iter().chain(attributes.iter()).copied(),
+        )
+    }
+
+    const PACKET_DATA: &str = r#"{"amount":"100","denom":"stake"}"#;
+    const PACKET_ACK: &str = r#"{"result":"AQ=="}"#;
+
+    #[test]
+    fn extract_packet_legacy_raw_attributes() {
+        // ibc-go v1 and v2 only emit the raw attributes
+        let event = write_ack_event(&[("packet_data", PACKET_DATA), ("packet_ack", PACKET_ACK)]);
+
+        let (packet, ack) = extract_packet_and_write_ack_from_tx(&event).unwrap();
+
+        assert_eq!(packet.sequence, Sequence::from(42));
+        assert_eq!(packet.data, PACKET_DATA.as_bytes());
+        assert_eq!(ack, PACKET_ACK.as_bytes());
+    }
+
+    #[test]
+    fn extract_packet_raw_and_hex_attributes() {
+        // ibc-go v3 and later emit both encodings, and the hex one is authoritative
+        let data_hex = hex::encode(PACKET_DATA);
+        let ack_hex = hex::encode(PACKET_ACK);
+
+        let event = write_ack_event(&[
+            ("packet_data", "lossy data"),
+            ("packet_data_hex", &data_hex),
+            ("packet_ack", "lossy ack"),
+            ("packet_ack_hex", &ack_hex),
+        ]);
+
+        let (packet, ack) = extract_packet_and_write_ack_from_tx(&event).unwrap();
+
+        assert_eq!(packet.data, PACKET_DATA.as_bytes());
+        assert_eq!(ack, PACKET_ACK.as_bytes());
+    }
+
+    #[test]
+    fn extract_packet_hex_attributes_only() {
+        let data = vec![0xff, 0x00, 0x01];
+        let data_hex = hex::encode(&data);
+
+        let event = write_ack_event(&[("packet_data_hex", &data_hex), ("packet_ack_hex", "00")]);
+
+        let (packet, ack) = extract_packet_and_write_ack_from_tx(&event).unwrap();
+
+        assert_eq!(packet.data, data);
+        assert_eq!(ack, vec![0x00]);
+    }
+
+    #[test]
+    fn extract_packet_invalid_hex_attribute() {
+        let event = write_ack_event(&[("packet_data_hex", "not hex")]);
+
+        assert!(extract_packet_and_write_ack_from_tx(&event).is_err());
+    }
 }
@@ -29,19 +29,15 @@ impl<T> EventBus<T> {
     where
         T: Clone,
     {
-        let mut disconnected = Vec::new();
-
-        for (idx, tx) in self.txs.iter().enumerate() {
-            // TODO: Avoid cloning when sending to last subscriber
-            if let Err(channel::SendError(_)) = tx.send(value.clone()) {
-                disconnected.push(idx);
-            }
-        }
+        // Remove the disconnected subscribers while broadcasting, so that the
+        // remaining ones keep receiving every value, in the same order.
+        // TODO: Avoid cloning when sending to last subscriber
+        self.txs.retain(|tx| tx.send(value.clone()).is_ok());
+    }
 
-        // Remove all disconnected subscribers
-        for idx in disconnected {
-            self.txs.remove(idx);
-        }
+    /// The number of subscribers to the bus, as of the last broadcast.
+    pub fn subscriber_count(&self) -> usize {
+        self.txs.len()
     }
 }
 
@@ -117,4 +113,30 @@ mod tests {
 
         assert_eq!(counter(), 20);
     }
+
+    #[test]
+    #[serial]
+    fn disconnected_subscribers() {
+        reset_counter();
+
+        let mut bus = EventBus::new();
+
+        let rx0 = bus.subscribe();
+        let rx1 = bus.subscribe();
+        let rx2 = bus.subscribe();
+        let rx3 = bus.subscribe();
+
+        drop(rx1);
+        drop(rx2);
+
+        bus.broadcast(Value(42));
+        assert_eq!(bus.subscriber_count(), 2);
+
+        bus.broadcast(Value(113));
+
+        for rx in [rx0, rx3] {
+            assert_eq!(rx.recv(), Ok(Value(42)));
+            assert_eq!(rx.recv(), Ok(Value(113)));
+        }
+    }
 }
@@ -1,20 +1,16 @@
 use alloc::sync::Arc;
 use core::cmp::Ordering;
-use std::time::Duration;
+use core::pin::Pin;
+use std::time::{Duration, Instant};
 
+use async_trait::async_trait;
 use crossbeam_channel as channel;
-use futures::{
-    pin_mut,
-    stream::{self, select_all, StreamExt},
-    Stream, TryStreamExt,
-};
-use tokio::task::JoinHandle;
-use tokio::{runtime::Runtime as TokioRuntime, sync::mpsc};
-use tracing::{debug, error, info, instrument, trace};
+use futures::{pin_mut, stream::StreamExt, Stream, TryStreamExt};
+use tokio::runtime::Runtime as TokioRuntime;
+use tracing::{debug, error, info, instrument, trace, warn};
 
 use tendermint_rpc::{
-    client::CompatMode, event::Event as RpcEvent, query::Query, Error as RpcError,
-    SubscriptionClient, WebSocketClient, WebSocketClientDriver, WebSocketClientUrl,
+    client::CompatMode, Client, HttpClient, Url, WebSocketClientUrl, WebSocketConfig,
 };
 
 use ibc_relayer_types::{
@@ -23,6 +19,7 @@ use ibc_relayer_types::{
 
 use crate::{
     chain::{handle::Subscription, tracking::TrackingId},
+    config::{ChainConfig, EventSourceMode},
     telemetry,
     util::{
         retry::{retry_with_index, RetryResult},
@@ -33,10 +30,19 @@ use crate::{
 mod error;
 pub use error::*;
 
+pub mod polling;
+pub mod websocket;
+
+pub use polling::PollingEventProvider;
+pub use websocket::{websocket_config, WebSocketEventProvider};
+
 use super::{bus::EventBus, IbcEventWithHeight};
 
 pub type Result<T> = core::result::Result<T, Error>;
 
+/// Interval between two checks of how far behind the node the monitor is
+const BLOCK_LAG_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
 mod retry_strategy {
     use crate::util::retry::clamp_total;
     use core::time::Duration;
@@ -61,12 +67,40 @@ pub struct EventBatch {
     pub events: Vec<IbcEventWithHeight>,
 }
 
-type SubscriptionResult = core::result::Result<RpcEvent, RpcError>;
-type SubscriptionStream = dyn Stream<Item = SubscriptionResult> + Send + Sync + Unpin;
+/// A stream of IBC events, in the order in which they were emitted by the chain.
+pub type EventStream = Pin<Box<dyn Stream<Item = Result<IbcEventWithHeight>> + Send>>;
 
 pub type EventSender = channel::Sender<Result<EventBatch>>;
 pub type EventReceiver = channel::Receiver<Result<EventBatch>>;
 
+/// A source of IBC events for the [`EventMonitor`].
+///
+/// The monitor takes care of batching the events by height, of broadcasting
+/// the batches to its subscribers, and of reconnecting to the source when
+/// the stream of events fails, so that providers only have to acquire the events,
+/// eg. from a WebSocket subscription, by polling an RPC endpoint, or from
+/// a remote event forwarder or a message queue.
+#[async_trait]
+pub trait EventStreamProvider: Send {
+    /// A short description of the source of the events, for logging purposes.
+    fn description(&self) -> String;
+
+    /// Subscribe to the events of the source, replacing any existing subscription.
+    async fn subscribe(&mut self) -> Result<()>;
+
+    /// Take the stream of IBC events obtained from the current subscription.
+    ///
+    /// The monitor reconnects to the source and subscribes again
+    /// when this stream yields a subscription error or terminates.
+    fn events(&mut self) -> EventStream;
+
+    /// Re-establish the connection to the source after the stream of events failed.
+    async fn reconnect(&mut self) -> Result<()>;
+
+    /// Close the connection to the source.
+    async fn shutdown(&mut self);
+}
+
 #[derive(Clone, Debug)]
 pub struct TxMonitorCmd(channel::Sender<MonitorCmd>);
 
@@ -95,39 +129,35 @@ pub enum MonitorCmd {
     Subscribe(channel::Sender<Subscription>),
 }
 
-/// Connect to a Tendermint node, subscribe to a set of queries,
-/// receive push events over a websocket, and filter them for the
-/// event handler.
-///
-/// The default events that are queried are:
-/// - [`EventType::NewBlock`](tendermint_rpc::query::EventType::NewBlock)
-/// - [`EventType::Tx`](tendermint_rpc::query::EventType::Tx)
+/// Receive the IBC events emitted by a chain from an [`EventStreamProvider`],
+/// group them in batches by height, and broadcast the batches to the
+/// subscribers of the monitor.
 pub struct EventMonitor {
     chain_id: ChainId,
     /// Delay until batch is emitted
     batch_delay: Duration,
-    /// WebSocket to collect events from
-    client: WebSocketClient,
-    /// Async task handle for the WebSocket client's driver
-    driver_handle: JoinHandle<()>,
+    /// Source of the events
+    provider: Box<dyn EventStreamProvider>,
     /// Event bus for broadcasting events
     event_bus: EventBus<Arc<Result<EventBatch>>>,
-    /// Channel where to receive client driver errors
-    rx_err: mpsc::UnboundedReceiver<tendermint_rpc::Error>,
-    /// Channel where to send client driver errors
-    tx_err: mpsc::UnboundedSender<tendermint_rpc::Error>,
     /// Channel where to receive commands
     rx_cmd: channel::Receiver<MonitorCmd>,
-    /// Node Address
-    ws_url: WebSocketClientUrl,
-    /// RPC compatibility mode
-    rpc_compat: CompatMode,
-    /// Queries
-    event_queries: Vec<Query>,
-    /// All subscriptions combined in a single stream
-    subscriptions: Box<SubscriptionStream>,
     /// Tokio runtime
     rt: Arc<TokioRuntime>,
+    /// Height of the last batch emitted
+    last_height: Option<Height>,
+    /// Detection of the monitor falling behind the node, if enabled
+    lag_check: Option<BlockLagCheck>,
+}
+
+/// Periodically compares the height of the last batch emitted by the monitor
+/// with the latest height of the node, to detect a subscription which stalled
+/// without being closed.
+struct BlockLagCheck {
+    /// RPC client to query the latest height of the node
+    client: HttpClient,
+    max_block_lag: u64,
+    last_check: Instant,
 }
 
 // TODO: These are SDK specific, should be eventually moved.
@@ -169,134 +199,146 @@ pub mod queries {
 }
 
 impl EventMonitor {
-    /// Create an event monitor, and connect to a node
-    #[instrument(
-        name = "event_monitor.create",
-        level = "error",
-        skip_all,
-        fields(chain = %chain_id, url = %ws_url)
-    )]
+    /// Create an event monitor receiving events from the given provider
     pub fn new(
         chain_id: ChainId,
-        ws_url: WebSocketClientUrl,
-        rpc_compat: CompatMode,
+        provider: Box<dyn EventStreamProvider>,
         batch_delay: Duration,
         rt: Arc<TokioRuntime>,
-    ) -> Result<(Self, TxMonitorCmd)> {
+    ) -> (Self, TxMonitorCmd) {
         let event_bus = EventBus::new();
         let (tx_cmd, rx_cmd) = channel::unbounded();
 
-        let builder = WebSocketClient::builder(ws_url.clone()).compat_mode(rpc_compat);
-
-        let (client, driver) = rt
-            .block_on(builder.build())
-            .map_err(|_| Error::client_creation_failed(chain_id.clone(), ws_url.clone()))?;
-
-        let (tx_err, rx_err) = mpsc::unbounded_channel();
-        let driver_handle = rt.spawn(run_driver(driver, tx_err.clone()));
-
-        // TODO: move them to config file(?)
-        let event_queries = queries::all();
-
         let monitor = Self {
             rt,
             chain_id,
             batch_delay,
-            client,
-            driver_handle,
-            event_queries,
+            provider,
             event_bus,
-            rx_err,
-            tx_err,
             rx_cmd,
-            ws_url,
-            rpc_compat,
-            subscriptions: Box::new(futures::stream::empty()),
+            last_height: None,
+            lag_check: None,
         };
 
-        Ok((monitor, TxMonitorCmd(tx_cmd)))
-    }
-
-    /// The list of [`Query`] that this event monitor is subscribing for.
-    pub fn queries(&self) -> &[Query] {
-        &self.event_queries
+        (monitor, TxMonitorCmd(tx_cmd))
     }
 
-    /// Clear the current subscriptions, and subscribe again to all queries.
-    #[instrument(name = "event_monitor.init_subscriptions", skip_all, fields(chain = %self.chain_id))]
-    pub fn init_subscriptions(&mut self) -> Result<()> {
-        let mut subscriptions = vec![];
-
-        for query in &self.event_queries {
-            trace!("subscribing to query: {}", query);
-
-            let subscription = self
-                .rt
-                .block_on(self.client.subscribe(query.clone()))
-                .map_err(Error::client_subscription_failed)?;
-
-            subscriptions.push(subscription);
-        }
-
-        self.subscriptions = Box::new(select_all(subscriptions));
-
-        trace!("subscribed to all queries");
+    /// Create an event monitor, and connect to the WebSocket endpoint of a node
+    pub fn websocket(
+        chain_id: ChainId,
+        ws_url: WebSocketClientUrl,
+        rpc_compat: CompatMode,
+        ws_config: WebSocketConfig,
+        batch_delay: Duration,
+        rpc_fallback: bool,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxMonitorCmd)> {
+        let provider = rt.block_on(WebSocketEventProvider::connect(
+            chain_id.clone(),
+            ws_url,
+            rpc_compat,
+            ws_config,
+            rpc_fallback,
+        ))?;
 
-        Ok(())
+        Ok(Self::new(chain_id, Box::new(provider), batch_delay, rt))
     }
 
-    #[instrument(
-        name = "event_monitor.try_reconnect",
-        level = "error",
-        skip_all,
-        fields(chain = %self.chain_id)
-    )]
-    fn try_reconnect(&mut self) -> Result<()> {
-        trace!("trying to reconnect to WebSocket endpoint {}", self.ws_url);
-
-        // Try to reconnect
-        let builder = WebSocketClient::builder(self.ws_url.clone()).compat_mode(self.rpc_compat);
-
-        let (mut client, driver) = self.rt.block_on(builder.build()).map_err(|_| {
-            Error::client_creation_failed(self.chain_id.clone(), self.ws_url.clone())
-        })?;
+    /// Create an event monitor polling the RPC endpoint of a node for new blocks
+    pub fn polling(
+        chain_id: ChainId,
+        rpc_addr: Url,
+        rpc_compat: CompatMode,
+        poll_interval: Duration,
+        adaptive: bool,
+        batch_delay: Duration,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxMonitorCmd)> {
+        let provider = PollingEventProvider::new(
+            chain_id.clone(),
+            rpc_addr,
+            rpc_compat,
+            poll_interval,
+            adaptive,
+        )?;
 
-        let mut driver_handle = self.rt.spawn(run_driver(driver, self.tx_err.clone()));
+        Ok(Self::new(chain_id, Box::new(provider), batch_delay, rt))
+    }
 
-        // Swap the new client with the previous one which failed,
-        // so that we can shut the latter down gracefully.
-        core::mem::swap(&mut self.client, &mut client);
-        core::mem::swap(&mut self.driver_handle, &mut driver_handle);
+    /// Resubscribe to the source of events when the monitor falls more than
+    /// `max_block_lag` blocks behind the latest height of the node at `rpc_addr`.
+    pub fn with_max_block_lag(
+        mut self,
+        rpc_addr: Url,
+        rpc_compat: CompatMode,
+        max_block_lag: u64,
+    ) -> Result<Self> {
+        let mut client = HttpClient::new(rpc_addr).map_err(Error::rpc)?;
+        client.set_compat_mode(rpc_compat);
 
-        trace!("reconnected to WebSocket endpoint {}", self.ws_url);
+        self.lag_check = Some(BlockLagCheck {
+            client,
+            max_block_lag,
+            last_check: Instant::now(),
+        });
 
-        // Shut down previous client
-        trace!("gracefully shutting down previous client",);
+        Ok(self)
+    }
 
-        let _ = client.close();
+    /// Create an event monitor for the given chain, using the event source
+    /// specified in its configuration.
+    pub fn from_config(
+        chain_config: &ChainConfig,
+        rpc_compat: CompatMode,
+        rt: Arc<TokioRuntime>,
+    ) -> Result<(Self, TxMonitorCmd)> {
+        let (monitor, tx_cmd) = match chain_config.event_source {
+            EventSourceMode::Push => Self::websocket(
+                chain_config.id.clone(),
+                chain_config.websocket_addr.clone(),
+                rpc_compat,
+                websocket_config(chain_config),
+                chain_config.batch_delay,
+                chain_config.event_rpc_fallback,
+                rt,
+            ),
+            EventSourceMode::Pull { interval, adaptive } => Self::polling(
+                chain_config.id.clone(),
+                chain_config.rpc_addr.clone(),
+                rpc_compat,
+                interval,
+                adaptive,
+                chain_config.batch_delay,
+                rt,
+            ),
+        }?;
 
-        self.rt
-            .block_on(driver_handle)
-            .map_err(Error::client_termination_failed)?;
+        match chain_config.max_block_lag {
+            Some(max_block_lag) => {
+                let monitor = monitor.with_max_block_lag(
+                    chain_config.rpc_addr.clone(),
+                    rpc_compat,
+                    max_block_lag,
+                )?;
 
-        trace!("previous client successfully shutdown");
+                Ok((monitor, tx_cmd))
+            }
+            None => Ok((monitor, tx_cmd)),
+        }
+    }
 
-        Ok(())
+    /// A short description of the source of the events of this monitor.
+    pub fn description(&self) -> String {
+        self.provider.description()
     }
 
-    /// Try to resubscribe to events
-    #[instrument(
-        name = "event_monitor.try_resubscribe",
-        level = "error",
-        skip_all,
-        fields(chain = %self.chain_id)
-    )]
-    fn try_resubscribe(&mut self) -> Result<()> {
-        trace!("trying to resubscribe to events");
-        self.init_subscriptions()
+    /// Clear the current subscriptions, and subscribe again to the source of events.
+    #[instrument(name = "event_monitor.init_subscriptions", skip_all, fields(chain = %self.chain_id))]
+    pub fn init_subscriptions(&mut self) -> Result<()> {
+        self.rt.block_on(self.provider.subscribe())
     }
 
-    /// Attempt to reconnect the WebSocket client using the given retry strategy.
+    /// Attempt to reconnect to the source of events using the given retry strategy.
     ///
     /// See the [`retry`](https://docs.rs/retry) crate and the
     /// [`crate::util::retry`] module for more information.
@@ -309,13 +351,13 @@ impl EventMonitor {
     fn reconnect(&mut self) {
         let result = retry_with_index(retry_strategy::default(), |_| {
             // Try to reconnect
-            if let Err(e) = self.try_reconnect() {
+            if let Err(e) = self.rt.block_on(self.provider.reconnect()) {
                 trace!("error when reconnecting: {}", e);
                 return RetryResult::Retry(());
             }
 
             // Try to resubscribe
-            if let Err(e) = self.try_resubscribe() {
+            if let Err(e) = self.init_subscriptions() {
                 trace!("error when resubscribing: {}", e);
                 return RetryResult::Retry(());
             }
@@ -324,13 +366,11 @@ impl EventMonitor {
         });
 
         match result {
-            Ok(()) => info!(
-                "successfully reconnected to WebSocket endpoint {}",
-                self.ws_url
-            ),
+            Ok(()) => info!("successfully reconnected to {}", self.description()),
             Err(e) => error!(
                 "failed to reconnect to {} after {} retries",
-                self.ws_url, e.tries
+                self.description(),
+                e.tries
             ),
         }
     }
@@ -350,7 +390,7 @@ impl EventMonitor {
         let rt = self.rt.clone();
 
         // Continuously run the event loop, so that when it aborts
-        // because of WebSocket client restart, we pick up the work again.
+        // because of a failure of the source, we pick up the work again.
         loop {
             match rt.block_on(self.run_loop()) {
                 Next::Continue => continue,
@@ -366,54 +406,62 @@ impl EventMonitor {
 
         debug!("event monitor is shutting down");
 
-        // Close the WebSocket connection
-        let _ = self.client.close();
-
-        // Wait for the WebSocket driver to finish
-        let _ = self.rt.block_on(self.driver_handle);
+        // Close the connection to the source
+        rt.block_on(self.provider.shutdown());
 
         trace!("event monitor has successfully shut down");
     }
 
     async fn run_loop(&mut self) -> Next {
-        // Take ownership of the subscriptions
-        let subscriptions =
-            core::mem::replace(&mut self.subscriptions, Box::new(futures::stream::empty()));
-
-        // Convert the stream of RPC events into a stream of event batches.
-        let batches = stream_batches(subscriptions, self.chain_id.clone(), self.batch_delay);
+        // Convert the stream of events into a stream of event batches.
+        let batches = stream_batches(
+            self.provider.events(),
+            self.chain_id.clone(),
+            self.batch_delay,
+        );
 
         // Needed to be able to poll the stream
         pin_mut!(batches);
 
         loop {
             // Process any shutdown or subscription commands
-            if let Ok(cmd) = self.rx_cmd.try_recv() {
-                match cmd {
-                    MonitorCmd::Shutdown => return Next::Abort,
-                    MonitorCmd::Subscribe(tx) => {
-                        if let Err(e) = tx.send(self.event_bus.subscribe()) {
-                            error!("failed to send back subscription: {e}");
-                        }
-                    }
-                }
+            if let Some(next) = self.process_command() {
+                return next;
+            }
+
+            if let Some(e) = self.check_block_lag().await {
+                warn!("{}, subscribing again", e);
+
+                // Let the subscribers know that events may have been missed
+                self.propagate_error(e);
+
+                return Next::Reconnect;
             }
 
-            let result = tokio::select! {
-                Some(batch) = batches.next() => batch,
-                Some(e) = self.rx_err.recv() => Err(Error::web_socket_driver(e)),
+            // Stop waiting for the next batch in time to check the block lag
+            let next = if self.lag_check.is_some() {
+                tokio::time::timeout(BLOCK_LAG_CHECK_INTERVAL, batches.next())
+                    .await
+                    .ok()
+            } else {
+                Some(batches.next().await)
             };
 
-            // Before handling the batch, check if there are any pending shutdown or subscribe commands.
-            if let Ok(cmd) = self.rx_cmd.try_recv() {
-                match cmd {
-                    MonitorCmd::Shutdown => return Next::Abort,
-                    MonitorCmd::Subscribe(tx) => {
-                        if let Err(e) = tx.send(self.event_bus.subscribe()) {
-                            error!("failed to send back subscription: {e}");
-                        }
-                    }
+            let result = match next {
+                // No batch was received before the block lag check was due
+                None => continue,
+                Some(Some(batch)) => batch,
+                Some(None) => {
+                    error!("stream of events from {} has ended", self.description());
+
+                    // Reconnect to the source, and subscribe again.
+                    return Next::Reconnect;
                 }
+            };
+
+            // Before handling the batch, check if there are any pending shutdown or subscribe commands.
+            if let Some(next) = self.process_command() {
+                return next;
             }
 
             match result {
@@ -423,20 +471,72 @@ impl EventMonitor {
                         error!("subscription cancelled, reason: {}", reason);
 
                         self.propagate_error(e);
-
-                        // Reconnect to the WebSocket endpoint, and subscribe again to the queries.
-                        return Next::Reconnect;
                     } else {
                         error!("failed to collect events: {}", e);
+                    }
 
-                        // Reconnect to the WebSocket endpoint, and subscribe again to the queries.
-                        return Next::Reconnect;
-                    };
+                    // Reconnect to the source, and subscribe again.
+                    return Next::Reconnect;
                 }
             }
         }
     }
 
+    /// Check how far behind the latest height of the node the monitor is,
+    /// if the check is enabled and due, returning an error if the lag
+    /// exceeds the configured maximum.
+    async fn check_block_lag(&mut self) -> Option<Error> {
+        let check = self.lag_check.as_mut()?;
+
+        if check.last_check.elapsed() < BLOCK_LAG_CHECK_INTERVAL {
+            return None;
+        }
+
+        check.last_check = Instant::now();
+
+        // Nothing to compare against until the first batch is emitted
+        let last_height = self.last_height?;
+
+        let latest_height = match check.client.status().await {
+            Ok(status) => status.sync_info.latest_block_height.value(),
+            Err(e) => {
+                warn!("failed to query the latest height to check the block lag: {e}");
+                return None;
+            }
+        };
+
+        let lag = latest_height.saturating_sub(last_height.revision_height());
+
+        telemetry!(event_monitor_block_lag, &self.chain_id, lag);
+
+        if lag > check.max_block_lag {
+            Some(Error::block_lag_exceeded(lag, check.max_block_lag))
+        } else {
+            None
+        }
+    }
+
+    /// Process a pending shutdown or subscription command, if any.
+    fn process_command(&mut self) -> Option<Next> {
+        if let Ok(cmd) = self.rx_cmd.try_recv() {
+            match cmd {
+                MonitorCmd::Shutdown => return Some(Next::Abort),
+                MonitorCmd::Subscribe(tx) => {
+                    if let Err(e) = tx.send(self.event_bus.subscribe()) {
+                        error!("failed to send back subscription: {e}");
+                    }
+
+                    debug!(
+                        subscribers = self.event_bus.subscriber_count(),
+                        "new subscription to the event monitor"
+                    );
+                }
+            }
+        }
+
+        None
+    }
+
     /// Propagate error to subscribers.
     ///
     /// The main use case for propagating RPC errors is for the [`Supervisor`]
@@ -448,42 +548,24 @@ impl EventMonitor {
         self.event_bus.broadcast(Arc::new(Err(error)));
     }
 
-    /// Collect the IBC events from the subscriptions
+    /// Broadcast the batch to the subscribers.
     fn process_batch(&mut self, batch: EventBatch) {
         telemetry!(ws_events, &batch.chain_id, batch.events.len() as u64);
 
         debug!(chain = %batch.chain_id, len = %batch.events.len(), "emitting batch");
 
+        self.last_height = Some(batch.height);
+
         self.event_bus.broadcast(Arc::new(Ok(batch)));
     }
 }
 
-/// Collect the IBC events from an RPC event
-fn collect_events(
-    chain_id: &ChainId,
-    event: RpcEvent,
-) -> impl Stream<Item = Result<IbcEventWithHeight>> {
-    let events = crate::event::rpc::get_all_events(chain_id, event).unwrap_or_default();
-    stream::iter(events).map(Ok)
-}
-
-/// Convert a stream of RPC event into a stream of event batches
+/// Convert a stream of IBC events into a stream of event batches
 fn stream_batches(
-    subscriptions: Box<SubscriptionStream>,
+    events: EventStream,
     chain_id: ChainId,
     batch_delay: Duration,
 ) -> impl Stream<Item = Result<EventBatch>> {
-    let id = chain_id.clone();
-
-    // Collect IBC events from each RPC event
-    let events = subscriptions
-        .map_ok(move |rpc_event| {
-            debug!(chain = %id, "received an RPC event: {}", rpc_event.query);
-            collect_events(&id, rpc_event)
-        })
-        .map_err(Error::canceled_or_generic)
-        .try_flatten();
-
     // Group events by height
     let grouped = try_group_while_timeout(events, |ev0, ev1| ev0.height == ev1.height, batch_delay);
 
@@ -516,17 +598,6 @@ fn sort_events(events: &mut [IbcEventWithHeight]) {
     })
 }
 
-async fn run_driver(
-    driver: WebSocketClientDriver,
-    tx: mpsc::UnboundedSender<tendermint_rpc::Error>,
-) {
-    if let Err(e) = driver.run().await {
-        if tx.send(e).is_err() {
-            error!("failed to relay driver error to event monitor");
-        }
-    }
-}
-
 pub enum Next {
     Abort,
     Continue,
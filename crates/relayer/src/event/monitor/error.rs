@@ -45,6 +45,10 @@ define_error! {
             [ TraceError<RpcError> ]
             |_| { "subscription cancelled" },
 
+        BlockLagExceeded
+            { lag: u64, max_block_lag: u64 }
+            |e| { format!("event monitor is {0} blocks behind the node, more than the maximum of {1}", e.lag, e.max_block_lag) },
+
         Rpc
             [ TraceError<RpcError> ]
             |_| { "RPC error" },
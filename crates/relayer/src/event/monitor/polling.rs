@@ -0,0 +1,307 @@
+use core::time::Duration;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use async_stream::stream;
+use async_trait::async_trait;
+use tracing::{trace, warn};
+
+use tendermint_rpc::{client::CompatMode, Client, HttpClient, Url};
+
+use ibc_relayer_types::{
+    core::ics02_client::height::Height, core::ics24_host::identifier::ChainId,
+};
+
+use crate::event::{rpc, IbcEventWithHeight};
+
+use super::{Error, EventStream, EventStreamProvider, Result};
+
+/// Poll a Tendermint node over RPC for new blocks, and query the
+/// events of each of them via `/block_results`.
+///
+/// This is an alternative to the [`WebSocketEventProvider`](super::websocket::WebSocketEventProvider)
+/// for nodes which cannot maintain a long-lived WebSocket connection,
+/// eg. because they sit behind a load balancer.
+pub struct PollingEventProvider {
+    chain_id: ChainId,
+    /// RPC client to poll for new blocks
+    client: HttpClient,
+    /// Node address
+    rpc_addr: Url,
+    /// RPC compatibility mode
+    rpc_compat: CompatMode,
+    /// Interval between two polls
+    poll_interval: Duration,
+    /// Whether to adapt the interval between two polls to the observed block time
+    adaptive: bool,
+}
+
+impl PollingEventProvider {
+    pub fn new(
+        chain_id: ChainId,
+        rpc_addr: Url,
+        rpc_compat: CompatMode,
+        poll_interval: Duration,
+        adaptive: bool,
+    ) -> Result<Self> {
+        let client = build_client(&rpc_addr, rpc_compat)?;
+
+        Ok(Self {
+            chain_id,
+            client,
+            rpc_addr,
+            rpc_compat,
+            poll_interval,
+            adaptive,
+        })
+    }
+}
+
+#[async_trait]
+impl EventStreamProvider for PollingEventProvider {
+    fn description(&self) -> String {
+        if self.adaptive {
+            format!(
+                "RPC endpoint {}, polled at the observed block time, at least every {:?}",
+                self.rpc_addr, self.poll_interval
+            )
+        } else {
+            format!(
+                "RPC endpoint {}, polled every {:?}",
+                self.rpc_addr, self.poll_interval
+            )
+        }
+    }
+
+    async fn subscribe(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Yield the events of every block committed since the stream was created,
+    /// starting with the events of the latest block at that time, similarly to
+    /// a freshly established WebSocket subscription.
+    ///
+    /// The blocks which could not be queried are retried on the next poll,
+    /// so the stream never fails.
+    fn events(&mut self) -> EventStream {
+        let client = self.client.clone();
+        let chain_id = self.chain_id.clone();
+        let poll_interval = self.poll_interval;
+        let adaptive = self.adaptive;
+
+        Box::pin(stream! {
+            let mut last_height: Option<Height> = None;
+            let mut block_time = BlockTimeEstimate::default();
+
+            loop {
+                match query_latest_height(&client).await {
+                    Ok(latest_height) => {
+                        block_time.observe(latest_height, Instant::now());
+
+                        // Start over from the latest height if the chain changed revision
+                        let mut height = match last_height {
+                            Some(last) if last.revision_number() == latest_height.revision_number() => {
+                                last.increment()
+                            }
+                            _ => latest_height,
+                        };
+
+                        while height <= latest_height {
+                            match query_events(&client, &chain_id, height).await {
+                                Ok(events) => {
+                                    for event in events {
+                                        let event: Result<IbcEventWithHeight> = Ok(event);
+                                        yield event;
+                                    }
+
+                                    last_height = Some(height);
+                                    height = height.increment();
+                                }
+                                Err(e) => {
+                                    warn!(chain = %chain_id, %height, "failed to query block results: {e}");
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                    Err(e) => warn!(chain = %chain_id, "failed to query latest height: {e}"),
+                }
+
+                let delay = if adaptive {
+                    with_jitter(block_time.next_poll_delay(Instant::now(), poll_interval))
+                } else {
+                    poll_interval
+                };
+
+                tokio::time::sleep(delay).await;
+            }
+        })
+    }
+
+    async fn reconnect(&mut self) -> Result<()> {
+        self.client = build_client(&self.rpc_addr, self.rpc_compat)?;
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) {}
+}
+
+/// Never poll more often than this, even on chains with a very short block time.
+const MIN_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Weight of the latest sample in the moving average of the block time, in percent.
+const BLOCK_TIME_SAMPLE_WEIGHT: u32 = 20;
+
+/// Estimate of the block time of a chain, as a moving average
+/// of the time elapsed between the new blocks seen when polling.
+#[derive(Debug, Default)]
+struct BlockTimeEstimate {
+    /// The latest height seen, and when it was first seen
+    last_block: Option<(Height, Instant)>,
+    /// The average block time, once at least two blocks were seen
+    average: Option<Duration>,
+}
+
+impl BlockTimeEstimate {
+    fn observe(&mut self, height: Height, now: Instant) {
+        match self.last_block {
+            Some((last_height, seen_at))
+                if last_height.revision_number() == height.revision_number() =>
+            {
+                if height <= last_height {
+                    return;
+                }
+
+                let blocks =
+                    u32::try_from(height.revision_height() - last_height.revision_height())
+                        .unwrap_or(u32::MAX);
+                let sample = now.duration_since(seen_at) / blocks;
+
+                self.average = Some(match self.average {
+                    Some(average) => {
+                        (average * (100 - BLOCK_TIME_SAMPLE_WEIGHT)
+                            + sample * BLOCK_TIME_SAMPLE_WEIGHT)
+                            / 100
+                    }
+                    None => sample,
+                });
+            }
+            _ => {}
+        }
+
+        self.last_block = Some((height, now));
+    }
+
+    /// How long to wait before the next poll: until the next block is expected,
+    /// or a fraction of the block time if it is already late, bounded by
+    /// [`MIN_POLL_INTERVAL`] and `max_interval`.
+    fn next_poll_delay(&self, now: Instant, max_interval: Duration) -> Duration {
+        let (average, seen_at) = match (self.average, self.last_block) {
+            (Some(average), Some((_, seen_at))) => (average, seen_at),
+            _ => return max_interval,
+        };
+
+        let until_next_block = (seen_at + average).saturating_duration_since(now);
+
+        let delay = if until_next_block.is_zero() {
+            average / 4
+        } else {
+            until_next_block
+        };
+
+        delay.clamp(MIN_POLL_INTERVAL, max_interval.max(MIN_POLL_INTERVAL))
+    }
+}
+
+/// Spread the given delay by up to 10% either way, so that the relayers
+/// polling a node at the same block time do not all hit it at once.
+fn with_jitter(delay: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .subsec_nanos();
+
+    // Pseudo-random factor between 90% and 110%
+    let percent = 90 + nanos % 21;
+
+    delay * percent / 100
+}
+
+fn build_client(rpc_addr: &Url, rpc_compat: CompatMode) -> Result<HttpClient> {
+    let mut client = HttpClient::new(rpc_addr.clone()).map_err(Error::rpc)?;
+    client.set_compat_mode(rpc_compat);
+    Ok(client)
+}
+
+async fn query_latest_height(client: &HttpClient) -> Result<Height> {
+    let status = client.status().await.map_err(Error::rpc)?;
+
+    // The revision number is taken from the chain identifier reported by the node,
+    // so that a change of revision can be detected.
+    Height::new(
+        ChainId::chain_version(status.node_info.network.as_str()),
+        u64::from(status.sync_info.latest_block_height),
+    )
+    .map_err(|_| Error::collect_events_failed("invalid latest block height of 0".to_string()))
+}
+
+/// Query the IBC events emitted at the given height via `/block_results`,
+/// in the order in which they were emitted, preceded by a `NewBlock` event.
+async fn query_events(
+    client: &HttpClient,
+    chain_id: &ChainId,
+    height: Height,
+) -> Result<Vec<IbcEventWithHeight>> {
+    trace!(chain = %chain_id, %height, "querying block results");
+
+    let tm_height = tendermint::block::Height::try_from(height.revision_height())
+        .map_err(|_| Error::collect_events_failed(format!("invalid block height {height}")))?;
+
+    let response = client.block_results(tm_height).await.map_err(Error::rpc)?;
+
+    Ok(rpc::get_block_results_events(chain_id, height, &response))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn height(h: u64) -> Height {
+        Height::new(0, h).unwrap()
+    }
+
+    #[test]
+    fn block_time_estimate() {
+        let max = Duration::from_secs(5);
+        let start = Instant::now();
+        let mut estimate = BlockTimeEstimate::default();
+
+        // Without any block time yet, poll at the configured interval
+        estimate.observe(height(10), start);
+        assert_eq!(estimate.next_poll_delay(start, max), max);
+
+        // Two blocks in 2 seconds, then polled again without a new block
+        estimate.observe(height(12), start + Duration::from_secs(2));
+        estimate.observe(height(12), start + Duration::from_millis(2500));
+        assert_eq!(estimate.average, Some(Duration::from_secs(1)));
+
+        // The next block is expected 1 second after block 12 was seen
+        let now = start + Duration::from_millis(2500);
+        assert_eq!(
+            estimate.next_poll_delay(now, max),
+            Duration::from_millis(500)
+        );
+
+        // When the block is late, poll at a quarter of the block time
+        let now = start + Duration::from_secs(4);
+        assert_eq!(
+            estimate.next_poll_delay(now, max),
+            Duration::from_millis(250)
+        );
+
+        // Never poll less often than the configured interval
+        assert_eq!(
+            estimate.next_poll_delay(now, Duration::from_millis(200)),
+            Duration::from_millis(200)
+        );
+    }
+}
@@ -0,0 +1,368 @@
+use alloc::sync::Arc;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use async_trait::async_trait;
+use futures::{
+    stream::{self, select_all, StreamExt},
+    Stream, TryStreamExt,
+};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, instrument, trace, warn};
+
+use tendermint_rpc::endpoint::block_results::Response as BlockResults;
+use tendermint_rpc::{
+    client::CompatMode, event::Event as RpcEvent, query::Query, Client, Error as RpcError,
+    SubscriptionClient, WebSocketClient, WebSocketClientDriver, WebSocketClientUrl,
+    WebSocketConfig,
+};
+
+use ibc_relayer_types::{
+    core::ics02_client::height::Height, core::ics24_host::identifier::ChainId,
+};
+
+use crate::config::ChainConfig;
+use crate::event::{rpc, IbcEventWithHeight};
+
+use super::{queries, Error, EventStream, EventStreamProvider, Result};
+
+type SubscriptionResult = core::result::Result<RpcEvent, RpcError>;
+type SubscriptionStream = dyn Stream<Item = SubscriptionResult> + Send + Sync + Unpin;
+
+/// Connect to a Tendermint node, subscribe to a set of queries,
+/// and receive push events over a WebSocket.
+///
+/// The default events that are queried are:
+/// - [`EventType::NewBlock`](tendermint_rpc::query::EventType::NewBlock)
+/// - [`EventType::Tx`](tendermint_rpc::query::EventType::Tx)
+pub struct WebSocketEventProvider {
+    chain_id: ChainId,
+    /// Whether to query `/block_results` when the events of a block cannot be decoded
+    rpc_fallback: bool,
+    /// WebSocket to collect events from
+    client: WebSocketClient,
+    /// Async task handle for the WebSocket client's driver
+    driver_handle: JoinHandle<()>,
+    /// Channel where to receive client driver errors
+    rx_err: Option<mpsc::UnboundedReceiver<RpcError>>,
+    /// Node Address
+    ws_url: WebSocketClientUrl,
+    /// RPC compatibility mode
+    rpc_compat: CompatMode,
+    /// WebSocket transport configuration
+    ws_config: WebSocketConfig,
+    /// Queries
+    event_queries: Vec<Query>,
+    /// All subscriptions combined in a single stream
+    subscriptions: Box<SubscriptionStream>,
+}
+
+impl WebSocketEventProvider {
+    /// Connect to the WebSocket endpoint of a node
+    #[instrument(
+        name = "event_monitor.websocket.create",
+        level = "error",
+        skip_all,
+        fields(chain = %chain_id, url = %ws_url)
+    )]
+    pub async fn connect(
+        chain_id: ChainId,
+        ws_url: WebSocketClientUrl,
+        rpc_compat: CompatMode,
+        ws_config: WebSocketConfig,
+        rpc_fallback: bool,
+    ) -> Result<Self> {
+        let (client, driver_handle, rx_err) =
+            build_client(&chain_id, &ws_url, rpc_compat, ws_config).await?;
+
+        // TODO: move them to config file(?)
+        let event_queries = queries::all();
+
+        Ok(Self {
+            chain_id,
+            rpc_fallback,
+            client,
+            driver_handle,
+            rx_err: Some(rx_err),
+            ws_url,
+            rpc_compat,
+            ws_config,
+            event_queries,
+            subscriptions: Box::new(stream::empty()),
+        })
+    }
+
+    /// The list of [`Query`] that this provider is subscribing for.
+    pub fn queries(&self) -> &[Query] {
+        &self.event_queries
+    }
+}
+
+#[async_trait]
+impl EventStreamProvider for WebSocketEventProvider {
+    fn description(&self) -> String {
+        format!("WebSocket endpoint {}", self.ws_url)
+    }
+
+    /// Clear the current subscriptions, and subscribe again to all queries.
+    async fn subscribe(&mut self) -> Result<()> {
+        let mut subscriptions = vec![];
+
+        for query in &self.event_queries {
+            trace!("subscribing to query: {}", query);
+
+            let subscription = self
+                .client
+                .subscribe(query.clone())
+                .await
+                .map_err(Error::client_subscription_failed)?;
+
+            subscriptions.push(subscription);
+        }
+
+        self.subscriptions = Box::new(select_all(subscriptions));
+
+        trace!("subscribed to all queries");
+
+        Ok(())
+    }
+
+    fn events(&mut self) -> EventStream {
+        // Take ownership of the subscriptions
+        let subscriptions = core::mem::replace(&mut self.subscriptions, Box::new(stream::empty()));
+
+        let fallback_client = self.rpc_fallback.then(|| self.client.clone());
+        let events = collect_all_events(subscriptions, self.chain_id.clone(), fallback_client);
+
+        // Surface the errors of the WebSocket driver in the stream of events,
+        // so that the monitor reconnects when the driver fails.
+        match self.rx_err.take() {
+            Some(rx_err) => {
+                let driver_errors = stream::unfold(rx_err, |mut rx_err| async move {
+                    rx_err
+                        .recv()
+                        .await
+                        .map(|e| (Err(Error::web_socket_driver(e)), rx_err))
+                });
+
+                Box::pin(stream::select(events, driver_errors))
+            }
+            None => Box::pin(events),
+        }
+    }
+
+    async fn reconnect(&mut self) -> Result<()> {
+        trace!("trying to reconnect to WebSocket endpoint {}", self.ws_url);
+
+        let (mut client, mut driver_handle, rx_err) = build_client(
+            &self.chain_id,
+            &self.ws_url,
+            self.rpc_compat,
+            self.ws_config,
+        )
+        .await?;
+
+        // Swap the new client with the previous one which failed,
+        // so that we can shut the latter down gracefully.
+        core::mem::swap(&mut self.client, &mut client);
+        core::mem::swap(&mut self.driver_handle, &mut driver_handle);
+        self.rx_err = Some(rx_err);
+
+        trace!("reconnected to WebSocket endpoint {}", self.ws_url);
+
+        // Shut down previous client
+        trace!("gracefully shutting down previous client",);
+
+        let _ = client.close();
+
+        driver_handle
+            .await
+            .map_err(Error::client_termination_failed)?;
+
+        trace!("previous client successfully shutdown");
+
+        Ok(())
+    }
+
+    async fn shutdown(&mut self) {
+        // Close the WebSocket connection
+        let _ = self.client.clone().close();
+
+        // Wait for the WebSocket driver to finish
+        let _ = (&mut self.driver_handle).await;
+    }
+}
+
+/// Build the WebSocket transport configuration of the event monitor for the given chain.
+///
+/// Large blocks with many events may exceed the default message size limits,
+/// in which case the subscription is closed by the WebSocket driver.
+pub fn websocket_config(chain_config: &ChainConfig) -> WebSocketConfig {
+    WebSocketConfig {
+        max_message_size: Some(chain_config.max_ws_message_size.get_bytes() as usize),
+        max_frame_size: Some(chain_config.max_ws_frame_size.get_bytes() as usize),
+        ..WebSocketConfig::default()
+    }
+}
+
+async fn build_client(
+    chain_id: &ChainId,
+    ws_url: &WebSocketClientUrl,
+    rpc_compat: CompatMode,
+    ws_config: WebSocketConfig,
+) -> Result<(
+    WebSocketClient,
+    JoinHandle<()>,
+    mpsc::UnboundedReceiver<RpcError>,
+)> {
+    let builder = WebSocketClient::builder(ws_url.clone())
+        .compat_mode(rpc_compat)
+        .config(ws_config);
+
+    let (client, driver) = builder
+        .build()
+        .await
+        .map_err(|_| Error::client_creation_failed(chain_id.clone(), ws_url.clone()))?;
+
+    let (tx_err, rx_err) = mpsc::unbounded_channel();
+    let driver_handle = tokio::spawn(run_driver(driver, tx_err));
+
+    Ok((client, driver_handle, rx_err))
+}
+
+/// Convert a stream of RPC events into a stream of IBC events
+fn collect_all_events(
+    subscriptions: Box<SubscriptionStream>,
+    chain_id: ChainId,
+    fallback_client: Option<WebSocketClient>,
+) -> impl Stream<Item = Result<IbcEventWithHeight>> {
+    // The revision number of the last block, for the heights of the transaction events
+    // to follow the revision of the chain when it is upgraded.
+    let revision_number = Arc::new(AtomicU64::new(chain_id.version()));
+
+    subscriptions
+        .map_err(Error::canceled_or_generic)
+        .and_then(move |rpc_event| {
+            let id = chain_id.clone();
+            let revision_number = revision_number.clone();
+            let fallback_client = fallback_client.clone();
+
+            async move {
+                debug!(chain = %id, "received an RPC event: {}", rpc_event.query);
+                Ok(
+                    collect_events(&id, &revision_number, rpc_event, fallback_client.as_ref())
+                        .await,
+                )
+            }
+        })
+        .try_flatten()
+}
+
+/// Collect the IBC events from an RPC event.
+///
+/// If the events of a `NewBlock` or `Tx` RPC event cannot be decoded and a fallback client
+/// is given, the events of that block or transaction are queried via `/block_results`
+/// instead of being dropped.
+async fn collect_events(
+    chain_id: &ChainId,
+    revision_number: &AtomicU64,
+    event: RpcEvent,
+    fallback_client: Option<&WebSocketClient>,
+) -> impl Stream<Item = Result<IbcEventWithHeight>> {
+    let block_height = rpc::new_block_height(&event);
+
+    if let Some(height) = block_height {
+        revision_number.store(height.revision_number(), Ordering::Relaxed);
+    }
+
+    let revision_number = revision_number.load(Ordering::Relaxed);
+    let tx_position = rpc::tx_position(&event, revision_number);
+    let query = event.query.clone();
+
+    let events = match rpc::get_all_events(chain_id, revision_number, event) {
+        Ok(events) => events,
+        Err(e) => match (fallback_client, block_height, tx_position) {
+            (Some(client), Some(height), _) => {
+                warn!(
+                    chain = %chain_id, %height,
+                    "failed to extract events from RPC event: {e}, querying block results instead"
+                );
+
+                match query_block_results(client, height).await {
+                    Ok(block_results) => rpc::get_new_block_events(height, &block_results),
+                    Err(e) => {
+                        error!(
+                            chain = %chain_id, %height,
+                            "failed to query block results, events at this height will be missed: {e}"
+                        );
+
+                        vec![rpc::new_block_event(height)]
+                    }
+                }
+            }
+            (Some(client), None, Some((height, index))) => {
+                warn!(
+                    chain = %chain_id, %height,
+                    "failed to extract events from RPC event: {e}, querying block results instead"
+                );
+
+                let events = query_block_results(client, height)
+                    .await
+                    .and_then(|block_results| {
+                        rpc::get_block_results_tx_events(
+                            chain_id,
+                            height,
+                            &query,
+                            &block_results,
+                            index,
+                        )
+                        .ok_or_else(|| {
+                            Error::collect_events_failed(format!(
+                                "no transaction at index {index} in block results"
+                            ))
+                        })
+                    });
+
+                events.unwrap_or_else(|e| {
+                    error!(
+                        chain = %chain_id, %height,
+                        "failed to query block results, events of the transaction will be missed: {e}"
+                    );
+
+                    vec![]
+                })
+            }
+            // Still report the new block, without its events
+            (None, Some(height), _) => {
+                warn!(
+                    chain = %chain_id, %height,
+                    "failed to extract events from RPC event, events at this height will be missed: {e}"
+                );
+
+                vec![rpc::new_block_event(height)]
+            }
+            (_, None, _) => {
+                warn!(chain = %chain_id, "failed to extract events from RPC event: {e}");
+                vec![]
+            }
+        },
+    };
+
+    stream::iter(events).map(Ok)
+}
+
+/// Query the results of the block at the given height via `/block_results`.
+async fn query_block_results(client: &WebSocketClient, height: Height) -> Result<BlockResults> {
+    let tm_height = tendermint::block::Height::try_from(height.revision_height())
+        .map_err(|_| Error::collect_events_failed(format!("invalid block height {height}")))?;
+
+    client.block_results(tm_height).await.map_err(Error::rpc)
+}
+
+async fn run_driver(driver: WebSocketClientDriver, tx: mpsc::UnboundedSender<RpcError>) {
+    if let Err(e) = driver.run().await {
+        if tx.send(e).is_err() {
+            error!("failed to relay driver error to event monitor");
+        }
+    }
+}
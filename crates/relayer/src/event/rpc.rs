@@ -2,13 +2,15 @@ use alloc::collections::BTreeMap as HashMap;
 use core::convert::TryFrom;
 use ibc_relayer_types::applications::ics29_fee::events::DistributionType;
 
+use tendermint::abci::Event as AbciEvent;
+use tendermint_rpc::endpoint::block_results::Response as BlockResults;
 use tendermint_rpc::{event::Event as RpcEvent, event::EventData as RpcEventData};
 
 use ibc_relayer_types::applications::ics31_icq::events::CrossChainQueryPacket;
 use ibc_relayer_types::core::ics02_client::{events as ClientEvents, height::Height};
 use ibc_relayer_types::core::ics04_channel::events as ChannelEvents;
 use ibc_relayer_types::core::ics24_host::identifier::ChainId;
-use ibc_relayer_types::events::IbcEvent;
+use ibc_relayer_types::events::{ErrorDetail as IbcEventErrorDetail, IbcEvent};
 
 use crate::chain::cosmos::types::events::channel::RawObject;
 use crate::event::monitor::queries;
@@ -116,8 +118,16 @@ use super::{ibc_event_try_from_abci_event, IbcEventWithHeight};
 /// {Begin,End}Block events however do not have any such `message.action` associated with them, so
 /// this doesn't work. For this reason, we extract block events in the following order ->
 /// OpenInit -> OpenTry -> OpenAck -> OpenConfirm -> SendPacket -> CloseInit -> CloseConfirm.
+///
+/// An error is returned if some of the block events or of the IBC events of a transaction
+/// cannot be decoded, so that the events can be queried otherwise, see [`get_new_block_events`]
+/// and [`get_block_results_tx_events`].
+///
+/// The heights of the `Tx` events, which do not carry the identifier of the chain, are
+/// assigned the given revision number, which should be that of the last `NewBlock` event.
 pub fn get_all_events(
     chain_id: &ChainId,
+    revision_number: u64,
     result: RpcEvent,
 ) -> Result<Vec<IbcEventWithHeight>, String> {
     let mut events_with_height: Vec<IbcEventWithHeight> = vec![];
@@ -126,13 +136,16 @@ pub fn get_all_events(
         events,
         query,
     } = result;
-    let events = events.ok_or("missing events")?;
-
     match data {
         RpcEventData::NewBlock { block, .. } if query == queries::new_block().to_string() => {
+            let events = events.ok_or("missing events")?;
+            let block = block.as_ref().ok_or("tx.height")?;
+
+            // Take the revision number from the chain identifier in the block header rather
+            // than from the configured one, so that a change of revision can be detected.
             let height = Height::new(
-                ChainId::chain_version(chain_id.to_string().as_str()),
-                u64::from(block.as_ref().ok_or("tx.height")?.header.height),
+                ChainId::chain_version(block.header.chain_id.as_str()),
+                u64::from(block.header.height),
             )
             .map_err(|_| String::from("tx.height: invalid header height of 0"))?;
 
@@ -140,63 +153,36 @@ pub fn get_all_events(
                 ClientEvents::NewBlock::new(height).into(),
                 height,
             ));
-            events_with_height.append(&mut extract_block_events(height, &events));
+            events_with_height.append(&mut extract_block_events(height, &events)?);
         }
         RpcEventData::Tx { tx_result } => {
-            let height = Height::new(
-                ChainId::chain_version(chain_id.to_string().as_str()),
-                tx_result.height as u64,
-            )
-            .map_err(|_| String::from("tx_result.height: invalid header height of 0"))?;
-
-            for abci_event in &tx_result.result.events {
-                if let Ok(ibc_event) = ibc_event_try_from_abci_event(abci_event) {
-                    if query == queries::ibc_client().to_string()
-                        && event_is_type_client(&ibc_event)
-                    {
-                        tracing::trace!("extracted ibc_client event {}", ibc_event);
-                        events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
-                    } else if query == queries::ibc_connection().to_string()
-                        && event_is_type_connection(&ibc_event)
-                    {
-                        tracing::trace!("extracted ibc_connection event {}", ibc_event);
-                        events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
-                    } else if query == queries::ibc_channel().to_string()
-                        && event_is_type_channel(&ibc_event)
-                    {
-                        let _span = tracing::trace_span!("ibc_channel event").entered();
-                        tracing::trace!("extracted {}", ibc_event);
-                        if matches!(ibc_event, IbcEvent::SendPacket(_)) {
-                            // Should be the same as the hash of tx_result.tx?
-                            if let Some(hash) =
-                                events.get("tx.hash").and_then(|values| values.get(0))
-                            {
-                                tracing::trace!(event = "SendPacket", "tx hash: {}", hash);
-                            }
-                        }
-
-                        events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
-                    } else if query == queries::ibc_query().to_string()
-                        && event_is_type_cross_chain_query(&ibc_event)
-                    {
-                        tracing::trace!("extracted cross chain queries {}", ibc_event);
-                        events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
-                    } else if query == queries::ibc_channel().to_string()
-                        && event_is_type_incentivized(&ibc_event)
-                    {
-                        events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
-                    } else if query == queries::ibc_channel().to_string()
-                        && event_is_type_distribute_fee(&ibc_event)
-                    {
-                        if let IbcEvent::DistributeFeePacket(dist) = ibc_event {
-                            // Only record rewarded fees
-                            if let DistributionType::Reward = dist.distribution_type {
-                                telemetry!(fees_amount, chain_id, &dist.receiver, dist.fee);
-                            }
-                        }
-                    }
+            let height = Height::new(revision_number, tx_result.height as u64)
+                .map_err(|_| String::from("tx_result.height: invalid header height of 0"))?;
+
+            let (mut tx_events, undecodable) =
+                extract_tx_events(chain_id, height, &query, &tx_result.result.events);
+
+            if undecodable > 0 {
+                return Err(format!(
+                    "{undecodable} IBC event(s) of the transaction could not be decoded"
+                ));
+            }
+
+            if tx_events
+                .iter()
+                .any(|e| matches!(e.event, IbcEvent::SendPacket(_)))
+            {
+                // Should be the same as the hash of tx_result.tx?
+                if let Some(hash) = events
+                    .as_ref()
+                    .and_then(|events| events.get("tx.hash"))
+                    .and_then(|values| values.get(0))
+                {
+                    tracing::trace!(event = "SendPacket", "tx hash: {}", hash);
                 }
             }
+
+            events_with_height.append(&mut tx_events);
         }
         _ => {}
     }
@@ -204,6 +190,239 @@ pub fn get_all_events(
     Ok(events_with_height)
 }
 
+/// Return the height of the block including the transaction carried by a `Tx` RPC event,
+/// with the given revision number, and the index of the transaction in the block, if any.
+pub fn tx_position(event: &RpcEvent, revision_number: u64) -> Option<(Height, usize)> {
+    match &event.data {
+        RpcEventData::Tx { tx_result } => {
+            let height =
+                Height::new(revision_number, u64::try_from(tx_result.height).ok()?).ok()?;
+            let index = tx_result
+                .index
+                .and_then(|index| usize::try_from(index).ok())?;
+            Some((height, index))
+        }
+        _ => None,
+    }
+}
+
+/// Return the height of the block carried by a `NewBlock` RPC event, if any.
+pub fn new_block_height(event: &RpcEvent) -> Option<Height> {
+    match &event.data {
+        RpcEventData::NewBlock {
+            block: Some(block), ..
+        } => Height::new(
+            ChainId::chain_version(block.header.chain_id.as_str()),
+            u64::from(block.header.height),
+        )
+        .ok(),
+        _ => None,
+    }
+}
+
+/// The `NewBlock` event of the block at the given height.
+pub fn new_block_event(height: Height) -> IbcEventWithHeight {
+    IbcEventWithHeight::new(ClientEvents::NewBlock::new(height).into(), height)
+}
+
+/// The IBC events of the block at the given height, given its results as returned by the
+/// `/block_results` RPC endpoint: the `NewBlock` event followed by the events emitted in
+/// BeginBlock and EndBlock, in the order in which they were emitted.
+///
+/// This is used as a fallback when the events of a `NewBlock` RPC event cannot be decoded,
+/// the events of the transactions being delivered by the `Tx` RPC events.
+pub fn get_new_block_events(
+    height: Height,
+    block_results: &BlockResults,
+) -> Vec<IbcEventWithHeight> {
+    let mut events = vec![new_block_event(height)];
+    events.append(&mut get_begin_block_events(height, block_results));
+    events.append(&mut get_end_block_events(height, block_results));
+    events
+}
+
+/// The IBC events of the block at the given height, given its results as returned by the
+/// `/block_results` RPC endpoint: the `NewBlock` event followed by the events emitted in
+/// BeginBlock, by each transaction and in EndBlock, in the order in which they were emitted.
+pub fn get_block_results_events(
+    chain_id: &ChainId,
+    height: Height,
+    block_results: &BlockResults,
+) -> Vec<IbcEventWithHeight> {
+    let mut events = vec![new_block_event(height)];
+
+    events.append(&mut get_begin_block_events(height, block_results));
+
+    for tx_result in block_results.txs_results.iter().flatten() {
+        events.append(&mut get_tx_events(chain_id, height, &tx_result.events));
+    }
+
+    events.append(&mut get_end_block_events(height, block_results));
+
+    events
+}
+
+/// The IBC events emitted by the transaction at the given index of the block at the given
+/// height, given the results of the block as returned by the `/block_results` RPC endpoint,
+/// which would be delivered to the given `Tx` subscription query.
+///
+/// This is used as a fallback when the events of a `Tx` RPC event cannot be decoded.
+/// Returns `None` if the block has no transaction at that index.
+pub fn get_block_results_tx_events(
+    chain_id: &ChainId,
+    height: Height,
+    query: &str,
+    block_results: &BlockResults,
+    index: usize,
+) -> Option<Vec<IbcEventWithHeight>> {
+    let tx_result = block_results.txs_results.as_ref()?.get(index)?;
+    let (events, _) = extract_tx_events(chain_id, height, query, &tx_result.events);
+    Some(events)
+}
+
+fn get_begin_block_events(height: Height, block_results: &BlockResults) -> Vec<IbcEventWithHeight> {
+    block_results
+        .begin_block_events
+        .as_deref()
+        .map(|events| get_block_events(height, events))
+        .unwrap_or_default()
+}
+
+fn get_end_block_events(height: Height, block_results: &BlockResults) -> Vec<IbcEventWithHeight> {
+    block_results
+        .end_block_events
+        .as_deref()
+        .map(|events| get_block_events(height, events))
+        .unwrap_or_default()
+}
+
+/// Extract the IBC events which can be emitted in {Begin,End}Block from a list of
+/// ABCI events, eg. as returned by the `/block_results` RPC endpoint, in their order.
+///
+/// This extracts the same kinds of events as `extract_block_events`.
+pub fn get_block_events(height: Height, abci_events: &[AbciEvent]) -> Vec<IbcEventWithHeight> {
+    abci_events
+        .iter()
+        .filter_map(|abci_event| ibc_event_try_from_abci_event(abci_event).ok())
+        .filter(event_is_type_block)
+        .map(|ibc_event| IbcEventWithHeight::new(ibc_event, height))
+        .collect()
+}
+
+/// Extract the IBC events emitted by a transaction from its ABCI events, eg. as returned
+/// by the `/block_results` RPC endpoint.
+///
+/// This extracts the same kinds of events as the `Tx` subscriptions of the event monitor,
+/// and is used when polling for the events of each block instead of subscribing to them.
+pub fn get_tx_events(
+    chain_id: &ChainId,
+    height: Height,
+    abci_events: &[AbciEvent],
+) -> Vec<IbcEventWithHeight> {
+    let mut events_with_height = vec![];
+
+    for abci_event in abci_events {
+        if let Ok(ibc_event) = ibc_event_try_from_abci_event(abci_event) {
+            if event_is_type_client(&ibc_event)
+                || event_is_type_connection(&ibc_event)
+                || event_is_type_channel(&ibc_event)
+                || event_is_type_cross_chain_query(&ibc_event)
+                || event_is_type_incentivized(&ibc_event)
+            {
+                events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
+            } else if let IbcEvent::DistributeFeePacket(dist) = ibc_event {
+                // Only record rewarded fees
+                if let DistributionType::Reward = dist.distribution_type {
+                    telemetry!(fees_amount, chain_id, &dist.receiver, dist.fee);
+                }
+            }
+        }
+    }
+
+    events_with_height
+}
+
+/// Extract the IBC events which are delivered to the given `Tx` subscription query from
+/// the ABCI events of a transaction, along with the number of IBC events which could not
+/// be decoded.
+fn extract_tx_events(
+    chain_id: &ChainId,
+    height: Height,
+    query: &str,
+    abci_events: &[AbciEvent],
+) -> (Vec<IbcEventWithHeight>, usize) {
+    let mut events_with_height = vec![];
+    let mut undecodable = 0;
+
+    for abci_event in abci_events {
+        let ibc_event = match ibc_event_try_from_abci_event(abci_event) {
+            Ok(ibc_event) => ibc_event,
+            // Not an IBC event
+            Err(e) if matches!(e.detail(), IbcEventErrorDetail::UnsupportedAbciEvent(_)) => {
+                continue
+            }
+            Err(e) => {
+                tracing::warn!(
+                    "failed to decode {} event at height {}: {}",
+                    abci_event.kind,
+                    height,
+                    e
+                );
+                undecodable += 1;
+                continue;
+            }
+        };
+
+        if query == queries::ibc_client().to_string() && event_is_type_client(&ibc_event) {
+            tracing::trace!("extracted ibc_client event {}", ibc_event);
+            events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
+        } else if query == queries::ibc_connection().to_string()
+            && event_is_type_connection(&ibc_event)
+        {
+            tracing::trace!("extracted ibc_connection event {}", ibc_event);
+            events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
+        } else if query == queries::ibc_channel().to_string() && event_is_type_channel(&ibc_event) {
+            let _span = tracing::trace_span!("ibc_channel event").entered();
+            tracing::trace!("extracted {}", ibc_event);
+            events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
+        } else if query == queries::ibc_query().to_string()
+            && event_is_type_cross_chain_query(&ibc_event)
+        {
+            tracing::trace!("extracted cross chain queries {}", ibc_event);
+            events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
+        } else if query == queries::ibc_channel().to_string()
+            && event_is_type_incentivized(&ibc_event)
+        {
+            events_with_height.push(IbcEventWithHeight::new(ibc_event, height));
+        } else if query == queries::ibc_channel().to_string()
+            && event_is_type_distribute_fee(&ibc_event)
+        {
+            if let IbcEvent::DistributeFeePacket(dist) = ibc_event {
+                // Only record rewarded fees
+                if let DistributionType::Reward = dist.distribution_type {
+                    telemetry!(fees_amount, chain_id, &dist.receiver, dist.fee);
+                }
+            }
+        }
+    }
+
+    (events_with_height, undecodable)
+}
+
+fn event_is_type_block(ev: &IbcEvent) -> bool {
+    matches!(
+        ev,
+        IbcEvent::OpenInitChannel(_)
+            | IbcEvent::OpenTryChannel(_)
+            | IbcEvent::OpenAckChannel(_)
+            | IbcEvent::OpenConfirmChannel(_)
+            | IbcEvent::SendPacket(_)
+            | IbcEvent::CloseInitChannel(_)
+            | IbcEvent::CloseConfirmChannel(_)
+            | IbcEvent::CrossChainQueryPacket(_)
+    )
+}
+
 fn event_is_type_client(ev: &IbcEvent) -> bool {
     matches!(
         ev,
@@ -254,27 +473,32 @@ fn event_is_type_distribute_fee(ev: &IbcEvent) -> bool {
     matches!(ev, IbcEvent::DistributeFeePacket(_))
 }
 
+/// Extract the IBC events emitted in {Begin,End}Block, failing if some cannot be decoded.
 fn extract_block_events(
     height: Height,
     block_events: &HashMap<String, Vec<String>>,
-) -> Vec<IbcEventWithHeight> {
+) -> Result<Vec<IbcEventWithHeight>, String> {
     #[inline]
     fn extract_events<'a, T: TryFrom<RawObject<'a>>>(
         height: Height,
         block_events: &'a HashMap<String, Vec<String>>,
         event_type: &str,
         event_field: &str,
+        undecodable: &mut usize,
     ) -> Vec<T> {
-        block_events
+        let count = block_events
             .get(&format!("{event_type}.{event_field}"))
-            .unwrap_or(&vec![])
-            .iter()
-            .enumerate()
-            .filter_map(|(i, _)| {
+            .map_or(0, Vec::len);
+
+        let events: Vec<T> = (0..count)
+            .filter_map(|i| {
                 let raw_obj = RawObject::new(height, event_type.to_owned(), i, block_events);
                 T::try_from(raw_obj).ok()
             })
-            .collect()
+            .collect();
+
+        *undecodable += count - events.len();
+        events
     }
 
     #[inline]
@@ -291,40 +515,83 @@ fn extract_block_events(
         );
     }
 
+    let mut undecodable = 0;
     let mut events: Vec<IbcEventWithHeight> = vec![];
     append_events::<ChannelEvents::OpenInit>(
         &mut events,
-        extract_events(height, block_events, "channel_open_init", "channel_id"),
+        extract_events(
+            height,
+            block_events,
+            "channel_open_init",
+            "channel_id",
+            &mut undecodable,
+        ),
         height,
     );
     append_events::<ChannelEvents::OpenTry>(
         &mut events,
-        extract_events(height, block_events, "channel_open_try", "channel_id"),
+        extract_events(
+            height,
+            block_events,
+            "channel_open_try",
+            "channel_id",
+            &mut undecodable,
+        ),
         height,
     );
     append_events::<ChannelEvents::OpenAck>(
         &mut events,
-        extract_events(height, block_events, "channel_open_ack", "channel_id"),
+        extract_events(
+            height,
+            block_events,
+            "channel_open_ack",
+            "channel_id",
+            &mut undecodable,
+        ),
         height,
     );
     append_events::<ChannelEvents::OpenConfirm>(
         &mut events,
-        extract_events(height, block_events, "channel_open_confirm", "channel_id"),
+        extract_events(
+            height,
+            block_events,
+            "channel_open_confirm",
+            "channel_id",
+            &mut undecodable,
+        ),
         height,
     );
     append_events::<ChannelEvents::SendPacket>(
         &mut events,
-        extract_events(height, block_events, "send_packet", "packet_data"),
+        extract_events(
+            height,
+            block_events,
+            "send_packet",
+            "packet_data",
+            &mut undecodable,
+        ),
         height,
     );
     append_events::<ChannelEvents::CloseInit>(
         &mut events,
-        extract_events(height, block_events, "channel_close_init", "channel_id"),
+        extract_events(
+            height,
+            block_events,
+            "channel_close_init",
+            "channel_id",
+            &mut undecodable,
+        ),
         height,
     );
     append_events::<ChannelEvents::CloseConfirm>(
         &mut events,
-        extract_events(height, block_events, "channel_close_confirm", "channel_id"),
+        extract_events(
+            height,
+            block_events,
+            "channel_close_confirm",
+            "channel_id",
+            &mut undecodable,
+        ),
         height,
     );
     // extract cross chain query event from block_events
@@ -332,5 +599,123 @@ fn extract_block_events(
         events.push(IbcEventWithHeight::new(ccq, height));
     }
 
-    events
+    if undecodable > 0 {
+        return Err(format!("{undecodable} block events could not be decoded"));
+    }
+
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::Attributes;
+    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+
+    fn height() -> Height {
+        Height::new(0, 10).unwrap()
+    }
+
+    fn channel_attributes() -> Attributes {
+        Attributes {
+            port_id: "transfer".parse().unwrap(),
+            channel_id: Some("channel-0".parse().unwrap()),
+            connection_id: "connection-0".parse().unwrap(),
+            counterparty_port_id: "transfer".parse().unwrap(),
+            counterparty_channel_id: None,
+        }
+    }
+
+    fn open_init_block_events(with_connection: bool) -> HashMap<String, Vec<String>> {
+        let mut block_events = HashMap::new();
+
+        for (key, value) in [
+            ("port_id", "transfer"),
+            ("channel_id", "channel-0"),
+            ("counterparty_port_id", "transfer"),
+            ("connection_id", "connection-0"),
+        ] {
+            if key != "connection_id" || with_connection {
+                block_events.insert(format!("channel_open_init.{key}"), vec![value.to_string()]);
+            }
+        }
+
+        block_events
+    }
+
+    #[test]
+    fn undecodable_block_events_are_an_error() {
+        let events = extract_block_events(height(), &open_init_block_events(true)).unwrap();
+        assert!(matches!(
+            events.as_slice(),
+            [IbcEventWithHeight {
+                event: IbcEvent::OpenInitChannel(_),
+                ..
+            }]
+        ));
+
+        assert!(extract_block_events(height(), &open_init_block_events(false)).is_err());
+    }
+
+    #[test]
+    fn undecodable_tx_events_are_counted() {
+        let query = queries::ibc_channel().to_string();
+
+        let open_init =
+            AbciEvent::from(ChannelEvents::OpenInit::try_from(channel_attributes()).unwrap());
+        let mut broken = open_init.clone();
+        broken.attributes.retain(|attr| attr.key != "connection_id");
+        let not_ibc = AbciEvent {
+            kind: "message".to_string(),
+            attributes: vec![],
+        };
+
+        let (events, undecodable) =
+            extract_tx_events(&ChainId::default(), height(), &query, &[open_init, not_ibc]);
+        assert_eq!(events.len(), 1);
+        assert_eq!(undecodable, 0);
+
+        let (_, undecodable) = extract_tx_events(&ChainId::default(), height(), &query, &[broken]);
+        assert_eq!(undecodable, 1);
+    }
+
+    #[test]
+    fn block_events_keep_their_order() {
+        let send_packet = ChannelEvents::SendPacket {
+            packet: Packet {
+                sequence: 1.into(),
+                source_port: "transfer".parse().unwrap(),
+                source_channel: "channel-0".parse().unwrap(),
+                destination_port: "transfer".parse().unwrap(),
+                destination_channel: "channel-1".parse().unwrap(),
+                data: b"data".to_vec(),
+                timeout_height: Height::new(0, 100).unwrap().into(),
+                timeout_timestamp: Default::default(),
+            },
+        };
+        let open_init = ChannelEvents::OpenInit::try_from(channel_attributes()).unwrap();
+        let close_init = ChannelEvents::CloseInit::try_from(channel_attributes()).unwrap();
+
+        let begin_block_events = [
+            AbciEvent::try_from(send_packet).unwrap(),
+            AbciEvent::from(open_init),
+        ];
+        let end_block_events = [AbciEvent::from(close_init)];
+
+        let events: Vec<_> = get_block_events(height(), &begin_block_events)
+            .into_iter()
+            .chain(get_block_events(height(), &end_block_events))
+            .map(|event| event.event)
+            .collect();
+
+        assert!(matches!(
+            events.as_slice(),
+            [
+                IbcEvent::SendPacket(_),
+                IbcEvent::OpenInitChannel(_),
+                IbcEvent::CloseInitChannel(_),
+            ]
+        ));
+    }
 }
@@ -1001,21 +1001,35 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
                 self.dst_chain().id()
             );
 
-            let target_dst_height = status.height.increment();
-            loop {
-                thread::sleep(Duration::from_millis(300));
-                status = self.dst_chain().query_application_status().map_err(|e| {
+            let dst_max_block_time = self
+                .dst_chain()
+                .config()
+                .map_err(|e| {
+                    ForeignClientError::client_update(
+                        self.dst_chain.id(),
+                        "failed fetching the configuration of the destination chain".to_string(),
+                        e,
+                    )
+                })?
+                .max_block_time;
+
+            self.dst_chain()
+                .wait_for_height(status.height.increment(), dst_max_block_time)
+                .map_err(|e| {
                     ForeignClientError::client_update(
                         self.dst_chain.id(),
-                        "failed querying latest status of the destination chain".to_string(),
+                        "failed waiting for the next height of the destination chain".to_string(),
                         e,
                     )
                 })?;
 
-                if status.height >= target_dst_height {
-                    break;
-                }
-            }
+            status = self.dst_chain().query_application_status().map_err(|e| {
+                ForeignClientError::client_update(
+                    self.dst_chain.id(),
+                    "failed querying latest status of the destination chain".to_string(),
+                    e,
+                )
+            })?;
         }
 
         let next_ts_adjusted =
@@ -1411,6 +1425,7 @@ impl<DstChain: ChainHandle, SrcChain: ChainHandle> ForeignClient<DstChain, SrcCh
             .query_consensus_state_heights(QueryConsensusStateHeightsRequest {
                 client_id: self.id.clone(),
                 pagination: Some(PageRequest::all()),
+                query_height: QueryHeight::Latest,
             })
             .map_err(|e| {
                 ForeignClientError::client_query(self.id().clone(), self.src_chain.id(), e)
@@ -21,6 +21,7 @@
 extern crate alloc;
 
 pub mod account;
+pub mod builder;
 pub mod cache;
 pub mod chain;
 pub mod channel;
@@ -36,16 +37,21 @@ pub mod foreign_client;
 pub mod keyring;
 pub mod light_client;
 pub mod link;
+pub mod migrate_path;
 pub mod misbehaviour;
 pub mod object;
 pub mod path;
+pub mod recover_client;
 pub mod registry;
 pub mod rest;
 pub mod sdk_error;
+pub mod sink;
 pub mod spawn;
 pub mod supervisor;
 pub mod telemetry;
 pub mod transfer;
 pub mod upgrade_chain;
 pub mod util;
+pub mod verify_client;
+pub mod webhook;
 pub mod worker;
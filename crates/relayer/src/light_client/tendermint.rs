@@ -1,5 +1,6 @@
 mod detector;
 
+use std::path::PathBuf;
 use std::time::Duration;
 
 use itertools::Itertools;
@@ -50,6 +51,7 @@ pub struct LightClient {
     peer_id: PeerId,
     io: AnyIo,
     enable_verification: bool,
+    evidence_dir: Option<PathBuf>,
 }
 
 impl super::LightClient<CosmosSdkChain> for LightClient {
@@ -208,10 +210,9 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
             })) => {
                 warn!("misbehavior detected, reporting evidence to RPC witness node and primary chain");
 
-                match detector::report_evidence(
-                    self.io.rpc_client().clone(),
-                    evidence.against_primary,
-                ) {
+                let attack = evidence.against_primary;
+
+                match detector::report_evidence(self.io.rpc_client().clone(), attack.clone()) {
                     Ok(hash) => warn!("evidence reported to RPC witness node with hash: {hash}"),
                     Err(e) => error!("failed to report evidence to RPC witness node: {}", e),
                 }
@@ -230,17 +231,26 @@ impl super::LightClient<CosmosSdkChain> for LightClient {
                 let (target_header, supporting_headers) =
                     self.adjust_headers(update_header.trusted_height, target_block, trace)?;
 
+                let misbehaviour = TmMisbehaviour {
+                    client_id: update.client_id().clone(),
+                    header1: update_header.clone(),
+                    header2: TmHeader {
+                        signed_header: challenging_block.signed_header,
+                        validator_set: challenging_block.validators,
+                        trusted_height: target_header.trusted_height,
+                        trusted_validator_set: target_header.trusted_validator_set,
+                    },
+                };
+
+                if let Some(dir) = &self.evidence_dir {
+                    match detector::export_evidence(dir, &self.chain_id, &misbehaviour, &attack) {
+                        Ok(path) => warn!("evidence exported to {}", path.display()),
+                        Err(e) => error!("failed to export evidence: {}", e),
+                    }
+                }
+
                 let evidence = MisbehaviourEvidence {
-                    misbehaviour: AnyMisbehaviour::Tendermint(TmMisbehaviour {
-                        client_id: update.client_id().clone(),
-                        header1: update_header.clone(),
-                        header2: TmHeader {
-                            signed_header: challenging_block.signed_header,
-                            validator_set: challenging_block.validators,
-                            trusted_height: target_header.trusted_height,
-                            trusted_validator_set: target_header.trusted_validator_set,
-                        },
-                    }),
+                    misbehaviour: AnyMisbehaviour::Tendermint(misbehaviour),
                     supporting_headers: supporting_headers
                         .into_iter()
                         .map(AnyHeader::Tendermint)
@@ -298,6 +308,7 @@ impl LightClient {
             io,
 
             enable_verification,
+            evidence_dir: config.evidence_dir.clone(),
         })
     }
 
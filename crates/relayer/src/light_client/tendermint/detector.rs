@@ -1,3 +1,7 @@
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+
+use serde::Serialize;
 use tracing::{error, info};
 
 use tendermint::{
@@ -16,6 +20,8 @@ use tendermint_light_client_detector::{detect_divergence, Divergence, Provider};
 use tendermint_rpc::{Client, HttpClient};
 
 use ibc_relayer_types::clients::ics07_tendermint::client_state::ClientState;
+use ibc_relayer_types::clients::ics07_tendermint::misbehaviour::Misbehaviour as TmMisbehaviour;
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 
 use crate::{error::Error, util::block_on};
 
@@ -110,3 +116,38 @@ pub fn report_evidence(
         .map(|response| response.hash)
         .map_err(|e| Error::rpc_response(e.to_string()))
 }
+
+/// Evidence of a light client attack, as exported to disk.
+#[derive(Serialize)]
+struct ExportedEvidence<'a> {
+    chain_id: &'a ChainId,
+    misbehaviour: &'a TmMisbehaviour,
+    light_client_attack: &'a LightClientAttackEvidence,
+}
+
+/// Write the evidence of a light client attack on the given chain as a JSON file
+/// in the given directory, so that it can be inspected or submitted out of band.
+///
+/// Returns the path of the file the evidence was written to.
+pub fn export_evidence(
+    dir: &Path,
+    chain_id: &ChainId,
+    misbehaviour: &TmMisbehaviour,
+    attack: &LightClientAttackEvidence,
+) -> Result<PathBuf, Error> {
+    fs::create_dir_all(dir).map_err(Error::io)?;
+
+    let height = misbehaviour.header1.signed_header.header.height;
+    let path = dir.join(format!("{chain_id}-{height}.json"));
+
+    let evidence = ExportedEvidence {
+        chain_id,
+        misbehaviour,
+        light_client_attack: attack,
+    };
+
+    let file = File::create(&path).map_err(Error::io)?;
+    serde_json::to_writer_pretty(file, &evidence).map_err(|e| Error::io(e.into()))?;
+
+    Ok(path)
+}
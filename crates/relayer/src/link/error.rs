@@ -148,6 +148,17 @@ define_error! {
 
         UpdateClientFailed
              |_| { "failed to update client" },
+
+        TxRejected
+            {
+                chain_id: ChainId,
+                code: String,
+                log: String,
+            }
+            |e| {
+                format!("transaction rejected by chain {}: {}: {}",
+                    e.chain_id, e.code, e.log)
+            },
    }
 }
 
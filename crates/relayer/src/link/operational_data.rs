@@ -11,6 +11,7 @@ use ibc_relayer_types::Height;
 use crate::chain::handle::ChainHandle;
 use crate::chain::requests::IncludeProof;
 use crate::chain::requests::QueryClientStateRequest;
+use crate::chain::requests::QueryConsensusStateRequest;
 use crate::chain::requests::QueryHeight;
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
@@ -98,6 +99,8 @@ pub struct OperationalData {
     pub tracking_id: TrackingId,
     /// Stores `Some(ConnectionDelay)` if the delay is non-zero and `None` otherwise
     connection_delay: Option<ConnectionDelay>,
+    /// The instant at which the messages, and their proofs, were built
+    built_at: Instant,
 }
 
 impl OperationalData {
@@ -119,6 +122,7 @@ impl OperationalData {
             target,
             connection_delay,
             tracking_id,
+            built_at: Instant::now(),
         }
     }
 
@@ -159,7 +163,7 @@ impl OperationalData {
     ) -> Result<TrackedMsgs, LinkError> {
         // For zero delay we prepend the client update msgs.
         let client_update_msg = if !self.conn_delay_needed() {
-            let update_height = self.proofs_height.increment();
+            let update_height = relay_path.proof_height(self);
 
             debug!(
                 "prepending {} client update at height {}",
@@ -222,6 +226,49 @@ impl OperationalData {
         Ok(tm)
     }
 
+    /// Returns `true` iff the client on the target chain holds a consensus state at
+    /// the height the proofs are verified at.
+    ///
+    /// The client is updated to that height when the operational data is scheduled,
+    /// but the consensus state may since have been pruned.
+    pub fn has_consensus_state_for_proofs<ChainA: ChainHandle, ChainB: ChainHandle>(
+        &self,
+        relay_path: &RelayPath<ChainA, ChainB>,
+    ) -> Result<bool, LinkError> {
+        let consensus_height = relay_path.proof_height(self);
+
+        let (chain_id, result) = match self.target {
+            OperationalDataTarget::Source => (
+                relay_path.src_chain().id(),
+                relay_path.src_chain().query_consensus_state(
+                    QueryConsensusStateRequest {
+                        client_id: relay_path.src_client_id().clone(),
+                        consensus_height,
+                        query_height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                ),
+            ),
+            OperationalDataTarget::Destination => (
+                relay_path.dst_chain().id(),
+                relay_path.dst_chain().query_consensus_state(
+                    QueryConsensusStateRequest {
+                        client_id: relay_path.dst_client_id().clone(),
+                        consensus_height,
+                        query_height: QueryHeight::Latest,
+                    },
+                    IncludeProof::No,
+                ),
+            ),
+        };
+
+        match result {
+            Ok(_) => Ok(true),
+            Err(e) if e.is_consensus_state_not_found() => Ok(false),
+            Err(e) => Err(LinkError::query(chain_id, e)),
+        }
+    }
+
     /// Returns true iff the batch contains a packet event
     fn has_packet_msgs(&self) -> bool {
         self.batch
@@ -243,6 +290,15 @@ impl OperationalData {
         self.get_delay_if_needed().is_some()
     }
 
+    /// Returns `true` iff the messages were built more than `max_age` ago and can be
+    /// rebuilt from their events with fresher proofs before being submitted.
+    ///
+    /// Messages awaiting a connection delay are never considered outdated, since the
+    /// delay is computed from the client update matching their proofs height.
+    pub fn proofs_outdated(&self, max_age: Duration) -> bool {
+        !self.conn_delay_needed() && self.built_at.elapsed() > max_age
+    }
+
     /// Sets the scheduled time that is used for connection-delay calculations
     pub fn set_scheduled_time(&mut self, scheduled_time: Instant) {
         if let Some(mut delay) = self.connection_delay.as_mut() {
@@ -94,6 +94,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                     counterparty_chain = %self.counterparty_chain_id,
                     port = %self.port_id,
                     channel = %self.channel_id,
+                    tracking_id = %od.tracking_id,
                 );
 
                 let _guard = span.enter();
@@ -170,6 +171,7 @@ impl<Chain: ChainHandle> PendingTxs<Chain> {
                 counterparty_chain = %self.counterparty_chain_id,
                 port = %self.port_id,
                 channel = %self.channel_id,
+                tracking_id = %pending.tracking_id(),
             );
 
             let _guard = span.enter();
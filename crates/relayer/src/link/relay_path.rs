@@ -7,6 +7,8 @@ use ibc_proto::google::protobuf::Any;
 use itertools::Itertools;
 use tracing::{debug, error, info, span, trace, warn, Level};
 
+use ibc_relayer_types::applications::transfer::forward::ForwardMetadata;
+use ibc_relayer_types::applications::transfer::packet::AnyPacketData;
 use ibc_relayer_types::core::ics02_client::events::ClientMisbehaviour as ClientMisbehaviourEvent;
 use ibc_relayer_types::core::ics04_channel::channel::{
     ChannelEnd, Ordering, State as ChannelState,
@@ -17,11 +19,13 @@ use ibc_relayer_types::core::ics04_channel::msgs::{
     recv_packet::MsgRecvPacket, timeout::MsgTimeout, timeout_on_close::MsgTimeoutOnClose,
 };
 use ibc_relayer_types::core::ics04_channel::packet::{Packet, PacketMsgType};
+use ibc_relayer_types::core::ics04_channel::version::Version;
 use ibc_relayer_types::core::ics24_host::identifier::{ChannelId, ClientId, ConnectionId, PortId};
 use ibc_relayer_types::events::{IbcEvent, IbcEventType, WithBlockDataType};
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::Timestamp;
 use ibc_relayer_types::tx_msg::Msg;
+use ibc_relayer_types::utils::redact::packet_data_redacted;
 use ibc_relayer_types::Height;
 
 use crate::chain::counterparty::unreceived_acknowledgements;
@@ -40,6 +44,7 @@ use crate::chain::requests::QueryUnreceivedPacketsRequest;
 use crate::chain::requests::{IncludeProof, Qualified};
 use crate::chain::tracking::TrackedMsgs;
 use crate::chain::tracking::TrackingId;
+use crate::chain::ProofHeightPolicy;
 use crate::channel::error::ChannelError;
 use crate::channel::Channel;
 use crate::event::monitor::EventBatch;
@@ -64,6 +69,11 @@ use crate::util::queue::Queue;
 
 const MAX_RETRIES: usize = 5;
 
+/// Number of destination chain blocks before the timeout of a packet within which
+/// the packet is watched after its `MsgRecvPacket` was submitted, so that it can be
+/// timed out as soon as possible should it fail to be received.
+const TIMEOUT_WATCH_MARGIN_BLOCKS: u64 = 20;
+
 /// Whether or not to resubmit packets when pending transactions
 /// fail to process within the given timeout duration.
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -100,6 +110,9 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     pub src_operational_data: Queue<OperationalData>,
     pub dst_operational_data: Queue<OperationalData>,
 
+    // Maximum number of operational data kept in each of the queues above, if any.
+    max_queued_operational_data: Option<usize>,
+
     // Toggle for the transaction confirmation mechanism.
     confirm_txes: bool,
 
@@ -108,6 +121,20 @@ pub struct RelayPath<ChainA: ChainHandle, ChainB: ChainHandle> {
     // transactions if [`confirm_txes`] is true.
     pending_txs_src: PendingTxs<ChainA>,
     pending_txs_dst: PendingTxs<ChainB>,
+
+    // Whether to relay the packets sent from the source chain,
+    // and the acknowledgements written on the source chain, respectively.
+    relay_packets: bool,
+    relay_acks: bool,
+
+    // The `SendPacket` events whose `MsgRecvPacket` was submitted close to
+    // the packet timeout, which are watched to be timed out if need be.
+    timeout_watchlist: Queue<IbcEventWithHeight>,
+
+    // How the proofs queried from the source and destination chain, respectively,
+    // relate to the height they were queried at.
+    src_proof_height_policy: ProofHeightPolicy,
+    dst_proof_height_policy: ProofHeightPolicy,
 }
 
 impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
@@ -134,6 +161,18 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         let src_port_id = channel.src_port_id().clone();
         let dst_port_id = channel.dst_port_id().clone();
 
+        let src_proof_height_policy = src_chain
+            .config()
+            .map_err(|e| LinkError::query(src_chain_id.clone(), e))?
+            .r#type
+            .proof_height_policy();
+
+        let dst_proof_height_policy = dst_chain
+            .config()
+            .map_err(|e| LinkError::query(dst_chain_id.clone(), e))?
+            .r#type
+            .proof_height_policy();
+
         let path = PathIdentifiers {
             port_id: dst_port_id.clone(),
             channel_id: dst_channel_id.clone(),
@@ -149,12 +188,61 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             src_operational_data: Queue::new(),
             dst_operational_data: Queue::new(),
 
+            max_queued_operational_data: None,
+
             confirm_txes: with_tx_confirmation,
             pending_txs_src: PendingTxs::new(src_chain, src_channel_id, src_port_id, dst_chain_id),
             pending_txs_dst: PendingTxs::new(dst_chain, dst_channel_id, dst_port_id, src_chain_id),
+
+            relay_packets: true,
+            relay_acks: true,
+
+            timeout_watchlist: Queue::new(),
+
+            src_proof_height_policy,
+            dst_proof_height_policy,
         })
     }
 
+    /// Restrict the work of this path to relaying the packets sent from the source chain
+    /// (along with their timeouts), and/or the acknowledgements written on the source chain.
+    pub fn set_relayed_events(&mut self, relay_packets: bool, relay_acks: bool) {
+        self.relay_packets = relay_packets;
+        self.relay_acks = relay_acks;
+    }
+
+    /// Bound the number of operational data queued for each of the source and destination
+    /// chains, dropping the oldest ones once the bound is reached. A bound of `0` disables it.
+    ///
+    /// See [`queue_bound`] for the channels on which the bound is not enforced.
+    pub fn set_max_queued_operational_data(&mut self, max: usize, clear_interval: u64) {
+        self.max_queued_operational_data = queue_bound(max, clear_interval, self.channel.ordering);
+
+        if max > 0 && self.max_queued_operational_data.is_none() {
+            debug!(
+                "not bounding the queued operational data, since dropping them \
+                would prevent relaying their packets on this channel"
+            );
+        }
+    }
+
+    /// The number of submitted transactions awaiting confirmation, on both chains.
+    pub fn pending_tx_count(&self) -> usize {
+        self.pending_txs_src.pending_queue.len() + self.pending_txs_dst.pending_queue.len()
+    }
+
+    /// The height at which the client on the target chain of the given operational data
+    /// must have a consensus state to verify the proofs in its messages, which were
+    /// queried from the counterparty chain at `proofs_height`.
+    pub(crate) fn proof_height(&self, od: &OperationalData) -> Height {
+        let policy = match od.target {
+            OperationalDataTarget::Source => self.dst_proof_height_policy,
+            OperationalDataTarget::Destination => self.src_proof_height_policy,
+        };
+
+        policy.proof_height(od.proofs_height)
+    }
+
     pub fn src_chain(&self) -> &ChainA {
         self.channel.src_chain()
     }
@@ -377,14 +465,16 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         for event_with_height in events.into_iter() {
             match &event_with_height.event {
                 IbcEvent::SendPacket(send_packet_ev) => {
-                    if src_channel_id == send_packet_ev.src_channel_id()
+                    if self.relay_packets
+                        && src_channel_id == send_packet_ev.src_channel_id()
                         && self.src_port_id() == send_packet_ev.src_port_id()
                     {
                         result.push(event_with_height);
                     }
                 }
                 IbcEvent::WriteAcknowledgement(write_ack_ev) => {
-                    if src_channel_id == write_ack_ev.dst_channel_id()
+                    if self.relay_acks
+                        && src_channel_id == write_ack_ev.dst_channel_id()
                         && self.src_port_id() == write_ack_ev.dst_port_id()
                     {
                         result.push(event_with_height);
@@ -419,8 +509,17 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         telemetry!(received_event_batch, tracking_id);
 
         for i in 1..=MAX_RETRIES {
-            let cleared_recv = self.schedule_recv_packet_and_timeout_msgs(height, tracking_id);
-            let cleared_ack = self.schedule_packet_ack_msgs(height, tracking_id);
+            let cleared_recv = if self.relay_packets {
+                self.schedule_recv_packet_and_timeout_msgs(height, tracking_id)
+            } else {
+                Ok(())
+            };
+
+            let cleared_ack = if self.relay_acks {
+                self.schedule_packet_ack_msgs(height, tracking_id)
+            } else {
+                Ok(())
+            };
 
             match cleared_recv.and(cleared_ack) {
                 Ok(()) => return Ok(()),
@@ -708,42 +807,53 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         &self,
         initial_odata: OperationalData,
     ) -> Option<OperationalData> {
-        let op_info = initial_odata.info();
-
         warn!(
             "failed. Regenerate operational data from {} events",
-            op_info.batch_len()
+            initial_odata.batch.len()
         );
 
-        // Retry by re-generating the operational data using the initial events
-        let (src_opt, dst_opt) = match self.generate_operational_data(initial_odata.into_events()) {
-            Ok(new_operational_data) => new_operational_data,
-            Err(e) => {
+        self.rebuild_operational_data(initial_odata)
+    }
+
+    /// Builds the messages of the given operational data anew from its events,
+    /// with proofs queried at the latest height of the chains.
+    ///
+    /// Return value and side effects are the same as for [`Self::regenerate_operational_data`].
+    fn rebuild_operational_data(&self, initial_odata: OperationalData) -> Option<OperationalData> {
+        let op_info = initial_odata.info();
+
+        // Cannot retry, contain the error by reporting a None
+        self.try_rebuild_operational_data(initial_odata)
+            .unwrap_or_else(|e| {
                 error!(
                     "failed to regenerate operational data from initial data: {} \
                     with error {}, discarding this op. data",
                     op_info, e
                 );
-                return None;
-            } // Cannot retry, contain the error by reporting a None
-        };
+                None
+            })
+    }
+
+    /// Builds the messages of the given operational data anew from its events, as for
+    /// [`Self::rebuild_operational_data`], but returns the error if building them fails.
+    fn try_rebuild_operational_data(
+        &self,
+        initial_odata: OperationalData,
+    ) -> Result<Option<OperationalData>, LinkError> {
+        let op_info = initial_odata.info();
+
+        // Retry by re-generating the operational data using the initial events
+        let (src_opt, dst_opt) = self.generate_operational_data(initial_odata.into_events())?;
 
         if let Some(src_od) = src_opt {
             if src_od.target == op_info.target() {
                 // Our target is the _source_ chain, retry these messages
                 info!(odata = %src_od.info(), "will retry");
-                return Some(src_od);
+                return Ok(Some(src_od));
             } else {
                 // Our target is the _destination_ chain, the data in `src_od` contains
                 // potentially new timeout messages that have to be handled separately.
-                if let Err(e) = self.schedule_operational_data(src_od) {
-                    error!(
-                        "failed to schedule newly-generated operational data from \
-                        initial data: {} with error {}, discarding this op. data",
-                        op_info, e
-                    );
-                    return None;
-                }
+                self.schedule_operational_data(src_od)?;
             }
         }
 
@@ -751,7 +861,7 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             if dst_od.target == op_info.target() {
                 // Our target is the _destination_ chain, retry these messages
                 info!(odata = %dst_od.info(), "will retry");
-                return Some(dst_od);
+                return Ok(Some(dst_od));
             } else {
                 // Our target is the _source_ chain, but `dst_od` has new messages
                 // intended for the destination chain, this should never be the case
@@ -764,11 +874,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             // There is no message intended for the destination chain
             if op_info.target() == OperationalDataTarget::Destination {
                 info!("exhausted all events from this operational data");
-                return None;
+                return Ok(None);
             }
         }
 
-        None
+        Ok(None)
     }
 
     /// Sends a transaction based on the [`OperationalData`] to
@@ -1166,7 +1276,9 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             unreceived_acknowledgements(self.dst_chain(), self.src_chain(), &self.path_id)
                 .map_err(LinkError::supervisor)?;
 
-        let Some((sequences, src_response_height)) = sequences_and_height else { return Ok(()) };
+        let Some((sequences, src_response_height)) = sequences_and_height else {
+            return Ok(());
+        };
 
         let query_height = opt_query_height.unwrap_or(src_response_height);
 
@@ -1214,6 +1326,25 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
 
         trace!(packet = %packet, height = %proofs.height(), "built recv_packet msg");
 
+        let route = forward_route(packet);
+        if let Some(last_hop) = route.last() {
+            let receiver = if packet_data_redacted() {
+                "<redacted>"
+            } else {
+                last_hop.receiver.as_str()
+            };
+
+            debug!(
+                packet = %packet,
+                "packet will be forwarded by {} in {} hop(s), to {} over {}/{} on the last chain",
+                self.dst_chain().id(),
+                route.len(),
+                receiver,
+                last_hop.port,
+                last_hop.channel,
+            );
+        }
+
         Ok(Some(msg.to_any()))
     }
 
@@ -1426,13 +1557,55 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
             match elapsed_result {
                 Ok(elapsed) => {
                     if elapsed {
-                        // The current piece of operational data has elapsed; we can go ahead and
-                        // attempt to relay it.
+                        // The current piece of operational data has elapsed; if it has been
+                        // waiting for longer than a block, build its messages again so that
+                        // they carry the freshest proofs, then attempt to relay it.
+                        let mut od = match self.refresh_operational_data(od, target_chain) {
+                            Some(od) => od,
+                            None => continue,
+                        };
+
+                        match self.reschedule_if_consensus_state_missing(&mut od) {
+                            Ok(false) => {}
+                            // The connection delay starts over from the new client update
+                            Ok(true) => {
+                                unprocessed.push_back(od);
+                                continue;
+                            }
+                            Err(e) => {
+                                unprocessed.push_back(od);
+                                unprocessed.extend(operations);
+
+                                return Err((unprocessed, e));
+                            }
+                        }
+
                         match self
                             .relay_from_operational_data::<relay_sender::AsyncSender>(od.clone())
                         {
                             // The operational data was successfully relayed; enqueue the associated tx.
-                            Ok(reply) => self.enqueue_pending_tx(reply, od),
+                            Ok(reply) => {
+                                if target_chain == OperationalDataTarget::Destination {
+                                    self.watch_timeouts(&od);
+                                }
+
+                                let target_chain_id = match target_chain {
+                                    OperationalDataTarget::Source => self.src_chain().id(),
+                                    OperationalDataTarget::Destination => self.dst_chain().id(),
+                                };
+
+                                let rejection = reply.rejection(&target_chain_id);
+
+                                self.enqueue_pending_tx(reply, od);
+
+                                // The chain rejected the transaction: report it, to back off
+                                // rather than keep submitting transactions bound to be rejected.
+                                if let Some(e) = rejection {
+                                    unprocessed.extend(operations);
+
+                                    return Err((unprocessed, e));
+                                }
+                            }
                             // The relaying process failed; return all of the subsequent pieces of operational
                             // data along with the underlying error that occurred.
                             Err(e) => {
@@ -1464,6 +1637,36 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(unprocessed)
     }
 
+    /// Rebuilds the given operational data from its events if its proofs were queried
+    /// more than one block ago on the chain they originate from.
+    ///
+    /// Returns `None` if there is nothing left to submit once rebuilt, as for
+    /// [`Self::regenerate_operational_data`]. If rebuilding it fails, the operational
+    /// data is returned as is, to be submitted with its original proofs.
+    fn refresh_operational_data(
+        &self,
+        od: OperationalData,
+        target_chain: OperationalDataTarget,
+    ) -> Option<OperationalData> {
+        // The proofs for messages targeting one chain are queried on the other one
+        let max_block_time = match target_chain {
+            OperationalDataTarget::Source => self.dst_max_block_time(),
+            OperationalDataTarget::Destination => self.src_max_block_time(),
+        };
+
+        match max_block_time {
+            Ok(max_age) if od.proofs_outdated(max_age) => {
+                debug!(odata = %od.info(), "rebuilding outdated operational data");
+                rebuilt_or_original(od, |od| self.try_rebuild_operational_data(od))
+            }
+            Ok(_) => Some(od),
+            Err(e) => {
+                warn!(odata = %od.info(), "failed to check if operational data is outdated: {}", e);
+                Some(od)
+            }
+        }
+    }
+
     /// While there are pending operational data items, this function
     /// performs the relaying of packets corresponding to those
     /// operational data items to both the source and destination chains.
@@ -1500,27 +1703,31 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     /// Kicks off the process of relaying pending txs to the source and destination chains.
     ///
     /// See [`Resubmit::from_clear_interval`] for more info about the `resubmit` parameter.
-    pub fn process_pending_txs(&self, resubmit: Resubmit) -> RelaySummary {
+    ///
+    /// The pending txs of both chains are processed even if processing those of the source
+    /// chain fails, in which case the error is returned.
+    pub fn process_pending_txs(&self, resubmit: Resubmit) -> Result<RelaySummary, LinkError> {
         if !self.confirm_txes {
-            return RelaySummary::empty();
+            return Ok(RelaySummary::empty());
         }
 
-        let mut summary_src = self.process_pending_txs_src(resubmit).unwrap_or_else(|e| {
+        let summary_src = self.process_pending_txs_src(resubmit).map_err(|e| {
             error!("error processing pending events in source chain: {}", e);
-            RelaySummary::empty()
+            e
         });
 
-        let summary_dst = self.process_pending_txs_dst(resubmit).unwrap_or_else(|e| {
+        let summary_dst = self.process_pending_txs_dst(resubmit).map_err(|e| {
             error!(
                 "error processing pending events in destination chain: {}",
                 e
             );
-
-            RelaySummary::empty()
+            e
         });
 
-        summary_src.extend(summary_dst);
-        summary_src
+        let mut summary = summary_src?;
+        summary.extend(summary_dst?);
+
+        Ok(summary)
     }
 
     fn process_pending_txs_src(&self, resubmit: Resubmit) -> Result<RelaySummary, LinkError> {
@@ -1561,6 +1768,11 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
     pub fn refresh_schedule(&self) -> Result<(), LinkError> {
         let _span = span!(Level::ERROR, "refresh_schedule").entered();
 
+        // The watched packets are kept on failure, and refreshed again next time
+        if let Err(e) = self.refresh_timeout_watchlist() {
+            warn!("failed to refresh the timeout watchlist: {}", e);
+        }
+
         // Bail fast if no op. data to refresh
         if self.dst_operational_data.is_empty() {
             return Ok(());
@@ -1662,6 +1874,110 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         Ok(())
     }
 
+    /// Adds to the timeout watchlist the packets of the given operational data, just
+    /// submitted to the destination chain, which are within [`TIMEOUT_WATCH_MARGIN_BLOCKS`]
+    /// blocks (or the equivalent time) of timing out.
+    fn watch_timeouts(&self, od: &OperationalData) {
+        let send_packets = od
+            .batch
+            .iter()
+            .filter(|gm| matches!(gm.event_with_height.event, IbcEvent::SendPacket(_)))
+            .collect_vec();
+
+        if send_packets.is_empty() {
+            return;
+        }
+
+        let margin = || -> Result<(Height, Timestamp), LinkError> {
+            let dst_status = self
+                .dst_chain()
+                .query_application_status()
+                .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+            let margin_time = self.dst_max_block_time()? * TIMEOUT_WATCH_MARGIN_BLOCKS as u32;
+            let timestamp = (dst_status.timestamp + margin_time).unwrap_or(dst_status.timestamp);
+
+            Ok((dst_status.height + TIMEOUT_WATCH_MARGIN_BLOCKS, timestamp))
+        };
+
+        let (height, timestamp) = match margin() {
+            Ok(margin) => margin,
+            Err(e) => {
+                warn!("failed to compute the timeout watch margin: {}", e);
+                return;
+            }
+        };
+
+        for gm in send_packets {
+            if let IbcEvent::SendPacket(ref event) = gm.event_with_height.event {
+                if event.packet.timed_out(&timestamp, height) {
+                    debug!(packet = %event.packet, "watching packet close to its timeout");
+                    self.timeout_watchlist
+                        .push_back(gm.event_with_height.clone());
+                }
+            }
+        }
+    }
+
+    /// Schedules timeout messages for the watched packets which timed out on the destination
+    /// chain without being received, and stops watching those which were received.
+    fn refresh_timeout_watchlist(&self) -> Result<(), LinkError> {
+        if self.timeout_watchlist.is_empty() {
+            return Ok(());
+        }
+
+        let dst_status = self
+            .dst_chain()
+            .query_application_status()
+            .map_err(|e| LinkError::query(self.dst_chain().id(), e))?;
+
+        let timeouts = refresh_watchlist(&self.timeout_watchlist, |event| {
+            if self.send_packet_event_handled(event)? {
+                return Ok(WatchedPacket::Received);
+            }
+
+            match self.build_timeout_from_send_packet_event(event, &dst_status)? {
+                Some(msg) => Ok(WatchedPacket::TimedOut(msg)),
+                None => Ok(WatchedPacket::InFlight),
+            }
+        })?;
+
+        if timeouts.is_empty() {
+            return Ok(());
+        }
+
+        let mut od = OperationalData::new(
+            dst_status.height,
+            OperationalDataTarget::Source,
+            TrackingId::new_static("timeout-watchlist"),
+            self.channel.connection_delay,
+        );
+
+        for timeout in timeouts {
+            info!(
+                event = %timeout.event_with_height.event,
+                "watched packet timed out, scheduling its timeout"
+            );
+
+            od.push(timeout);
+        }
+
+        let watched = od
+            .batch
+            .iter()
+            .map(|gm| gm.event_with_height.clone())
+            .collect_vec();
+
+        self.schedule_operational_data(od).map_err(|e| {
+            // Keep watching the packets, to schedule their timeout again next time
+            for event_with_height in watched.into_iter().rev() {
+                self.timeout_watchlist.push_front(event_with_height);
+            }
+
+            e
+        })
+    }
+
     /// Adds a new operational data item for this relaying path to process later.
     /// If the relaying path has non-zero packet delays, this method also updates the client on the
     /// target chain with the appropriate headers.
@@ -1679,36 +1995,72 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         // Update clients ahead of scheduling the operational data, if the delays are non-zero.
         // If the connection-delay must be taken into account, set the `scheduled_time` to an
         // instant in the past, i.e. when this client update was first processed (`processed_time`)
-        let scheduled_time = if od.conn_delay_needed() {
+        if od.conn_delay_needed() {
             debug!("connection delay must be taken into account: updating client");
-            let target_height = od.proofs_height.increment();
-            match od.target {
-                OperationalDataTarget::Source => {
-                    let update_height = self.update_client_src(target_height, od.tracking_id)?;
-                    od.set_update_height(update_height);
-                    self.src_time_at_height(update_height)?
-                }
-                OperationalDataTarget::Destination => {
-                    let update_height = self.update_client_dst(target_height, od.tracking_id)?;
-                    od.set_update_height(update_height);
-                    self.dst_time_at_height(update_height)?
-                }
-            }
+            self.update_client_for_conn_delay(&mut od)?;
         } else {
             debug!("connection delay need not be taken into account: client update message will be prepended later");
-            Instant::now()
+            od.set_scheduled_time(Instant::now());
+        }
+
+        let queue = match od.target {
+            OperationalDataTarget::Source => &self.src_operational_data,
+            OperationalDataTarget::Destination => &self.dst_operational_data,
         };
 
-        od.set_scheduled_time(scheduled_time);
+        push_bounded(queue, od, self.max_queued_operational_data);
 
-        match od.target {
-            OperationalDataTarget::Source => self.src_operational_data.push_back(od),
-            OperationalDataTarget::Destination => self.dst_operational_data.push_back(od),
+        Ok(())
+    }
+
+    /// Updates the client on the target chain of the given operational data to the height
+    /// its proofs are verified at, and starts its connection delay from that client update.
+    fn update_client_for_conn_delay(&self, od: &mut OperationalData) -> Result<(), LinkError> {
+        let target_height = self.proof_height(od);
+
+        let scheduled_time = match od.target {
+            OperationalDataTarget::Source => {
+                let update_height = self.update_client_src(target_height, od.tracking_id)?;
+                od.set_update_height(update_height);
+                self.src_time_at_height(update_height)?
+            }
+            OperationalDataTarget::Destination => {
+                let update_height = self.update_client_dst(target_height, od.tracking_id)?;
+                od.set_update_height(update_height);
+                self.dst_time_at_height(update_height)?
+            }
         };
 
+        od.set_scheduled_time(scheduled_time);
+
         Ok(())
     }
 
+    /// Updates the client again for the given operational data, whose connection delay elapsed,
+    /// if the consensus state its proofs are verified against was pruned meanwhile.
+    ///
+    /// Returns `true` iff the client was updated, in which case the messages must only be
+    /// submitted once the connection delay elapsed again.
+    fn reschedule_if_consensus_state_missing(
+        &self,
+        od: &mut OperationalData,
+    ) -> Result<bool, LinkError> {
+        if !od.conn_delay_needed() || od.has_consensus_state_for_proofs(self)? {
+            return Ok(false);
+        }
+
+        warn!(
+            "no consensus state for the proofs of {} on the {} client, updating the client \
+            and waiting for the connection delay again",
+            od.info(),
+            od.target
+        );
+
+        self.update_client_for_conn_delay(od)?;
+
+        Ok(true)
+    }
+
     /// Pulls out the operational elements with elapsed delay period and that can
     /// now be processed.
     pub(crate) fn try_fetch_scheduled_operational_data(
@@ -1813,12 +2165,14 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
                 );
             }
             IbcEvent::WriteAcknowledgement(write_ack_ev) => {
-                ibc_telemetry::global().backlog_remove(
+                ibc_telemetry::global().packet_received(
                     write_ack_ev.packet.sequence.into(),
                     &self.dst_chain().id(),
                     self.dst_channel_id(),
                     self.dst_port_id(),
                     &self.src_chain().id(),
+                    self.src_channel_id(),
+                    self.src_port_id(),
                 );
             }
             IbcEvent::TimeoutPacket(timeout_packet) => {
@@ -1875,3 +2229,249 @@ impl<ChainA: ChainHandle, ChainB: ChainHandle> RelayPath<ChainA, ChainB> {
         }
     }
 }
+
+/// The hops over which the packet forward middleware of the chain receiving
+/// the given ICS-20 packet forwards its tokens, according to the packet memo.
+fn forward_route(packet: &Packet) -> Vec<ForwardMetadata> {
+    [Version::ics20(), Version::ics20_v2()]
+        .iter()
+        .find_map(|version| AnyPacketData::decode(version, &packet.data).ok())
+        .and_then(|data| data.memo().map(ForwardMetadata::route))
+        .unwrap_or_default()
+}
+
+/// The status of a packet in the timeout watchlist.
+enum WatchedPacket {
+    /// The packet was received by the destination chain
+    Received,
+    /// The packet timed out, with the message to time it out on the source chain
+    TimedOut(Any),
+    /// The packet is neither received nor timed out yet
+    InFlight,
+}
+
+/// Refresh the given watchlist of `SendPacket` events, returning the timeout messages for the
+/// packets which timed out, and keeping watching those which are still in flight.
+///
+/// If checking the status of a packet fails, the watchlist is left unchanged.
+fn refresh_watchlist<F>(
+    watchlist: &Queue<IbcEventWithHeight>,
+    mut check: F,
+) -> Result<Vec<TransitMessage>, LinkError>
+where
+    F: FnMut(&SendPacket) -> Result<WatchedPacket, LinkError>,
+{
+    let events = watchlist.take();
+
+    let statuses = events
+        .iter()
+        .map(|event_with_height| match &event_with_height.event {
+            IbcEvent::SendPacket(event) => check(event),
+            _ => Ok(WatchedPacket::Received),
+        })
+        .collect::<Result<Vec<_>, _>>();
+
+    let statuses = match statuses {
+        Ok(statuses) => statuses,
+        Err(e) => {
+            // Restore the watchlist, ahead of the events watched meanwhile
+            for event_with_height in events.into_iter().rev() {
+                watchlist.push_front(event_with_height);
+            }
+
+            return Err(e);
+        }
+    };
+
+    let mut timeouts = Vec::new();
+    let mut watched = VecDeque::new();
+
+    for (event_with_height, status) in events.into_iter().zip(statuses) {
+        match status {
+            WatchedPacket::Received => {}
+            WatchedPacket::TimedOut(msg) => timeouts.push(TransitMessage {
+                event_with_height,
+                msg,
+            }),
+            WatchedPacket::InFlight => watched.push_back(event_with_height),
+        }
+    }
+
+    // Keep the events watched meanwhile
+    watched.extend(watchlist.take());
+    watchlist.replace(watched);
+
+    Ok(timeouts)
+}
+
+/// The operational data to submit in place of `od` once rebuilt with the given function,
+/// or `od` itself if rebuilding it fails, so that it is not lost.
+fn rebuilt_or_original(
+    od: OperationalData,
+    rebuild: impl FnOnce(OperationalData) -> Result<Option<OperationalData>, LinkError>,
+) -> Option<OperationalData> {
+    match rebuild(od.clone()) {
+        Ok(rebuilt) => rebuilt,
+        Err(e) => {
+            warn!(
+                odata = %od.info(),
+                "failed to rebuild outdated operational data, submitting it as is: {}", e
+            );
+            Some(od)
+        }
+    }
+}
+
+/// The bound on the number of operational data queued in each direction of a channel
+/// with the given ordering, if any.
+///
+/// The packets of the operational data dropped once the bound is reached are only relayed
+/// again by packet clearing. The bound is thus not enforced when packet clearing is disabled,
+/// ie. with a clear interval of `0`, nor on ordered channels, on which a dropped packet would
+/// hold back all the subsequent ones until the next packet clearing.
+fn queue_bound(max: usize, clear_interval: u64, ordering: Ordering) -> Option<usize> {
+    (max > 0 && clear_interval > 0 && ordering != Ordering::Ordered).then_some(max)
+}
+
+/// Push the given operational data to the back of the queue, dropping the oldest
+/// operational data of the queue as long as it holds more than `max` of them.
+fn push_bounded(queue: &Queue<OperationalData>, od: OperationalData, max: Option<usize>) {
+    queue.push_back(od);
+
+    let Some(max) = max else {
+        return;
+    };
+
+    while queue.len() > max {
+        if let Some(dropped) = queue.pop_front() {
+            warn!(
+                "dropping operational data {} after reaching the maximum of {} queued \
+                operational data, its packets will be relayed by the next packet clearing",
+                dropped.info(),
+                max
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn send_packet(sequence: u64) -> IbcEventWithHeight {
+        IbcEventWithHeight::new(
+            IbcEvent::SendPacket(SendPacket {
+                packet: Packet {
+                    sequence: sequence.into(),
+                    ..Default::default()
+                },
+            }),
+            Height::new(0, 1).unwrap(),
+        )
+    }
+
+    fn od(height: u64) -> OperationalData {
+        OperationalData::new(
+            Height::new(0, height).unwrap(),
+            OperationalDataTarget::Destination,
+            TrackingId::new_uuid(),
+            Duration::ZERO,
+        )
+    }
+
+    fn sequences(watchlist: &Queue<IbcEventWithHeight>) -> Vec<u64> {
+        watchlist
+            .clone_vec()
+            .into_iter()
+            .filter_map(|e| e.event.packet().map(|p| p.sequence.into()))
+            .collect()
+    }
+
+    #[test]
+    fn refresh_watchlist_schedules_timeouts() {
+        let watchlist = Queue::from(VecDeque::from(vec![
+            send_packet(1),
+            send_packet(2),
+            send_packet(3),
+        ]));
+
+        // Packet 1 was received, packet 2 timed out, packet 3 is still in flight
+        let timeouts = refresh_watchlist(&watchlist, |event| {
+            Ok(match u64::from(event.packet.sequence) {
+                1 => WatchedPacket::Received,
+                2 => WatchedPacket::TimedOut(Any::default()),
+                _ => WatchedPacket::InFlight,
+            })
+        })
+        .unwrap();
+
+        assert_eq!(timeouts.len(), 1);
+        assert_eq!(sequences(&watchlist), vec![3]);
+    }
+
+    #[test]
+    fn refresh_watchlist_keeps_events_on_error() {
+        let watchlist = Queue::from(VecDeque::from(vec![
+            send_packet(1),
+            send_packet(2),
+            send_packet(3),
+        ]));
+
+        let result = refresh_watchlist(&watchlist, |event| {
+            if u64::from(event.packet.sequence) == 2 {
+                Err(LinkError::old_packet_clearing_failed())
+            } else {
+                Ok(WatchedPacket::TimedOut(Any::default()))
+            }
+        });
+
+        assert!(result.is_err());
+        assert_eq!(sequences(&watchlist), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn queue_bound_only_when_packets_are_cleared() {
+        assert_eq!(queue_bound(10, 100, Ordering::Unordered), Some(10));
+        assert_eq!(queue_bound(0, 100, Ordering::Unordered), None);
+
+        // Without packet clearing, the packets of dropped operational data would never be relayed
+        assert_eq!(queue_bound(10, 0, Ordering::Unordered), None);
+
+        // On ordered channels, a dropped packet would hold back the subsequent ones
+        assert_eq!(queue_bound(10, 100, Ordering::Ordered), None);
+    }
+
+    #[test]
+    fn push_bounded_drops_the_oldest() {
+        let queue = Queue::new();
+        for height in 1..=3 {
+            push_bounded(&queue, od(height), Some(2));
+        }
+
+        let heights: Vec<_> = queue
+            .clone_vec()
+            .into_iter()
+            .map(|od| od.proofs_height.revision_height())
+            .collect();
+        assert_eq!(heights, vec![2, 3]);
+
+        let queue = Queue::new();
+        for height in 1..=3 {
+            push_bounded(&queue, od(height), None);
+        }
+
+        assert_eq!(queue.len(), 3);
+    }
+
+    #[test]
+    fn keep_the_original_when_rebuilding_fails() {
+        let kept = rebuilt_or_original(od(1), |_| Err(LinkError::old_packet_clearing_failed()));
+        assert_eq!(kept.unwrap().proofs_height, Height::new(0, 1).unwrap());
+
+        let rebuilt = rebuilt_or_original(od(1), |_| Ok(Some(od(2))));
+        assert_eq!(rebuilt.unwrap().proofs_height, Height::new(0, 2).unwrap());
+
+        // Nothing left to submit once rebuilt
+        assert!(rebuilt_or_original(od(1), |_| Ok(None)).is_none());
+    }
+}
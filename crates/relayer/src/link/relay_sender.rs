@@ -3,6 +3,7 @@ use core::fmt::{Display, Error as FmtError, Formatter};
 use tendermint_rpc::endpoint::broadcast::tx_sync;
 use tracing::info;
 
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
 use ibc_relayer_types::events::IbcEvent;
 
 use crate::chain::handle::ChainHandle;
@@ -77,6 +78,22 @@ pub struct AsyncReply {
     pub responses: Vec<tx_sync::Response>,
 }
 
+impl AsyncReply {
+    /// The error reported by the chain for the first transaction it rejected, if any.
+    pub fn rejection(&self, chain_id: &ChainId) -> Option<LinkError> {
+        self.responses
+            .iter()
+            .find(|response| response.code.is_err())
+            .map(|response| {
+                LinkError::tx_rejected(
+                    chain_id.clone(),
+                    PrettyCode(&response.code).to_string(),
+                    response.log.to_string(),
+                )
+            })
+    }
+}
+
 impl SubmitReply for AsyncReply {
     fn empty() -> Self {
         Self { responses: vec![] }
@@ -0,0 +1,350 @@
+//! Assessment of what remains to be done on a channel before abandoning it
+//! in favor of a new one, eg. after a mistake during the channel handshake.
+
+use flex_error::define_error;
+use serde::Serialize;
+
+use ibc_relayer_types::core::ics03_connection::connection::IdentifiedConnectionEnd;
+use ibc_relayer_types::core::ics04_channel::channel::{IdentifiedChannelEnd, State};
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+use ibc_relayer_types::events::IbcEvent;
+
+use crate::account::Balance;
+use crate::chain::counterparty::{channel_on_destination, pending_packet_summary, PendingPackets};
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{IncludeProof, QueryChannelRequest, QueryHeight};
+use crate::channel::{Channel, ChannelError, ChannelSide};
+use crate::error::Error;
+use crate::supervisor::Error as SupervisorError;
+use crate::transfer::{escrow_address, TransferError};
+
+define_error! {
+    MigratePathError {
+        Query
+            { chain_id: ChainId }
+            [ Error ]
+            |e| { format!("error querying chain {0}", e.chain_id) },
+
+        PendingPackets
+            { chain_id: ChainId }
+            [ SupervisorError ]
+            |e| { format!("failed to compute the pending packets on chain {0}", e.chain_id) },
+
+        MissingCounterpartyChannel
+            { channel_id: ChannelId }
+            |e| { format!("channel {0} has no counterparty channel", e.channel_id) },
+
+        EscrowAddress
+            [ TransferError ]
+            |_| { "failed to derive the escrow address" },
+
+        NotReadyToClose
+            { channel_id: ChannelId }
+            |e| {
+                format!("channel {0} still has pending packets, clear or time them out before closing it",
+                    e.channel_id)
+            },
+
+        EscrowNotEmpty
+            { channel_id: ChannelId, escrow_address: String }
+            |e| {
+                format!("tokens remain escrowed in {0}, which can only be returned over channel {1}, so it must not be closed",
+                    e.escrow_address, e.channel_id)
+            },
+
+        Close
+            { channel_id: ChannelId }
+            [ ChannelError ]
+            |e| { format!("failed to close channel {0}", e.channel_id) },
+    }
+}
+
+/// Report on the state of a channel which is to be abandoned
+/// in favor of a new channel on the same port.
+#[derive(Debug, Serialize)]
+pub struct MigratePathReport {
+    pub chain_id: ChainId,
+    pub port_id: PortId,
+    pub old_channel_id: ChannelId,
+    pub old_channel_state: State,
+    pub new_channel_id: ChannelId,
+    pub new_channel_state: State,
+    /// The packets sent on the old channel from this chain
+    pub sent: PendingPackets,
+    /// The packets sent on the old channel from the counterparty chain
+    pub received: PendingPackets,
+    /// The ICS-20 escrow account of the old channel on this chain
+    pub escrow_address: String,
+    pub escrow_balances: Vec<Balance>,
+    /// The actions to take before the old channel can be abandoned
+    pub actions: Vec<String>,
+}
+
+impl MigratePathReport {
+    /// Whether there are no packets left in flight on the old channel.
+    pub fn no_packets_in_flight(&self) -> bool {
+        self.sent.unreceived_packets.is_empty()
+            && self.sent.unreceived_acks.is_empty()
+            && self.received.unreceived_packets.is_empty()
+            && self.received.unreceived_acks.is_empty()
+    }
+
+    /// Whether the old channel can be closed, ie. there are no packets left in flight
+    /// on it and no tokens left in its escrow account.
+    pub fn ready_to_close(&self) -> bool {
+        self.no_packets_in_flight() && self.escrow_balances.is_empty()
+    }
+}
+
+/// Enumerate the packets in flight on the `old_channel` of `chain`, in both directions,
+/// along with the tokens held in its ICS-20 escrow account, and report what must be
+/// cleared or timed out before the channel can be abandoned for `new_channel_id`.
+pub fn migrate_path_report<Chain: ChainHandle, Counterparty: ChainHandle>(
+    chain: &Chain,
+    counterparty_chain: &Counterparty,
+    old_channel: &IdentifiedChannelEnd,
+    connection: &IdentifiedConnectionEnd,
+    new_channel_id: &ChannelId,
+) -> Result<MigratePathReport, MigratePathError> {
+    let (new_channel, _) = chain
+        .query_channel(
+            QueryChannelRequest {
+                port_id: old_channel.port_id.clone(),
+                channel_id: new_channel_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(|e| MigratePathError::query(chain.id(), e))?;
+
+    let sent = pending_packet_summary(chain, counterparty_chain, old_channel)
+        .map_err(|e| MigratePathError::pending_packets(chain.id(), e))?;
+
+    let counterparty_channel = channel_on_destination(old_channel, connection, counterparty_chain)
+        .map_err(|e| MigratePathError::pending_packets(counterparty_chain.id(), e))?
+        .ok_or_else(|| {
+            MigratePathError::missing_counterparty_channel(old_channel.channel_id.clone())
+        })?;
+
+    let received = pending_packet_summary(counterparty_chain, chain, &counterparty_channel)
+        .map_err(|e| MigratePathError::pending_packets(counterparty_chain.id(), e))?;
+
+    let chain_config = chain
+        .config()
+        .map_err(|e| MigratePathError::query(chain.id(), e))?;
+
+    let escrow_address = escrow_address(
+        &chain_config.account_prefix,
+        &old_channel.port_id,
+        &old_channel.channel_id,
+    )
+    .map_err(MigratePathError::escrow_address)?;
+
+    let escrow_balances = chain
+        .query_address_balances(escrow_address.clone())
+        .map_err(|e| MigratePathError::query(chain.id(), e))?;
+
+    let mut report = MigratePathReport {
+        chain_id: chain.id(),
+        port_id: old_channel.port_id.clone(),
+        old_channel_id: old_channel.channel_id.clone(),
+        old_channel_state: *old_channel.channel_end.state(),
+        new_channel_id: new_channel_id.clone(),
+        new_channel_state: *new_channel.state(),
+        sent,
+        received,
+        escrow_address,
+        escrow_balances,
+        actions: vec![],
+    };
+
+    report.actions = actions(&report);
+
+    Ok(report)
+}
+
+fn actions(report: &MigratePathReport) -> Vec<String> {
+    let mut actions = vec![];
+
+    if !report.new_channel_state.is_open() {
+        actions.push(format!(
+            "complete the handshake of the new channel {}, which is in state {}",
+            report.new_channel_id, report.new_channel_state
+        ));
+    }
+
+    if !report.sent.unreceived_packets.is_empty() {
+        actions.push(format!(
+            "relay or time out the {} packet(s) sent from {} on {} and not yet received",
+            report.sent.unreceived_packets.len(),
+            report.chain_id,
+            report.old_channel_id
+        ));
+    }
+
+    if !report.sent.unreceived_acks.is_empty() {
+        actions.push(format!(
+            "relay the acknowledgements of the {} packet(s) sent from {} on {}",
+            report.sent.unreceived_acks.len(),
+            report.chain_id,
+            report.old_channel_id
+        ));
+    }
+
+    if !report.received.unreceived_packets.is_empty() || !report.received.unreceived_acks.is_empty()
+    {
+        actions.push(format!(
+            "clear the {} packet(s) and {} acknowledgement(s) pending from the counterparty of {}",
+            report.received.unreceived_packets.len(),
+            report.received.unreceived_acks.len(),
+            report.old_channel_id
+        ));
+    }
+
+    if !report.escrow_balances.is_empty() {
+        actions.push(format!(
+            "tokens remain escrowed in {}: they can only be returned over {}, so it must not be closed while they are in circulation",
+            report.escrow_address, report.old_channel_id
+        ));
+    }
+
+    if report.ready_to_close() && report.old_channel_state.is_open() {
+        actions.push(format!(
+            "no packets are in flight and no tokens are escrowed, channel {} can be closed",
+            report.old_channel_id
+        ));
+    }
+
+    actions
+}
+
+/// Initiate the closing handshake of the `old_channel` on `chain`, provided that
+/// no packets are in flight on it and no tokens are escrowed according to `report`.
+pub fn close_old_channel<Chain: ChainHandle, Counterparty: ChainHandle>(
+    chain: Chain,
+    counterparty_chain: Counterparty,
+    old_channel: &IdentifiedChannelEnd,
+    connection: &IdentifiedConnectionEnd,
+    report: &MigratePathReport,
+) -> Result<IbcEvent, MigratePathError> {
+    if !report.no_packets_in_flight() {
+        return Err(MigratePathError::not_ready_to_close(
+            old_channel.channel_id.clone(),
+        ));
+    }
+
+    if !report.escrow_balances.is_empty() {
+        return Err(MigratePathError::escrow_not_empty(
+            old_channel.channel_id.clone(),
+            report.escrow_address.clone(),
+        ));
+    }
+
+    let counterparty = old_channel.channel_end.counterparty();
+
+    // The closing handshake is initiated on the destination side of the channel
+    let channel = Channel {
+        connection_delay: Default::default(),
+        ordering: *old_channel.channel_end.ordering(),
+        a_side: ChannelSide::new(
+            counterparty_chain,
+            connection.connection_end.counterparty().client_id().clone(),
+            connection
+                .connection_end
+                .counterparty()
+                .connection_id()
+                .cloned()
+                .unwrap_or_default(),
+            counterparty.port_id.clone(),
+            counterparty.channel_id.clone(),
+            None,
+        ),
+        b_side: ChannelSide::new(
+            chain,
+            connection.connection_end.client_id().clone(),
+            connection.connection_id.clone(),
+            old_channel.port_id.clone(),
+            Some(old_channel.channel_id.clone()),
+            None,
+        ),
+    };
+
+    channel
+        .build_chan_close_init_and_send()
+        .map_err(|e| MigratePathError::close(old_channel.channel_id.clone(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> MigratePathReport {
+        MigratePathReport {
+            chain_id: ChainId::from_string("ibc-0"),
+            port_id: PortId::transfer(),
+            old_channel_id: ChannelId::new(0),
+            old_channel_state: State::Open,
+            new_channel_id: ChannelId::new(1),
+            new_channel_state: State::Open,
+            sent: PendingPackets {
+                unreceived_packets: vec![],
+                unreceived_acks: vec![],
+            },
+            received: PendingPackets {
+                unreceived_packets: vec![],
+                unreceived_acks: vec![],
+            },
+            escrow_address: "cosmos1escrow".to_string(),
+            escrow_balances: vec![],
+            actions: vec![],
+        }
+    }
+
+    #[test]
+    fn ready_to_close_without_packets_nor_escrowed_tokens() {
+        let report = report();
+
+        assert!(report.ready_to_close());
+        assert_eq!(
+            actions(&report),
+            vec!["no packets are in flight and no tokens are escrowed, channel channel-0 can be closed"]
+        );
+    }
+
+    #[test]
+    fn not_ready_to_close_with_packets_in_flight() {
+        let mut report = report();
+        report.sent.unreceived_packets = vec![1.into(), 2.into()];
+        report.received.unreceived_acks = vec![3.into()];
+
+        assert!(!report.no_packets_in_flight());
+        assert!(!report.ready_to_close());
+        assert_eq!(
+            actions(&report),
+            vec![
+                "relay or time out the 2 packet(s) sent from ibc-0 on channel-0 and not yet received",
+                "clear the 0 packet(s) and 1 acknowledgement(s) pending from the counterparty of channel-0",
+            ]
+        );
+    }
+
+    #[test]
+    fn not_ready_to_close_with_escrowed_tokens() {
+        let mut report = report();
+        report.new_channel_state = State::TryOpen;
+        report.escrow_balances = vec![Balance {
+            amount: "100".to_string(),
+            denom: "stake".to_string(),
+        }];
+
+        assert!(report.no_packets_in_flight());
+        assert!(!report.ready_to_close());
+        assert_eq!(
+            actions(&report),
+            vec![
+                "complete the handshake of the new channel channel-1, which is in state TRYOPEN",
+                "tokens remain escrowed in cosmos1escrow: they can only be returned over channel-0, so it must not be closed while they are in circulation",
+            ]
+        );
+    }
+}
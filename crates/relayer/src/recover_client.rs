@@ -0,0 +1,170 @@
+//! Governance proposals for recovering an expired or frozen client
+//! by substituting it with a healthy client tracking the same chain.
+
+use flex_error::define_error;
+
+use tendermint::Hash as TxHash;
+
+use ibc_proto::cosmos::gov::v1beta1::MsgSubmitProposal;
+use ibc_proto::google::protobuf::Any;
+use ibc_proto::ibc::core::client::v1::ClientUpdateProposal;
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{IncludeProof, QueryClientStateRequest, QueryHeight};
+use crate::chain::tracking::TrackedMsgs;
+use crate::client_state::AnyClientState;
+use crate::error::Error;
+
+define_error! {
+    RecoverClientError {
+        Query
+            [ Error ]
+            |_| { "error during a query" },
+
+        Key
+            [ Error ]
+            |_| { "key error" },
+
+        Submit
+            { chain_id: ChainId }
+            [ Error ]
+            |e| {
+                format!("failed while submitting the client recovery proposal to chain {0}", e.chain_id)
+            },
+
+        SameClient
+            { client_id: ClientId }
+            |e| {
+                format!("subject and substitute clients must be different, both are {0}", e.client_id)
+            },
+
+        SubstituteFrozen
+            { client_id: ClientId }
+            |e| {
+                format!("substitute client {0} is frozen", e.client_id)
+            },
+
+        ChainIdMismatch
+            {
+                subject_chain_id: ChainId,
+                substitute_chain_id: ChainId,
+            }
+            |e| {
+                format!("subject client tracks chain {0} but substitute client tracks chain {1}",
+                    e.subject_chain_id, e.substitute_chain_id)
+            },
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RecoverClientOptions {
+    /// The expired or frozen client to recover
+    pub subject_client_id: ClientId,
+    /// The healthy client whose state is copied over the subject client
+    pub substitute_client_id: ClientId,
+    pub amount: u64,
+    pub denom: String,
+    pub title: String,
+    pub description: String,
+}
+
+fn query_client_state(
+    host_chain: &impl ChainHandle,
+    client_id: &ClientId,
+) -> Result<AnyClientState, RecoverClientError> {
+    let (client_state, _) = host_chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(RecoverClientError::query)?;
+
+    Ok(client_state)
+}
+
+/// Build the `ClientUpdateProposal` recovering the subject client with the
+/// substitute client, both hosted on `host_chain`, wrapped in a `MsgSubmitProposal`.
+///
+/// Fails early if the substitute client cannot be used for the recovery,
+/// so that no deposit is spent on a proposal which would fail on execution.
+pub fn build_recover_client_proposal(
+    host_chain: &impl ChainHandle,
+    opts: &RecoverClientOptions,
+) -> Result<Any, RecoverClientError> {
+    if opts.subject_client_id == opts.substitute_client_id {
+        return Err(RecoverClientError::same_client(
+            opts.subject_client_id.clone(),
+        ));
+    }
+
+    let subject = query_client_state(host_chain, &opts.subject_client_id)?;
+    let substitute = query_client_state(host_chain, &opts.substitute_client_id)?;
+
+    if substitute.is_frozen() {
+        return Err(RecoverClientError::substitute_frozen(
+            opts.substitute_client_id.clone(),
+        ));
+    }
+
+    if subject.chain_id() != substitute.chain_id() {
+        return Err(RecoverClientError::chain_id_mismatch(
+            subject.chain_id(),
+            substitute.chain_id(),
+        ));
+    }
+
+    let proposal = ClientUpdateProposal {
+        title: opts.title.clone(),
+        description: opts.description.clone(),
+        subject_client_id: opts.subject_client_id.to_string(),
+        substitute_client_id: opts.substitute_client_id.to_string(),
+    };
+
+    let mut buf_proposal = Vec::new();
+    prost::Message::encode(&proposal, &mut buf_proposal).unwrap();
+    let any_proposal = Any {
+        type_url: "/ibc.core.client.v1.ClientUpdateProposal".to_string(),
+        value: buf_proposal,
+    };
+
+    let proposer = host_chain.get_signer().map_err(RecoverClientError::key)?;
+
+    let coins = ibc_proto::cosmos::base::v1beta1::Coin {
+        denom: opts.denom.clone(),
+        amount: opts.amount.to_string(),
+    };
+
+    let msg = MsgSubmitProposal {
+        content: Some(any_proposal),
+        initial_deposit: vec![coins],
+        proposer: proposer.to_string(),
+    };
+
+    let mut buf_msg = Vec::new();
+    prost::Message::encode(&msg, &mut buf_msg).unwrap();
+
+    Ok(Any {
+        type_url: "/cosmos.gov.v1beta1.MsgSubmitProposal".to_string(),
+        value: buf_msg,
+    })
+}
+
+pub fn build_and_send_recover_client_proposal(
+    host_chain: impl ChainHandle, // the chain hosting both the subject and substitute clients
+    opts: &RecoverClientOptions,
+) -> Result<TxHash, RecoverClientError> {
+    let any_msg = build_recover_client_proposal(&host_chain, opts)?;
+
+    // As for upgrade proposals, no IBC event is emitted when submitting
+    // the proposal, so we can only wait for the transaction to pass CheckTx.
+    let responses = host_chain
+        .send_messages_and_wait_check_tx(TrackedMsgs::new_single(any_msg, "recover-client"))
+        .map_err(|e| RecoverClientError::submit(host_chain.id(), e))?;
+
+    Ok(responses[0].hash)
+}
@@ -34,10 +34,16 @@ pub struct SharedRegistry<Chain: ChainHandle> {
 impl<Chain: ChainHandle> Registry<Chain> {
     /// Construct a new [`Registry`] using the provided [`Config`]
     pub fn new(config: Config) -> Self {
+        Self::with_runtime(config, Arc::new(TokioRuntime::new().unwrap()))
+    }
+
+    /// Construct a new [`Registry`] using the provided [`Config`],
+    /// spawning the chain runtimes on the given Tokio runtime.
+    pub fn with_runtime(config: Config, rt: Arc<TokioRuntime>) -> Self {
         Self {
             config,
             handles: HashMap::new(),
-            rt: Arc::new(TokioRuntime::new().unwrap()),
+            rt,
         }
     }
 
@@ -100,6 +106,14 @@ impl<Chain: ChainHandle> SharedRegistry<Chain> {
         }
     }
 
+    pub fn with_runtime(config: Config, rt: Arc<TokioRuntime>) -> Self {
+        let registry = Registry::with_runtime(config, rt);
+
+        Self {
+            registry: Arc::new(RwLock::new(registry)),
+        }
+    }
+
     pub fn get_or_spawn(&self, chain_id: &ChainId) -> Result<Chain, SpawnError> {
         self.registry.write().unwrap().get_or_spawn(chain_id)
     }
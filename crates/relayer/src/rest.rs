@@ -31,6 +31,9 @@ pub type Receiver = crossbeam_channel::Receiver<Request>;
 //  e.g., adjusting chain config, removing chains, etc.
 pub enum Command {
     DumpState(ReplySender<SupervisorState>),
+    ReprocessDeadLetters(ReplySender<usize>),
+    ResumeWorkers(ReplySender<usize>),
+    Health(ReplySender<()>),
 }
 
 /// Process incoming REST requests.
@@ -82,6 +85,24 @@ pub fn process_incoming_requests(config: &Config, channel: &Receiver) -> Option<
 
                 return Some(Command::DumpState(reply_to));
             }
+
+            Request::ReprocessDeadLetters { reply_to } => {
+                trace!("ReprocessDeadLetters");
+
+                return Some(Command::ReprocessDeadLetters(reply_to));
+            }
+
+            Request::ResumeWorkers { reply_to } => {
+                trace!("ResumeWorkers");
+
+                return Some(Command::ResumeWorkers(reply_to));
+            }
+
+            Request::Health { reply_to } => {
+                trace!("Health");
+
+                return Some(Command::Health(reply_to));
+            }
         },
         Err(e) => {
             if !matches!(e, TryRecvError::Empty) {
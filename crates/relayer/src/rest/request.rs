@@ -36,4 +36,16 @@ pub enum Request {
         chain_id: ChainId,
         reply_to: ReplySender<ChainConfig>,
     },
+
+    ReprocessDeadLetters {
+        reply_to: ReplySender<usize>,
+    },
+
+    ResumeWorkers {
+        reply_to: ReplySender<usize>,
+    },
+
+    Health {
+        reply_to: ReplySender<()>,
+    },
 }
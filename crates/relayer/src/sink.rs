@@ -0,0 +1,300 @@
+//! Publication of the events processed by the relayer to an external message bus.
+//!
+//! The sink is initialized once by the supervisor from the `[event_sink]` section
+//! of the configuration. The messages are queued and published from a background
+//! thread, so that publishing never blocks the relayer. When the sink is not
+//! initialized, publishing is a no-op.
+//!
+//! Only NATS is supported, through the [`async_nats`] client, which requires Hermes
+//! to be built with the `nats` feature. The messages are always encoded as JSON.
+
+use std::thread;
+
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use flex_error::{define_error, TraceError};
+use once_cell::sync::OnceCell;
+use serde::Serialize;
+use tracing::{error, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height;
+
+use crate::chain::tracking::TrackingId;
+use crate::config::{EventSinkConfig, EventSinkKind};
+use crate::event::monitor::EventBatch;
+use crate::event::IbcEventWithHeight;
+
+define_error! {
+    SinkError {
+        Encode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to encode message for the event sink" },
+
+        Nats
+            { reason: String }
+            |e| { format!("failed to publish to the NATS server: {}", e.reason) },
+
+        Stopped
+            |_| { "event sink publisher has stopped" },
+
+        QueueFull
+            |_| { "too many messages awaiting publication to the event sink" },
+    }
+}
+
+/// A message published to the event sink.
+#[derive(Debug, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SinkMessage<'a> {
+    /// A batch of events received from a chain's event source
+    EventBatch {
+        chain_id: &'a ChainId,
+        tracking_id: String,
+        height: Height,
+        events: &'a [IbcEventWithHeight],
+    },
+    /// The events resulting from a transaction submitted by the relayer
+    TxResult {
+        chain_id: &'a ChainId,
+        tracking_id: String,
+        events: &'a [IbcEventWithHeight],
+    },
+}
+
+/// A destination for the messages published by the relayer.
+pub trait EventSink: Send {
+    fn publish(&mut self, payload: &[u8]) -> Result<(), SinkError>;
+}
+
+/// Maximum number of messages awaiting publication.
+const QUEUE_CAPACITY: usize = 1000;
+
+#[cfg(feature = "nats")]
+pub use nats::NatsSink;
+
+#[cfg(feature = "nats")]
+mod nats {
+    use std::time::Duration;
+
+    use async_nats::{Client, ConnectOptions};
+    use tokio::runtime::Runtime as TokioRuntime;
+    use tracing::debug;
+
+    use super::{EventSink, SinkError};
+    use crate::config::EventSinkConfig;
+
+    const NATS_TIMEOUT: Duration = Duration::from_secs(5);
+
+    /// Publish messages to a subject of a NATS server.
+    ///
+    /// The connection is established on the first publication. Once connected,
+    /// the client reconnects by itself and buffers the messages published meanwhile.
+    pub struct NatsSink {
+        config: EventSinkConfig,
+        rt: TokioRuntime,
+        client: Option<Client>,
+    }
+
+    impl NatsSink {
+        pub fn new(config: EventSinkConfig) -> Result<Self, SinkError> {
+            let rt = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .map_err(|e| SinkError::nats(e.to_string()))?;
+
+            Ok(Self {
+                config,
+                rt,
+                client: None,
+            })
+        }
+
+        fn connect_options(&self) -> ConnectOptions {
+            let mut options = ConnectOptions::new()
+                .connection_timeout(NATS_TIMEOUT)
+                .require_tls(self.config.tls_required);
+
+            if let (Some(user), Some(password)) = (&self.config.user, &self.config.password) {
+                options = options.user_and_password(user.clone(), password.clone());
+            }
+
+            if let Some(token) = &self.config.token {
+                options = options.token(token.clone());
+            }
+
+            if let Some(certificate) = &self.config.tls_root_certificate {
+                options = options.add_root_certificates(certificate.clone());
+            }
+
+            options
+        }
+
+        fn client(&mut self) -> Result<Client, SinkError> {
+            if let Some(client) = &self.client {
+                return Ok(client.clone());
+            }
+
+            let client = self
+                .rt
+                .block_on(self.connect_options().connect(self.config.address.as_str()))
+                .map_err(|e| SinkError::nats(e.to_string()))?;
+
+            debug!("connected to NATS server at {}", self.config.address);
+
+            Ok(self.client.insert(client).clone())
+        }
+    }
+
+    impl EventSink for NatsSink {
+        fn publish(&mut self, payload: &[u8]) -> Result<(), SinkError> {
+            let client = self.client()?;
+            let subject = self.config.subject.clone();
+
+            self.rt.block_on(async {
+                client
+                    .publish(subject, payload.to_vec().into())
+                    .await
+                    .map_err(|e| SinkError::nats(e.to_string()))?;
+
+                client
+                    .flush()
+                    .await
+                    .map_err(|e| SinkError::nats(e.to_string()))
+            })
+        }
+    }
+}
+
+/// The queue of encoded messages awaiting publication.
+struct Publisher {
+    sender: Sender<Vec<u8>>,
+}
+
+impl Publisher {
+    /// Spawn the thread publishing the queued messages to the given sink.
+    fn spawn(sink: Box<dyn EventSink>) -> Self {
+        let (sender, receiver) = crossbeam_channel::bounded(QUEUE_CAPACITY);
+
+        thread::spawn(move || publish_messages(sink, receiver));
+
+        Self { sender }
+    }
+
+    /// Queue a message for publication, without blocking.
+    fn enqueue(&self, payload: Vec<u8>) -> Result<(), SinkError> {
+        match self.sender.try_send(payload) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => Err(SinkError::queue_full()),
+            Err(TrySendError::Disconnected(_)) => Err(SinkError::stopped()),
+        }
+    }
+}
+
+fn publish_messages(mut sink: Box<dyn EventSink>, receiver: Receiver<Vec<u8>>) {
+    for payload in receiver {
+        if let Err(e) = sink.publish(&payload) {
+            error!("failed to publish to the event sink: {}", e);
+        }
+    }
+}
+
+static SINK: OnceCell<Publisher> = OnceCell::new();
+
+/// Initialize the global event sink from the given configuration,
+/// and spawn the thread publishing to it.
+///
+/// Does nothing if the sink is disabled or was already initialized.
+pub fn init(config: &EventSinkConfig) {
+    if !config.enabled || SINK.get().is_some() {
+        return;
+    }
+
+    let sink = match config.kind {
+        EventSinkKind::Nats => nats_sink(config),
+    };
+
+    match sink {
+        Ok(sink) => {
+            let _ = SINK.set(Publisher::spawn(sink));
+        }
+        Err(e) => error!("failed to initialize the event sink: {}", e),
+    }
+}
+
+#[cfg(feature = "nats")]
+fn nats_sink(config: &EventSinkConfig) -> Result<Box<dyn EventSink>, SinkError> {
+    Ok(Box::new(NatsSink::new(config.clone())?))
+}
+
+#[cfg(not(feature = "nats"))]
+fn nats_sink(_config: &EventSinkConfig) -> Result<Box<dyn EventSink>, SinkError> {
+    Err(SinkError::nats(
+        "Hermes was built without the `nats` feature".to_string(),
+    ))
+}
+
+/// Publish a batch of events received from a chain.
+pub fn publish_batch(batch: &EventBatch) {
+    publish(|| SinkMessage::EventBatch {
+        chain_id: &batch.chain_id,
+        tracking_id: batch.tracking_id.to_string(),
+        height: batch.height,
+        events: &batch.events,
+    });
+}
+
+/// Publish the events resulting from a transaction submitted to a chain.
+pub fn publish_tx_result(
+    chain_id: &ChainId,
+    tracking_id: TrackingId,
+    events: &[IbcEventWithHeight],
+) {
+    publish(|| SinkMessage::TxResult {
+        chain_id,
+        tracking_id: tracking_id.to_string(),
+        events,
+    });
+}
+
+fn publish<'a>(message: impl FnOnce() -> SinkMessage<'a>) {
+    let sink = match SINK.get() {
+        Some(sink) => sink,
+        None => return,
+    };
+
+    let result = serde_json::to_vec(&message())
+        .map_err(SinkError::encode)
+        .and_then(|payload| sink.enqueue(payload));
+
+    if let Err(e) = result {
+        warn!("dropping message for the event sink: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn drop_messages_when_queue_is_full() {
+        let (sender, _receiver) = crossbeam_channel::bounded(1);
+        let publisher = Publisher { sender };
+
+        assert!(publisher.enqueue(b"first".to_vec()).is_ok());
+        assert!(matches!(
+            publisher.enqueue(b"second".to_vec()).unwrap_err().detail(),
+            SinkErrorDetail::QueueFull(_)
+        ));
+    }
+
+    #[test]
+    #[cfg(not(feature = "nats"))]
+    fn nats_requires_the_nats_feature() {
+        let config = EventSinkConfig {
+            enabled: true,
+            ..EventSinkConfig::default()
+        };
+
+        assert!(nats_sink(&config).is_err());
+    }
+}
@@ -4,14 +4,16 @@ use core::convert::Infallible;
 use core::ops::Deref;
 use core::time::Duration;
 use std::sync::RwLock;
+use std::thread;
 
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use itertools::Itertools;
 use tracing::{debug, error, error_span, info, instrument, trace, warn};
 
 use ibc_relayer_types::{
-    core::ics24_host::identifier::{ChainId, ChannelId, PortId},
+    core::ics24_host::identifier::{ChainId, ChannelId, ClientId, PortId},
     events::IbcEvent,
+    utils::redact::set_packet_data_redaction,
     Height,
 };
 
@@ -22,21 +24,36 @@ use crate::{
         monitor::{self, Error as EventError, ErrorDetail as EventErrorDetail, EventBatch},
         IbcEventWithHeight,
     },
-    object::Object,
+    foreign_client::ForeignClient,
+    object::{Client, Object},
     registry::{Registry, SharedRegistry},
-    rest,
+    rest, sink,
     supervisor::scan::ScanMode,
     telemetry,
     util::{
-        lock::LockExt,
-        task::{spawn_background_task, Next, TaskError, TaskHandle},
+        lock::{LockExt, RwArc},
+        retry::{retry_with_index, RetryResult},
+        task::{spawn_background_task, Next, TaskError, TaskHandle, TaskStatus},
     },
+    webhook,
     worker::WorkerMap,
 };
 
+pub mod batch_retry;
+use batch_retry::BatchRetryQueue;
+
+pub mod checkpoint;
+use checkpoint::Checkpoints;
+
+pub mod channel_resolver;
+use channel_resolver::ChannelResolver;
+
 pub mod client_state_filter;
 use client_state_filter::{FilterPolicy, Permission};
 
+pub mod ordering;
+use ordering::SequenceOrdering;
+
 pub mod error;
 pub use error::{Error, ErrorDetail};
 
@@ -49,10 +66,18 @@ pub mod spawn;
 pub mod cmd;
 use cmd::SupervisorCmd;
 
-use self::{scan::ChainScanner, spawn::SpawnContext};
+use self::{
+    scan::{ChainScanner, ChainsScan},
+    spawn::SpawnContext,
+};
 
 type ArcBatch = Arc<monitor::Result<EventBatch>>;
 type Subscription = Receiver<ArcBatch>;
+type RetryQueues = RwArc<HashMap<ChainId, BatchRetryQueue>>;
+
+/// The height to which each client, identified by its host chain and its identifier,
+/// is being or was upgraded following a change of revision of the chain it tracks.
+type ClientUpgrades = RwArc<HashMap<(ChainId, ClientId), Height>>;
 
 /**
     A wrapper around the SupervisorCmd sender so that we can
@@ -75,6 +100,9 @@ pub struct SupervisorOptions {
     /// even when an allow list is configured for a chain and the full scan could
     /// be omitted.
     pub force_full_scan: bool,
+
+    /// Periodically report the amount of data held in memory by the packet workers
+    pub profile_memory: bool,
 }
 
 /**
@@ -119,6 +147,21 @@ impl SupervisorHandle {
         }
     }
 
+    /// Whether all the tasks of the supervisor are still running.
+    ///
+    /// The tasks of the supervisor are meant to run forever, so if one of them stopped,
+    /// eg. because it panicked, some events are no longer processed.
+    pub fn is_alive(&self) -> bool {
+        !self.tasks.iter().any(TaskHandle::is_stopped)
+    }
+
+    /// Block until one of the tasks of the supervisor stops.
+    pub fn wait_until_failed(&self) {
+        while self.is_alive() {
+            thread::sleep(Duration::from_secs(1));
+        }
+    }
+
     /// Ask the supervisor to dump its internal state
     pub fn dump_state(&self) -> Result<SupervisorState, Error> {
         let (tx, rx) = crossbeam_channel::bounded(1);
@@ -131,6 +174,34 @@ impl SupervisorHandle {
 
         Ok(state)
     }
+
+    /// Ask the supervisor to retry the event batches which were moved to the
+    /// dead letters, returning the number of batches scheduled for a retry.
+    pub fn reprocess_dead_letters(&self) -> Result<usize, Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(SupervisorCmd::ReprocessDeadLetters(tx))
+            .map_err(|_| Error::handle_send())?;
+
+        let count = rx.recv().map_err(|_| Error::handle_recv())?;
+
+        Ok(count)
+    }
+
+    /// Ask the supervisor to resume the workers suspended by their circuit breaker,
+    /// returning the number of workers which were resumed.
+    pub fn resume_workers(&self) -> Result<usize, Error> {
+        let (tx, rx) = crossbeam_channel::bounded(1);
+
+        self.sender
+            .send(SupervisorCmd::ResumeWorkers(tx))
+            .map_err(|_| Error::handle_send())?;
+
+        let count = rx.recv().map_err(|_| Error::handle_recv())?;
+
+        Ok(count)
+    }
 }
 
 pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
@@ -144,6 +215,10 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         health_check(&config, &mut registry.write());
     }
 
+    // The workers of the chains whose node is still syncing are only
+    // spawned once it has caught up, see `spawn_workers_once_synced`.
+    let syncing = syncing_chains(chain_handles(&config, &registry));
+
     // If telemetry is enabled, for each chain register the relayer's address
     // in the list of visible fee addresses.
     if config.telemetry.enabled {
@@ -154,43 +229,86 @@ pub fn spawn_supervisor_tasks<Chain: ChainHandle>(
         }
     }
 
+    set_packet_data_redaction(config.global.redact_packet_data);
+    sink::init(&config.event_sink);
+    webhook::init(&config.webhooks);
+
     let workers = Arc::new(RwLock::new(WorkerMap::new()));
     let client_state_filter = Arc::new(RwLock::new(FilterPolicy::default()));
 
-    let scan = chain_scanner(
-        &config,
-        &mut registry.write(),
-        &mut client_state_filter.acquire_write(),
-        if options.force_full_scan {
-            ScanMode::Full
-        } else {
-            ScanMode::Auto
-        },
-    )
-    .scan_chains();
+    let scan_mode = if options.force_full_scan {
+        ScanMode::Full
+    } else {
+        ScanMode::Auto
+    };
+
+    let scan = {
+        let mut registry = registry.write();
+        let mut client_state_filter = client_state_filter.acquire_write();
+        let mut scanner =
+            chain_scanner(&config, &mut registry, &mut client_state_filter, scan_mode);
+
+        ChainsScan {
+            chains: config
+                .chains
+                .iter()
+                .filter(|chain_config| !syncing.iter().any(|chain| chain.id() == chain_config.id))
+                .map(|chain_config| scanner.scan_chain(chain_config))
+                .collect(),
+        }
+    };
 
     info!("scanned chains:");
     info!("{}", scan);
 
     spawn_context(&config, &mut registry.write(), &mut workers.acquire_write()).spawn_workers(scan);
 
+    for chain in syncing {
+        spawn_workers_once_synced(
+            config.clone(),
+            registry.clone(),
+            client_state_filter.clone(),
+            workers.clone(),
+            chain,
+            scan_mode,
+        );
+    }
+
     let subscriptions = init_subscriptions(&config, &mut registry.write())?;
 
+    let checkpoints = load_checkpoints(&config);
+
+    let retry_queues = Arc::new(RwLock::new(HashMap::new()));
+
     let batch_tasks = spawn_batch_workers(
         &config,
         registry.clone(),
         client_state_filter,
         workers.clone(),
         subscriptions,
+        checkpoints,
+        retry_queues.clone(),
     );
 
-    let cmd_task = spawn_cmd_worker(registry.clone(), workers.clone(), cmd_rx);
+    let cmd_task = spawn_cmd_worker(
+        registry.clone(),
+        workers.clone(),
+        retry_queues.clone(),
+        cmd_rx,
+    );
 
     let mut tasks = vec![cmd_task];
     tasks.extend(batch_tasks);
 
+    if options.profile_memory {
+        let profile_task = spawn_memory_profile_worker(registry.clone(), workers.clone());
+        tasks.push(profile_task);
+    }
+
     if let Some(rest_rx) = rest_rx {
-        let rest_task = spawn_rest_worker(config, registry, workers, rest_rx);
+        let statuses = tasks.iter().map(TaskHandle::status).collect();
+        let rest_task =
+            spawn_rest_worker(config, registry, workers, retry_queues, statuses, rest_rx);
         tasks.push(rest_task);
     }
 
@@ -203,30 +321,63 @@ fn spawn_batch_workers<Chain: ChainHandle>(
     client_state_filter: Arc<RwLock<FilterPolicy>>,
     workers: Arc<RwLock<WorkerMap>>,
     subscriptions: Vec<(Chain, Subscription)>,
+    checkpoints: Option<RwArc<Checkpoints>>,
+    retry_queues: RetryQueues,
 ) -> Vec<TaskHandle> {
     let mut handles = Vec::with_capacity(subscriptions.len());
+    let client_upgrades = RwArc::new_lock(HashMap::new());
 
     for (chain, subscription) in subscriptions {
-        let config = config.clone();
-        let registry = registry.clone();
-        let client_state_filter = client_state_filter.clone();
-        let workers = workers.clone();
+        let mut worker = BatchWorker {
+            sequence_ordering: config
+                .mode
+                .packets
+                .sequence_ordering
+                .then(|| SequenceOrdering::new(config.mode.packets.sequence_gap_tolerance)),
+            config: config.clone(),
+            registry: registry.clone(),
+            client_state_filter: client_state_filter.clone(),
+            workers: workers.clone(),
+            checkpoints: checkpoints.clone(),
+            retry_queues: retry_queues.clone(),
+            client_upgrades: client_upgrades.clone(),
+            channel_resolver: ChannelResolver::new(),
+            last_block_height: None,
+            chain: chain.clone(),
+        };
+        let mut caught_up = false;
+        let mut caught_up_to = None;
 
         let handle = spawn_background_task(
             error_span!("worker.batch", chain = %chain.id()),
             Some(Duration::from_millis(5)),
             move || -> Result<Next, TaskError<Infallible>> {
+                // Before handling the events received live, which are buffered
+                // by the subscription meanwhile, process the blocks committed
+                // since the last checkpoint of the chain.
+                if !caught_up {
+                    caught_up = true;
+
+                    let last = worker
+                        .checkpoints
+                        .as_ref()
+                        .and_then(|checkpoints| checkpoints.acquire_read().get(&chain.id()));
+
+                    if let Some(last) = last {
+                        let max_blocks = worker.config.checkpoint.max_catch_up_blocks;
+                        caught_up_to =
+                            catch_up(&chain, last, max_blocks, |batch| worker.handle(batch));
+                    }
+                }
+
                 if let Ok(batch) = subscription.try_recv() {
-                    handle_batch(
-                        &config,
-                        &mut registry.write(),
-                        &mut client_state_filter.acquire_write(),
-                        &mut workers.acquire_write(),
-                        chain.clone(),
-                        batch,
-                    );
+                    if !already_caught_up(caught_up_to, &batch) {
+                        worker.handle(batch);
+                    }
                 }
 
+                worker.retry_due();
+
                 Ok(Next::Continue)
             },
         );
@@ -237,9 +388,319 @@ fn spawn_batch_workers<Chain: ChainHandle>(
     handles
 }
 
+/// The state of the task handling the event batches of a chain.
+///
+/// The retry queues are never locked while the registry, client state filter or
+/// workers are, so that the batch workers of different chains cannot deadlock.
+struct BatchWorker<Chain: ChainHandle> {
+    config: Config,
+    registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
+    workers: Arc<RwLock<WorkerMap>>,
+    checkpoints: Option<RwArc<Checkpoints>>,
+    retry_queues: RetryQueues,
+    client_upgrades: ClientUpgrades,
+    channel_resolver: ChannelResolver,
+    sequence_ordering: Option<SequenceOrdering>,
+    last_block_height: Option<Height>,
+    chain: Chain,
+}
+
+impl<Chain: ChainHandle> BatchWorker<Chain> {
+    /// Handle a batch received from the chain, scheduling a retry if it fails to be processed.
+    fn handle(&mut self, batch: ArcBatch) {
+        if let Ok(batch) = batch.deref() {
+            if let Some(last_height) = revision_change(&mut self.last_block_height, batch) {
+                self.channel_resolver.invalidate_chain(&self.chain.id());
+
+                handle_revision_change(
+                    &self.registry,
+                    &self.workers,
+                    &self.client_upgrades,
+                    &self.chain,
+                    last_height,
+                    batch.height,
+                );
+            }
+        }
+
+        let result = handle_batch(
+            &self.config,
+            &mut self.registry.write(),
+            &mut self.client_state_filter.acquire_write(),
+            &mut self.workers.acquire_write(),
+            &mut self.channel_resolver,
+            self.sequence_ordering.as_mut(),
+            self.chain.clone(),
+            batch.clone(),
+        );
+
+        match (result, batch.deref()) {
+            (Err(e), Ok(batch)) => {
+                error!(chain = %self.chain.id(), "error during batch processing: {}", e);
+                self.schedule_retry(batch.clone(), 1, e);
+            }
+            (Ok(()), Ok(batch)) => self.record_checkpoint(batch.height),
+            _ => {}
+        }
+    }
+
+    /// Record that the blocks of the chain up to `height` were processed,
+    /// unless a batch of the chain is still awaiting a retry: the checkpoint
+    /// then stays behind it, so that its events are processed again when
+    /// catching up after a restart, and only moves on once it is processed
+    /// or moved to the dead letters.
+    fn record_checkpoint(&self, height: Height) {
+        let Some(checkpoints) = &self.checkpoints else {
+            return;
+        };
+
+        let retry_pending = self
+            .retry_queues
+            .acquire_read()
+            .get(&self.chain.id())
+            .map_or(false, |queue| queue.pending_count() > 0);
+
+        if !retry_pending {
+            record_checkpoint(checkpoints, &self.chain.id(), height);
+        }
+    }
+
+    /// Retry processing the next batch of the chain which is due for a retry, if any,
+    /// scheduling a new attempt or moving it to the dead letters if it fails again.
+    ///
+    /// Events dispatched to workers before a previous attempt failed are dispatched again,
+    /// which the workers tolerate since they discard the packets already relayed.
+    fn retry_due(&mut self) {
+        if !self.config.batch_retry.enabled {
+            return;
+        }
+
+        let failed = self
+            .retry_queues
+            .acquire_write()
+            .get_mut(&self.chain.id())
+            .and_then(BatchRetryQueue::next_due);
+
+        let Some(failed) = failed else {
+            return;
+        };
+
+        let result = process_batch(
+            &self.config,
+            &mut self.registry.write(),
+            &mut self.client_state_filter.acquire_write(),
+            &mut self.workers.acquire_write(),
+            &mut self.channel_resolver,
+            self.sequence_ordering.as_mut(),
+            self.chain.clone(),
+            &failed.batch,
+        );
+
+        match result {
+            Ok(()) => {
+                info!(
+                    chain = %self.chain.id(),
+                    height = %failed.batch.height,
+                    "processed event batch after {} failed attempts",
+                    failed.attempts
+                );
+
+                // The batches received after this one were processed in the meantime
+                let height = self
+                    .last_block_height
+                    .map_or(failed.batch.height, |last| last.max(failed.batch.height));
+
+                self.record_checkpoint(height);
+            }
+            Err(e) => {
+                error!(chain = %self.chain.id(), "error during batch processing: {}", e);
+                self.schedule_retry(failed.batch, failed.attempts + 1, e);
+            }
+        }
+    }
+
+    fn schedule_retry(&self, batch: EventBatch, attempts: u32, e: Error) {
+        if !self.config.batch_retry.enabled {
+            return;
+        }
+
+        self.retry_queues
+            .acquire_write()
+            .entry(self.chain.id())
+            .or_insert_with(|| BatchRetryQueue::new(&self.config.batch_retry))
+            .schedule(batch, attempts, e.to_string());
+    }
+}
+
+/// Load the checkpoints of the chains, if checkpointing is enabled.
+///
+/// Failing to load them is not fatal: the relayer then starts without
+/// catching up, and overwrites the checkpoint file as it goes.
+fn load_checkpoints(config: &Config) -> Option<RwArc<Checkpoints>> {
+    if !config.checkpoint.enabled {
+        return None;
+    }
+
+    match Checkpoints::load(&config.checkpoint) {
+        Ok(checkpoints) => Some(RwArc::new_lock(checkpoints)),
+        Err(e) => {
+            error!("failed to load checkpoints, disabling checkpointing: {}", e);
+            None
+        }
+    }
+}
+
+/// Record the given height as the last one processed for the chain,
+/// saving the checkpoints once the lock on them is released.
+fn record_checkpoint(checkpoints: &RwArc<Checkpoints>, chain_id: &ChainId, height: Height) {
+    let snapshot = checkpoints.acquire_write().record(chain_id, height);
+
+    if let Some(Err(e)) = snapshot.map(|snapshot| snapshot.save()) {
+        error!("failed to save checkpoint: {}", e);
+    }
+}
+
+/// Whether the given live batch was already processed while catching up
+/// on the blocks up to `caught_up_to`.
+fn already_caught_up(caught_up_to: Option<Height>, batch: &ArcBatch) -> bool {
+    match (caught_up_to, batch.deref()) {
+        (Some(caught_up_to), Ok(batch)) => batch.height <= caught_up_to,
+        _ => false,
+    }
+}
+
+/// Query the events of the blocks committed by the chain since its `last` checkpoint,
+/// up to its latest height, and handle them as if they had been received live.
+///
+/// At most `max_blocks` blocks are processed: if more were missed, only the latest
+/// ones are, and the packets sent in the others are left to packet clearing.
+///
+/// Returns the height of the last block processed, if any.
+#[instrument(
+    name = "supervisor.catch_up",
+    level = "error",
+    skip_all,
+    fields(chain = %chain.id(), %last)
+)]
+fn catch_up<Chain: ChainHandle>(
+    chain: &Chain,
+    last: Height,
+    max_blocks: u64,
+    mut handle: impl FnMut(ArcBatch),
+) -> Option<Height> {
+    let latest = match chain.query_latest_height() {
+        Ok(latest) => latest,
+        Err(e) => {
+            error!("failed to query latest height, skipping catch up: {}", e);
+            return None;
+        }
+    };
+
+    if latest.revision_number() != last.revision_number() {
+        warn!(
+            "chain changed revision since the last checkpoint, skipping catch up to {}",
+            latest
+        );
+        return None;
+    }
+
+    let missed = latest
+        .revision_height()
+        .saturating_sub(last.revision_height());
+    if missed == 0 {
+        return None;
+    }
+
+    let from = if missed > max_blocks {
+        warn!(
+            "{} blocks were committed since the last checkpoint, only catching up on the last {}",
+            missed, max_blocks
+        );
+
+        (latest - max_blocks).map_or(last, Height::increment)
+    } else {
+        last.increment()
+    };
+
+    info!("catching up on blocks {} to {}", from, latest);
+
+    let mut processed = None;
+    let mut height = from;
+    while height <= latest {
+        match chain.query_block_events(height) {
+            Ok(events) => handle(Arc::new(Ok(EventBatch {
+                chain_id: chain.id(),
+                tracking_id: TrackingId::new_uuid(),
+                height,
+                events,
+            }))),
+            Err(e) => {
+                error!(
+                    "failed to query events at height {}, stopping catch up: {}",
+                    height, e
+                );
+                break;
+            }
+        }
+
+        processed = Some(height);
+        height = height.increment();
+    }
+
+    processed
+}
+
+/// Every minute, report the amount of operational data and pending transactions
+/// held by each packet worker, to help tracking down steady memory growth.
+///
+/// These are the only unbounded queues of the relayer: the caches of the chain
+/// runtimes and of the packet workers are bounded moka caches, and the relayer
+/// neither caches consensus states nor keeps a journal of past events.
+fn spawn_memory_profile_worker<Chain: ChainHandle>(
+    registry: SharedRegistry<Chain>,
+    workers: Arc<RwLock<WorkerMap>>,
+) -> TaskHandle {
+    spawn_background_task(
+        error_span!("worker.profile_memory"),
+        Some(Duration::from_secs(60)),
+        move || -> Result<Next, TaskError<Infallible>> {
+            let state = state(&registry.read(), &workers.acquire_read());
+
+            let mut total_queued = 0;
+            let mut total_pending = 0;
+
+            for desc in state.all_workers() {
+                total_queued += desc.stats.queued_operational_data;
+                total_pending += desc.stats.pending_txs;
+
+                if desc.stats.queued_operational_data > 0 || desc.stats.pending_txs > 0 {
+                    info!(
+                        worker = %desc.object.short_name(),
+                        queued_operational_data = desc.stats.queued_operational_data,
+                        pending_txs = desc.stats.pending_txs,
+                        "memory profile"
+                    );
+                }
+            }
+
+            info!(
+                chains = state.chains.len(),
+                workers = state.all_workers().count(),
+                queued_operational_data = total_queued,
+                pending_txs = total_pending,
+                "memory profile totals"
+            );
+
+            Ok(Next::Continue)
+        },
+    )
+}
+
 pub fn spawn_cmd_worker<Chain: ChainHandle>(
     registry: SharedRegistry<Chain>,
     workers: Arc<RwLock<WorkerMap>>,
+    retry_queues: RetryQueues,
     cmd_rx: Receiver<SupervisorCmd>,
 ) -> TaskHandle {
     spawn_background_task(
@@ -251,6 +712,12 @@ pub fn spawn_cmd_worker<Chain: ChainHandle>(
                     SupervisorCmd::DumpState(reply_to) => {
                         dump_state(&registry.read(), &workers.acquire_read(), reply_to);
                     }
+                    SupervisorCmd::ReprocessDeadLetters(reply_to) => {
+                        let _ = reply_to.try_send(reprocess_dead_letters(&retry_queues));
+                    }
+                    SupervisorCmd::ResumeWorkers(reply_to) => {
+                        let _ = reply_to.try_send(resume_workers(&workers.acquire_read()));
+                    }
                 }
             }
 
@@ -263,13 +730,22 @@ pub fn spawn_rest_worker<Chain: ChainHandle>(
     config: Config,
     registry: SharedRegistry<Chain>,
     workers: Arc<RwLock<WorkerMap>>,
+    retry_queues: RetryQueues,
+    statuses: Vec<TaskStatus>,
     rest_rx: rest::Receiver,
 ) -> TaskHandle {
     spawn_background_task(
         error_span!("rest"),
         Some(Duration::from_millis(500)),
         move || -> Result<Next, TaskError<Infallible>> {
-            handle_rest_requests(&config, &registry.read(), &workers.acquire_read(), &rest_rx);
+            handle_rest_requests(
+                &config,
+                &registry,
+                &workers,
+                &retry_queues,
+                &statuses,
+                &rest_rx,
+            );
 
             Ok(Next::Continue)
         },
@@ -400,9 +876,45 @@ fn collect_event<F>(
     }
 }
 
+/// If `enabled`, resolve the packet [`Object`] for the given channel end on `src_chain`
+/// using the `resolver`, and add the given `event` to the `collected` events for this object.
+fn collect_packet_event(
+    collected: &mut CollectedEvents,
+    resolver: &mut ChannelResolver,
+    event_with_height: IbcEventWithHeight,
+    enabled: bool,
+    src_chain: &impl ChainHandle,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) {
+    if !enabled {
+        return;
+    }
+
+    match resolver.packet_object(src_chain, port_id, channel_id) {
+        Ok(object) => {
+            collected
+                .per_object
+                .entry(object)
+                .or_default()
+                .push(event_with_height);
+        }
+        Err(e) => {
+            debug!(
+                "failed to resolve channel {}/{} on chain {}: {}",
+                port_id,
+                channel_id,
+                src_chain.id(),
+                e
+            );
+        }
+    }
+}
+
 pub fn collect_events(
     config: &Config,
     workers: &WorkerMap,
+    resolver: &mut ChannelResolver,
     src_chain: &impl ChainHandle,
     batch: &EventBatch,
 ) -> CollectedEvents {
@@ -504,38 +1016,62 @@ pub fn collect_events(
                     || Object::client_from_chan_open_events(&attributes, src_chain).ok(),
                 );
             }
-            IbcEvent::SendPacket(ref packet) => {
-                collect_event(
+            IbcEvent::SendPacket(ref send_packet) => {
+                collect_packet_event(
                     &mut collected,
+                    resolver,
                     event_with_height.clone(),
                     mode.packets.enabled,
-                    || Object::for_send_packet(packet, src_chain).ok(),
+                    src_chain,
+                    &send_packet.packet.source_port,
+                    &send_packet.packet.source_channel,
                 );
             }
-            IbcEvent::TimeoutPacket(ref packet) => {
-                collect_event(
+            IbcEvent::TimeoutPacket(ref timeout) => {
+                collect_packet_event(
                     &mut collected,
+                    resolver,
                     event_with_height.clone(),
                     mode.packets.enabled,
-                    || Object::for_timeout_packet(packet, src_chain).ok(),
+                    src_chain,
+                    timeout.src_port_id(),
+                    timeout.src_channel_id(),
                 );
             }
-            IbcEvent::WriteAcknowledgement(ref packet) => {
-                collect_event(
+            IbcEvent::WriteAcknowledgement(ref write_ack) => {
+                collect_packet_event(
                     &mut collected,
+                    resolver,
                     event_with_height.clone(),
                     mode.packets.enabled,
-                    || Object::for_write_ack(packet, src_chain).ok(),
+                    src_chain,
+                    &write_ack.packet.destination_port,
+                    &write_ack.packet.destination_channel,
                 );
             }
-            IbcEvent::CloseInitChannel(ref packet) => {
-                collect_event(
+            IbcEvent::CloseInitChannel(ref close_init) => {
+                collect_packet_event(
                     &mut collected,
+                    resolver,
                     event_with_height.clone(),
                     mode.packets.enabled,
-                    || Object::for_close_init_channel(packet, src_chain).ok(),
+                    src_chain,
+                    close_init.port_id(),
+                    close_init.channel_id(),
+                );
+
+                // The channel is being closed, do not keep routing information around
+                resolver.invalidate(
+                    &src_chain.id(),
+                    close_init.port_id(),
+                    close_init.channel_id(),
                 );
             }
+            IbcEvent::CloseConfirmChannel(ref close_confirm) => {
+                if let Some(channel_id) = close_confirm.channel_id() {
+                    resolver.invalidate(&src_chain.id(), &close_confirm.port_id, channel_id);
+                }
+            }
             IbcEvent::CrossChainQueryPacket(ref packet) => {
                 collect_event(
                     &mut collected,
@@ -545,11 +1081,14 @@ pub fn collect_events(
                 );
             }
             IbcEvent::IncentivizedPacket(ref packet) => {
-                collect_event(
+                collect_packet_event(
                     &mut collected,
+                    resolver,
                     event_with_height.clone(),
                     mode.packets.enabled,
-                    || Object::for_incentivized_packet(packet, src_chain).ok(),
+                    src_chain,
+                    &packet.port_id,
+                    &packet.channel_id,
                 );
             }
             _ => (),
@@ -605,6 +1144,136 @@ fn health_check<Chain: ChainHandle>(config: &Config, registry: &mut Registry<Cha
     }
 }
 
+mod readiness_strategy {
+    use crate::util::retry::clamp_total;
+    use core::time::Duration;
+    use retry::delay::Fibonacci;
+
+    // Parameters for waiting on a node to catch up with its chain
+    const MAX_DELAY: Duration = Duration::from_secs(60); // 1 minute
+    const MAX_TOTAL_DELAY: Duration = Duration::from_secs(10 * 60); // 10 minutes
+    const INITIAL_DELAY: Duration = Duration::from_secs(1); // 1 second
+
+    pub fn default() -> impl Iterator<Item = Duration> {
+        clamp_total(Fibonacci::from(INITIAL_DELAY), MAX_DELAY, MAX_TOTAL_DELAY)
+    }
+}
+
+/// The handles of the configured chains, spawning their runtime if need be.
+fn chain_handles<Chain: ChainHandle>(
+    config: &Config,
+    registry: &SharedRegistry<Chain>,
+) -> Vec<Chain> {
+    config
+        .chains
+        .iter()
+        .filter_map(|chain_config| {
+            registry
+                .get_or_spawn(&chain_config.id)
+                .map_err(|e| {
+                    error!(
+                        chain = %chain_config.id,
+                        "skipping readiness check, reason: failed to spawn chain runtime with error: {}",
+                        e
+                    )
+                })
+                .ok()
+        })
+        .collect()
+}
+
+/// Check, in parallel, whether the nodes of the given chains have caught up
+/// with their chain, returning the chains whose node has not.
+fn syncing_chains<Chain: ChainHandle>(chains: Vec<Chain>) -> Vec<Chain> {
+    thread::scope(|scope| {
+        let checks: Vec<_> = chains
+            .into_iter()
+            .map(|chain| {
+                scope.spawn(move || {
+                    let syncing = matches!(node_readiness(&chain), RetryResult::Retry(_));
+                    (syncing, chain)
+                })
+            })
+            .collect();
+
+        checks
+            .into_iter()
+            .filter_map(|check| check.join().ok())
+            .filter_map(|(syncing, chain)| syncing.then_some(chain))
+            .collect()
+    })
+}
+
+/// Whether the node of the given chain has caught up with its chain.
+///
+/// The node is deemed ready if its status cannot be queried because of the
+/// configuration of the chain, since waiting for it would be pointless.
+fn node_readiness(chain: &impl ChainHandle) -> RetryResult<(), ()> {
+    match chain.is_syncing() {
+        Ok(false) => RetryResult::Ok(()),
+        Ok(true) => RetryResult::Retry(()),
+        Err(e) if e.is_config_error() => {
+            error!(
+                chain = %chain.id(),
+                "not waiting for the node to catch up, reason: failed to query node status: {}",
+                e
+            );
+            RetryResult::Ok(())
+        }
+        Err(e) => {
+            trace!(chain = %chain.id(), "failed to query node status: {}", e);
+            RetryResult::Retry(())
+        }
+    }
+}
+
+/// Wait, with backoff, for the node of the given chain to catch up with its chain,
+/// then scan the chain and spawn its workers, so that the workers do not flood a
+/// syncing node with queries that are bound to fail.
+///
+/// The wait happens on a dedicated thread, so that the workers of the other chains
+/// are not held back. Gives up waiting after a while and spawns the workers anyway.
+fn spawn_workers_once_synced<Chain: ChainHandle>(
+    config: Config,
+    registry: SharedRegistry<Chain>,
+    client_state_filter: Arc<RwLock<FilterPolicy>>,
+    workers: Arc<RwLock<WorkerMap>>,
+    chain: Chain,
+    scan_mode: ScanMode,
+) {
+    thread::spawn(move || {
+        let _span = error_span!("wait_for_readiness", chain = %chain.id()).entered();
+
+        warn!("node is still syncing, waiting for it to catch up before spawning workers");
+
+        match retry_with_index(readiness_strategy::default(), |_| node_readiness(&chain)) {
+            Ok(()) => info!("node has caught up, spawning workers"),
+            Err(e) => warn!(
+                "node has not caught up after {} checks, spawning workers anyway",
+                e.tries
+            ),
+        }
+
+        let Some(chain_config) = config.find_chain(&chain.id()) else {
+            return;
+        };
+
+        let scan = chain_scanner(
+            &config,
+            &mut registry.write(),
+            &mut client_state_filter.acquire_write(),
+            scan_mode,
+        )
+        .scan_chain(chain_config);
+
+        match scan {
+            Ok(scan) => spawn_context(&config, &mut registry.write(), &mut workers.acquire_write())
+                .spawn_workers_for_chain(scan),
+            Err(e) => error!("failed to scan chain, reason: {}", e),
+        }
+    });
+}
+
 /// Subscribe to the events emitted by the chains the supervisor is connected to.
 #[instrument(name = "supervisor.init_subscriptions", level = "error", skip_all)]
 fn init_subscriptions<Chain: ChainHandle>(
@@ -666,28 +1335,60 @@ fn state<Chain: ChainHandle>(registry: &Registry<Chain>, workers: &WorkerMap) ->
 
 fn handle_rest_requests<Chain: ChainHandle>(
     config: &Config,
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    registry: &SharedRegistry<Chain>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    retry_queues: &RetryQueues,
+    statuses: &[TaskStatus],
     rest_rx: &rest::Receiver,
 ) {
     if let Some(cmd) = rest::process_incoming_requests(config, rest_rx) {
-        handle_rest_cmd(registry, workers, cmd);
+        handle_rest_cmd(registry, workers, retry_queues, statuses, cmd);
     }
 }
 
 #[instrument(name = "supervisor.handle_rest_cmd", level = "error", skip_all)]
 fn handle_rest_cmd<Chain: ChainHandle>(
-    registry: &Registry<Chain>,
-    workers: &WorkerMap,
+    registry: &SharedRegistry<Chain>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    retry_queues: &RetryQueues,
+    statuses: &[TaskStatus],
     m: rest::Command,
 ) {
+    // Only take the locks needed by each command, so that the retry queues
+    // are never locked while the registry or the workers are.
     match m {
         rest::Command::DumpState(reply) => {
-            let state = state(registry, workers);
+            let state = state(&registry.read(), &workers.acquire_read());
             reply
                 .send(Ok(state))
                 .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
         }
+
+        rest::Command::ReprocessDeadLetters(reply) => {
+            let count = reprocess_dead_letters(retry_queues);
+            reply
+                .send(Ok(count))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+
+        rest::Command::ResumeWorkers(reply) => {
+            let count = resume_workers(&workers.acquire_read());
+            reply
+                .send(Ok(count))
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
+
+        rest::Command::Health(reply) => {
+            let result = if statuses.iter().any(TaskStatus::is_stopped) {
+                Err(rest::RestApiError::SupervisorStopped)
+            } else {
+                Ok(())
+            };
+
+            reply
+                .send(result)
+                .unwrap_or_else(|e| error!("error replying to a REST request {}", e));
+        }
     }
 }
 
@@ -710,13 +1411,15 @@ fn clear_pending_packets(workers: &mut WorkerMap, chain_id: &ChainId) -> Result<
     name = "supervisor.process_batch",
     level = "error",
     skip_all,
-    fields(chain = %src_chain.id()))
+    fields(chain = %src_chain.id(), tracking_id = %batch.tracking_id))
 ]
 fn process_batch<Chain: ChainHandle>(
     config: &Config,
     registry: &mut Registry<Chain>,
     client_state_filter: &mut FilterPolicy,
     workers: &mut WorkerMap,
+    channel_resolver: &mut ChannelResolver,
+    sequence_ordering: Option<&mut SequenceOrdering>,
     src_chain: Chain,
     batch: &EventBatch,
 ) -> Result<(), Error> {
@@ -724,7 +1427,14 @@ fn process_batch<Chain: ChainHandle>(
 
     telemetry!(received_event_batch, batch.tracking_id);
 
-    let collected = collect_events(config, workers, &src_chain, batch);
+    sink::publish_batch(batch);
+    webhook::notify(&batch.chain_id, &batch.events);
+
+    let mut collected = collect_events(config, workers, channel_resolver, &src_chain, batch);
+
+    if let Some(sequence_ordering) = sequence_ordering {
+        sequence_ordering.apply(&mut collected.per_object);
+    }
 
     // If there is a NewBlock event, forward this event first to any workers affected by it.
     if let Some(IbcEvent::NewBlock(new_block)) = collected.new_block {
@@ -839,6 +1549,8 @@ fn send_telemetry<Src, Dst>(
 
 /// Process the given batch if it does not contain any errors,
 /// output the errors on the console otherwise.
+///
+/// Returns the error of processing the batch, if it failed.
 #[instrument(
     name = "supervisor.handle_batch",
     level = "error",
@@ -850,18 +1562,25 @@ fn handle_batch<Chain: ChainHandle>(
     registry: &mut Registry<Chain>,
     client_state_filter: &mut FilterPolicy,
     workers: &mut WorkerMap,
+    channel_resolver: &mut ChannelResolver,
+    sequence_ordering: Option<&mut SequenceOrdering>,
     chain: Chain,
     batch: ArcBatch,
-) {
+) -> Result<(), Error> {
     let chain_id = chain.id();
 
     match batch.deref() {
         Ok(batch) => {
-            if let Err(e) =
-                process_batch(config, registry, client_state_filter, workers, chain, batch)
-            {
-                error!("error during batch processing: {}", e);
-            }
+            return process_batch(
+                config,
+                registry,
+                client_state_filter,
+                workers,
+                channel_resolver,
+                sequence_ordering,
+                chain,
+                batch,
+            );
         }
         Err(EventError(EventErrorDetail::SubscriptionCancelled(_), _)) => {
             warn!("event subscription was cancelled, clearing pending packets");
@@ -869,10 +1588,214 @@ fn handle_batch<Chain: ChainHandle>(
             let _ = clear_pending_packets(workers, &chain_id)
                 .map_err(|e| error!("error during clearing pending packets: {}", e));
         }
+        Err(EventError(EventErrorDetail::BlockLagExceeded(_), _)) => {
+            warn!("event monitor fell behind the node, clearing pending packets");
+
+            let _ = clear_pending_packets(workers, &chain_id)
+                .map_err(|e| error!("error during clearing pending packets: {}", e));
+        }
         Err(e) => {
             error!("error when receiving event batch: {}", e)
         }
     }
+
+    Ok(())
+}
+
+/// Resume the workers suspended by their circuit breaker, eg. once the
+/// wallet of the relayer was refunded, returning the number of workers resumed.
+fn resume_workers(workers: &WorkerMap) -> usize {
+    let count = workers.handles().filter(|worker| worker.resume()).count();
+
+    if count > 0 {
+        info!("resuming {} suspended workers", count);
+    }
+
+    count
+}
+
+/// Move the dead letters of all chains back to their retry queue,
+/// returning the number of batches which were requeued.
+fn reprocess_dead_letters(retry_queues: &RetryQueues) -> usize {
+    let mut retry_queues = retry_queues.acquire_write();
+
+    retry_queues
+        .iter_mut()
+        .map(|(chain_id, retry_queue)| {
+            let count = retry_queue.requeue_dead_letters();
+
+            if count > 0 {
+                info!(
+                    chain = %chain_id,
+                    "scheduled {} dead letters for a retry, {} left",
+                    count,
+                    retry_queue.dead_letter_count()
+                );
+            }
+
+            count
+        })
+        .sum()
+}
+
+/// Track the height of the `NewBlock` events of a chain, returning the height
+/// of the last block of the previous revision if the given batch is the first
+/// one of a new revision.
+fn revision_change(last_block_height: &mut Option<Height>, batch: &EventBatch) -> Option<Height> {
+    let height = batch
+        .events
+        .iter()
+        .find(|e| matches!(e.event, IbcEvent::NewBlock(_)))
+        .map(|e| e.height)?;
+
+    last_block_height
+        .replace(height)
+        .filter(|last| last.revision_number() != height.revision_number())
+}
+
+/// Handle a change of revision of the given chain, typically following an upgrade
+/// which changed its chain identifier, as detected from its `NewBlock` events.
+///
+/// The workers relaying to and from the chain are shut down, so that they are
+/// spawned again with fresh state, and in particular without any cached height of
+/// the previous revision, upon the next batch of events involving them.
+/// The clients tracking the chain on its counterparties are then upgraded,
+/// assuming the chain halted right after the last block of its previous revision.
+///
+/// The registry and the workers are only locked while looking up the clients.
+/// Each upgrade is submitted from its own thread, so as not to hold up the batches
+/// of the chain meanwhile, unless the client is already being or was already
+/// upgraded to the same height (see [`claim_client_upgrade`]).
+#[instrument(
+    name = "supervisor.handle_revision_change",
+    level = "error",
+    skip_all,
+    fields(chain = %chain.id(), %last_height, %new_height)
+)]
+fn handle_revision_change<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    workers: &Arc<RwLock<WorkerMap>>,
+    client_upgrades: &ClientUpgrades,
+    chain: &Chain,
+    last_height: Height,
+    new_height: Height,
+) {
+    warn!(
+        "chain changed revision from {} to {}, restarting its workers and upgrading the clients tracking it",
+        last_height.revision_number(),
+        new_height.revision_number()
+    );
+
+    let objects = {
+        let mut workers = workers.acquire_write();
+        let objects = workers.objects_for_chain(&chain.id());
+
+        for object in &objects {
+            workers.shutdown_worker(object);
+        }
+
+        objects
+    };
+
+    let upgrade_height = last_height.increment();
+
+    for object in objects {
+        let client = match object {
+            Object::Client(client) if client.src_chain_id == chain.id() => client,
+            _ => continue,
+        };
+
+        if !claim_client_upgrade(
+            client_upgrades,
+            &client.dst_chain_id,
+            &client.dst_client_id,
+            upgrade_height,
+        ) {
+            debug!(
+                "client {} on chain {} is already upgraded to {}, skipping",
+                client.dst_client_id, client.dst_chain_id, upgrade_height
+            );
+            continue;
+        }
+
+        let registry = registry.clone();
+        let client_upgrades = client_upgrades.clone();
+        let chain = chain.clone();
+
+        thread::spawn(move || {
+            let _span = error_span!(
+                "supervisor.upgrade_client",
+                client = %client.dst_client_id,
+                host_chain = %client.dst_chain_id,
+            )
+            .entered();
+
+            if !upgrade_client(&registry, chain, &client, upgrade_height) {
+                // Let the upgrade be attempted again upon the next change of revision
+                client_upgrades
+                    .acquire_write()
+                    .remove(&(client.dst_chain_id, client.dst_client_id));
+            }
+        });
+    }
+}
+
+/// Record that the given client is being upgraded to the given height, returning `false`
+/// if it is already being or was already upgraded to that height or a later one.
+fn claim_client_upgrade(
+    client_upgrades: &ClientUpgrades,
+    chain_id: &ChainId,
+    client_id: &ClientId,
+    height: Height,
+) -> bool {
+    let mut client_upgrades = client_upgrades.acquire_write();
+    let key = (chain_id.clone(), client_id.clone());
+
+    match client_upgrades.get(&key) {
+        Some(claimed) if *claimed >= height => false,
+        _ => {
+            client_upgrades.insert(key, height);
+            true
+        }
+    }
+}
+
+/// Upgrade the given client to the given height, returning whether the upgrade succeeded.
+fn upgrade_client<Chain: ChainHandle>(
+    registry: &SharedRegistry<Chain>,
+    chain: Chain,
+    client: &Client,
+    upgrade_height: Height,
+) -> bool {
+    let dst_chain = match registry.get_or_spawn(&client.dst_chain_id) {
+        Ok(dst_chain) => dst_chain,
+        Err(e) => {
+            error!(
+                "failed to spawn chain runtime for {}: {}",
+                client.dst_chain_id, e
+            );
+            return false;
+        }
+    };
+
+    let foreign_client = ForeignClient::restore(client.dst_client_id.clone(), dst_chain, chain);
+
+    match foreign_client.upgrade(upgrade_height) {
+        Ok(_) => {
+            info!(
+                "upgraded client {} on chain {}",
+                client.dst_client_id, client.dst_chain_id
+            );
+            true
+        }
+        Err(e) => {
+            error!(
+                "failed to upgrade client {} on chain {}: {}",
+                client.dst_client_id, client.dst_chain_id, e
+            );
+            false
+        }
+    }
 }
 
 /// Describes the result of [`collect_events`].
@@ -908,3 +1831,257 @@ impl CollectedEvents {
         self.new_block.is_some()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics02_client::events::NewBlock;
+
+    use crate::chain::handle::MockChainHandle;
+    use crate::config::{BatchRetryConfig, CheckpointConfig};
+
+    fn empty_batch(chain_id: &ChainId, height: u64) -> EventBatch {
+        EventBatch {
+            chain_id: chain_id.clone(),
+            tracking_id: TrackingId::new_uuid(),
+            height: Height::new(chain_id.version(), height).unwrap(),
+            events: Vec::new(),
+        }
+    }
+
+    fn new_block_batch(chain_id: &ChainId, revision_number: u64, height: u64) -> EventBatch {
+        let height = Height::new(revision_number, height).unwrap();
+
+        EventBatch {
+            chain_id: chain_id.clone(),
+            tracking_id: TrackingId::new_uuid(),
+            height,
+            events: vec![IbcEventWithHeight::new(
+                NewBlock::new(height).into(),
+                height,
+            )],
+        }
+    }
+
+    #[test]
+    fn detect_revision_change() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let mut last_block_height = None;
+
+        assert_eq!(
+            revision_change(&mut last_block_height, &new_block_batch(&chain_id, 0, 9)),
+            None
+        );
+        assert_eq!(
+            revision_change(&mut last_block_height, &empty_batch(&chain_id, 10)),
+            None
+        );
+        assert_eq!(
+            revision_change(&mut last_block_height, &new_block_batch(&chain_id, 0, 10)),
+            None
+        );
+        assert_eq!(
+            revision_change(&mut last_block_height, &new_block_batch(&chain_id, 1, 1)),
+            Some(Height::new(0, 10).unwrap())
+        );
+        assert_eq!(
+            revision_change(&mut last_block_height, &new_block_batch(&chain_id, 1, 2)),
+            None
+        );
+    }
+
+    #[test]
+    fn upgrade_each_client_once_per_height() {
+        let client_upgrades = RwArc::new_lock(HashMap::new());
+        let chain_id = ChainId::from_string("ibc-1");
+        let client_id = ClientId::default();
+
+        let claim = |revision_height| {
+            claim_client_upgrade(
+                &client_upgrades,
+                &chain_id,
+                &client_id,
+                Height::new(0, revision_height).unwrap(),
+            )
+        };
+
+        assert!(claim(10));
+        assert!(!claim(10));
+        assert!(!claim(9));
+        assert!(claim(11));
+
+        // Another client of the same chain is upgraded independently
+        assert!(claim_client_upgrade(
+            &client_upgrades,
+            &chain_id,
+            &"07-tendermint-1".parse::<ClientId>().unwrap(),
+            Height::new(0, 10).unwrap(),
+        ));
+    }
+
+    #[test]
+    fn skip_live_batches_processed_while_catching_up() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let chain = MockChainHandle::new(chain_id.clone());
+        for _ in 0..3 {
+            chain.produce_block(Vec::new());
+        }
+
+        let mut handled = Vec::new();
+        let caught_up_to = catch_up(&chain, Height::new(0, 2).unwrap(), 1000, |batch| {
+            handled.push(batch.deref().as_ref().unwrap().height)
+        });
+
+        assert_eq!(
+            handled,
+            vec![Height::new(0, 3).unwrap(), Height::new(0, 4).unwrap()]
+        );
+        assert_eq!(caught_up_to, Some(Height::new(0, 4).unwrap()));
+
+        let live = |height| Arc::new(Ok(empty_batch(&chain_id, height)));
+        assert!(already_caught_up(caught_up_to, &live(4)));
+        assert!(!already_caught_up(caught_up_to, &live(5)));
+        assert!(!already_caught_up(None, &live(4)));
+    }
+
+    #[test]
+    fn only_syncing_chains_are_waited_for() {
+        let ready = MockChainHandle::new(ChainId::from_string("ibc-0"));
+        let syncing = MockChainHandle::new(ChainId::from_string("ibc-1"));
+        let unreachable = MockChainHandle::new(ChainId::from_string("ibc-2"));
+
+        syncing.set_syncing(true);
+        unreachable.fail_next("is_syncing", 1);
+
+        let waited_for: Vec<_> = syncing_chains(vec![ready.clone(), syncing, unreachable])
+            .iter()
+            .map(|chain| chain.id())
+            .collect();
+
+        assert_eq!(
+            waited_for,
+            vec![ChainId::from_string("ibc-1"), ChainId::from_string("ibc-2")]
+        );
+        assert_eq!(ready.calls("is_syncing"), 1);
+    }
+
+    #[test]
+    fn batch_workers_of_two_chains_do_not_deadlock() {
+        let config = Config {
+            batch_retry: BatchRetryConfig {
+                enabled: true,
+                backoff: Duration::ZERO,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let registry = SharedRegistry::<MockChainHandle>::new(config.clone());
+        let client_state_filter = Arc::new(RwLock::new(FilterPolicy::default()));
+        let workers = Arc::new(RwLock::new(WorkerMap::new()));
+        let retry_queues: RetryQueues = Arc::new(RwLock::new(HashMap::new()));
+
+        let (done_tx, done_rx) = crossbeam_channel::unbounded();
+
+        for chain_id in ["ibc-0", "ibc-1"] {
+            let chain_id = ChainId::from_string(chain_id);
+
+            let mut worker = BatchWorker {
+                config: config.clone(),
+                registry: registry.clone(),
+                client_state_filter: client_state_filter.clone(),
+                workers: workers.clone(),
+                checkpoints: None,
+                retry_queues: retry_queues.clone(),
+                client_upgrades: RwArc::new_lock(HashMap::new()),
+                channel_resolver: ChannelResolver::new(),
+                sequence_ordering: None,
+                last_block_height: None,
+                chain: MockChainHandle::new(chain_id.clone()),
+            };
+
+            let done_tx = done_tx.clone();
+
+            thread::spawn(move || {
+                for height in 1..=200 {
+                    // Schedule a retry as if processing a batch had failed,
+                    // so that both the live and the retry paths take the locks
+                    worker.schedule_retry(empty_batch(&chain_id, height), 1, Error::handle_send());
+                    worker.handle(Arc::new(Ok(empty_batch(&chain_id, height))));
+                    worker.retry_due();
+                }
+
+                done_tx.send(chain_id).unwrap();
+            });
+        }
+
+        for _ in 0..2 {
+            done_rx
+                .recv_timeout(Duration::from_secs(30))
+                .expect("the batch workers deadlocked");
+        }
+
+        let retry_queues = retry_queues.acquire_read();
+        assert_eq!(retry_queues.len(), 2);
+        assert!(retry_queues
+            .values()
+            .all(|queue| queue.pending_count() == 0 && queue.dead_letter_count() == 0));
+    }
+
+    #[test]
+    fn checkpoint_stays_behind_batches_awaiting_a_retry() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let height = |height| Height::new(0, height).unwrap();
+
+        let config = Config {
+            batch_retry: BatchRetryConfig {
+                enabled: true,
+                backoff: Duration::ZERO,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let dir = std::env::temp_dir().join(format!(
+            "hermes-supervisor-checkpoints-{}",
+            std::process::id()
+        ));
+        let checkpoints = RwArc::new_lock(
+            Checkpoints::load(&CheckpointConfig {
+                enabled: true,
+                path: Some(dir.join("checkpoints.json")),
+                max_catch_up_blocks: 1000,
+            })
+            .unwrap(),
+        );
+
+        let mut worker = BatchWorker {
+            config: config.clone(),
+            registry: SharedRegistry::new(config),
+            client_state_filter: Arc::new(RwLock::new(FilterPolicy::default())),
+            workers: Arc::new(RwLock::new(WorkerMap::new())),
+            checkpoints: Some(checkpoints.clone()),
+            retry_queues: Arc::new(RwLock::new(HashMap::new())),
+            client_upgrades: RwArc::new_lock(HashMap::new()),
+            channel_resolver: ChannelResolver::new(),
+            sequence_ordering: None,
+            last_block_height: None,
+            chain: MockChainHandle::new(chain_id.clone()),
+        };
+
+        worker.handle(Arc::new(Ok(new_block_batch(&chain_id, 0, 1))));
+        assert_eq!(checkpoints.acquire_read().get(&chain_id), Some(height(1)));
+
+        // Processing the batch at height 2 failed, it is only held in memory until retried
+        worker.schedule_retry(empty_batch(&chain_id, 2), 1, Error::handle_send());
+        worker.handle(Arc::new(Ok(new_block_batch(&chain_id, 0, 3))));
+        assert_eq!(checkpoints.acquire_read().get(&chain_id), Some(height(1)));
+
+        // Once retried, the checkpoint moves past the batches processed in the meantime
+        worker.retry_due();
+        assert_eq!(checkpoints.acquire_read().get(&chain_id), Some(height(3)));
+
+        std::fs::remove_dir_all(dir).ok();
+    }
+}
@@ -0,0 +1,217 @@
+//! Bounded, rate-limited retry of the event batches which the supervisor
+//! fails to process, with dead letters for the batches which keep failing.
+
+use alloc::collections::VecDeque;
+use core::mem;
+use core::time::Duration;
+use std::time::Instant;
+
+use tracing::{error, warn};
+
+use crate::config::BatchRetryConfig;
+use crate::event::monitor::EventBatch;
+
+/// The batches of a chain awaiting a retry, and the dead letters,
+/// ie. the batches which failed to be processed after the maximum
+/// number of attempts.
+#[derive(Debug)]
+pub struct BatchRetryQueue {
+    max_attempts: u32,
+    backoff: Duration,
+    capacity: usize,
+    max_dead_letters: usize,
+    pending: VecDeque<FailedBatch>,
+    dead_letters: VecDeque<FailedBatch>,
+}
+
+/// A batch which failed to be processed.
+#[derive(Debug)]
+pub struct FailedBatch {
+    pub batch: EventBatch,
+    /// Number of failed attempts at processing the batch
+    pub attempts: u32,
+    /// Error of the last attempt
+    pub last_error: String,
+    retry_at: Instant,
+}
+
+impl BatchRetryQueue {
+    pub fn new(config: &BatchRetryConfig) -> Self {
+        Self {
+            max_attempts: config.max_attempts,
+            backoff: config.backoff,
+            capacity: config.capacity,
+            max_dead_letters: config.max_dead_letters,
+            pending: VecDeque::new(),
+            dead_letters: VecDeque::new(),
+        }
+    }
+
+    /// Schedule a retry of a batch which failed to be processed `attempts` times,
+    /// with a delay doubling after each attempt, or move it to the dead letters
+    /// if the maximum number of attempts is reached.
+    pub fn schedule(&mut self, batch: EventBatch, attempts: u32, last_error: String) {
+        let now = Instant::now();
+        let delay = self
+            .backoff
+            .saturating_mul(2u32.saturating_pow(attempts.saturating_sub(1)));
+
+        let failed = FailedBatch {
+            batch,
+            attempts,
+            last_error,
+            retry_at: now + delay,
+        };
+
+        if attempts >= self.max_attempts {
+            self.dead_letter(failed);
+            return;
+        }
+
+        if self.pending.len() >= self.capacity.max(1) {
+            if let Some(oldest) = self.pending.pop_front() {
+                warn!(
+                    height = %oldest.batch.height,
+                    "retry queue is full, giving up on the oldest batch"
+                );
+
+                self.dead_letter(oldest);
+            }
+        }
+
+        self.pending.push_back(failed);
+    }
+
+    /// The next batch due for a retry, if any.
+    ///
+    /// At most one batch is returned per call, which limits the rate
+    /// of retries to the rate at which the caller polls the queue.
+    pub fn next_due(&mut self) -> Option<FailedBatch> {
+        let now = Instant::now();
+        let position = self.pending.iter().position(|f| f.retry_at <= now)?;
+
+        self.pending.remove(position)
+    }
+
+    /// Move the dead letters back to the retry queue, with their number of
+    /// attempts reset, as long as there is room for them in the queue.
+    ///
+    /// Returns the number of batches which were requeued.
+    pub fn requeue_dead_letters(&mut self) -> usize {
+        let room = self.capacity.saturating_sub(self.pending.len());
+        let count = room.min(self.dead_letters.len());
+        let now = Instant::now();
+
+        let mut dead_letters = mem::take(&mut self.dead_letters);
+
+        for mut failed in dead_letters.drain(..count) {
+            failed.attempts = 0;
+            failed.retry_at = now;
+
+            self.pending.push_back(failed);
+        }
+
+        self.dead_letters = dead_letters;
+
+        count
+    }
+
+    /// Number of batches awaiting a retry
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Number of dead letters
+    pub fn dead_letter_count(&self) -> usize {
+        self.dead_letters.len()
+    }
+
+    fn dead_letter(&mut self, failed: FailedBatch) {
+        error!(
+            height = %failed.batch.height,
+            tracking_id = %failed.batch.tracking_id,
+            events = failed.batch.events.len(),
+            attempts = failed.attempts,
+            "giving up on processing event batch, moving it to the dead letters: {}",
+            failed.last_error
+        );
+
+        if self.dead_letters.len() >= self.max_dead_letters {
+            if let Some(oldest) = self.dead_letters.pop_front() {
+                warn!(
+                    height = %oldest.batch.height,
+                    "too many dead letters, discarding the oldest one"
+                );
+            }
+        }
+
+        if self.max_dead_letters > 0 {
+            self.dead_letters.push_back(failed);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::Height;
+
+    use crate::chain::tracking::TrackingId;
+
+    fn config() -> BatchRetryConfig {
+        BatchRetryConfig {
+            enabled: true,
+            max_attempts: 2,
+            backoff: Duration::ZERO,
+            capacity: 2,
+            max_dead_letters: 10,
+        }
+    }
+
+    fn batch(height: u64) -> EventBatch {
+        EventBatch {
+            chain_id: ChainId::from_string("ibc-0"),
+            tracking_id: TrackingId::new_uuid(),
+            height: Height::new(0, height).unwrap(),
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn dead_letters_after_max_attempts() {
+        let mut queue = BatchRetryQueue::new(&config());
+
+        queue.schedule(batch(1), 1, "failed".to_string());
+        assert_eq!(queue.pending_count(), 1);
+
+        let failed = queue.next_due().unwrap();
+        assert_eq!(failed.attempts, 1);
+        assert!(queue.next_due().is_none());
+
+        queue.schedule(failed.batch, failed.attempts + 1, "failed".to_string());
+        assert_eq!(queue.pending_count(), 0);
+        assert_eq!(queue.dead_letter_count(), 1);
+
+        assert_eq!(queue.requeue_dead_letters(), 1);
+        assert_eq!(queue.dead_letter_count(), 0);
+        assert_eq!(queue.next_due().unwrap().attempts, 0);
+    }
+
+    #[test]
+    fn bounded_queue() {
+        let mut queue = BatchRetryQueue::new(&config());
+
+        for height in 1..=3 {
+            queue.schedule(batch(height), 1, "failed".to_string());
+        }
+
+        assert_eq!(queue.pending_count(), 2);
+        assert_eq!(queue.dead_letter_count(), 1);
+        assert_eq!(
+            queue.next_due().unwrap().batch.height,
+            Height::new(0, 2).unwrap()
+        );
+    }
+}
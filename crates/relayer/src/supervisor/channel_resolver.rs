@@ -0,0 +1,277 @@
+//! Lazily populated cache resolving a channel end to the connection,
+//! client and counterparty chain it is built upon.
+
+use alloc::collections::btree_map::BTreeMap as HashMap;
+
+use serde::{Deserialize, Serialize};
+use tracing::trace;
+
+use ibc_relayer_types::core::{
+    ics02_client::client_state::ClientState,
+    ics24_host::identifier::{ChainId, ChannelId, ClientId, ConnectionId, PortChannelId, PortId},
+};
+
+use crate::chain::{counterparty::channel_connection_client, handle::ChainHandle};
+use crate::object::{Object, Packet};
+use crate::supervisor::Error;
+
+/// The routing context of a channel end: the connection and client
+/// underlying the channel, and the chain whose headers that client tracks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedChannel {
+    pub connection_id: ConnectionId,
+    pub client_id: ClientId,
+    pub counterparty_chain_id: ChainId,
+}
+
+/// Resolves `(chain, port, channel)` triples into their [`ResolvedChannel`]
+/// context, querying the chain only the first time a channel is seen.
+///
+/// Only channels whose connection is open are cached. The connection and client
+/// underlying such a channel never change, but the identifier of the chain tracked
+/// by the client does when that chain is upgraded to a new revision. Entries are
+/// dropped when the channel is closed, and all the entries of a chain when it
+/// changes revision.
+#[derive(Debug, Default)]
+pub struct ChannelResolver {
+    channels: HashMap<(ChainId, PortChannelId), ResolvedChannel>,
+}
+
+impl ChannelResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the [`ResolvedChannel`] for the given channel on `chain`,
+    /// querying the chain and caching the result if not yet known.
+    pub fn resolve(
+        &mut self,
+        chain: &impl ChainHandle,
+        port_id: &PortId,
+        channel_id: &ChannelId,
+    ) -> Result<ResolvedChannel, Error> {
+        let key = (
+            chain.id(),
+            PortChannelId {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+            },
+        );
+
+        if let Some(resolved) = self.channels.get(&key) {
+            return Ok(resolved.clone());
+        }
+
+        let chan_conn_client = channel_connection_client(chain, port_id, channel_id)?;
+        let connection_open = chan_conn_client.connection.connection_end.is_open();
+
+        let resolved = ResolvedChannel {
+            connection_id: chan_conn_client.connection.connection_id,
+            client_id: chan_conn_client.client.client_id,
+            counterparty_chain_id: chan_conn_client.client.client_state.chain_id(),
+        };
+
+        trace!(
+            chain = %key.0, port = %port_id, channel = %channel_id,
+            connection = %resolved.connection_id, client = %resolved.client_id,
+            counterparty_chain = %resolved.counterparty_chain_id,
+            "resolved channel"
+        );
+
+        // The connection of a channel in the middle of its handshake may not be
+        // open yet, in which case the channel is resolved again next time
+        if connection_open {
+            self.channels.insert(key, resolved.clone());
+        }
+
+        Ok(resolved)
+    }
+
+    /// Build the packet [`Object`] for the given channel end on `src_chain`.
+    pub fn packet_object(
+        &mut self,
+        src_chain: &impl ChainHandle,
+        src_port_id: &PortId,
+        src_channel_id: &ChannelId,
+    ) -> Result<Object, Error> {
+        let resolved = self.resolve(src_chain, src_port_id, src_channel_id)?;
+
+        Ok(Packet {
+            dst_chain_id: resolved.counterparty_chain_id,
+            src_chain_id: src_chain.id(),
+            src_channel_id: src_channel_id.clone(),
+            src_port_id: src_port_id.clone(),
+        }
+        .into())
+    }
+
+    /// Forget the cached context for the given channel,
+    /// eg. because the channel is being closed.
+    pub fn invalidate(&mut self, chain_id: &ChainId, port_id: &PortId, channel_id: &ChannelId) {
+        let key = (
+            chain_id.clone(),
+            PortChannelId {
+                port_id: port_id.clone(),
+                channel_id: channel_id.clone(),
+            },
+        );
+
+        self.channels.remove(&key);
+    }
+
+    /// Forget the cached context of all the channels of the given chain,
+    /// eg. because the chain changed revision.
+    pub fn invalidate_chain(&mut self, chain_id: &ChainId) {
+        self.channels
+            .retain(|(channel_chain_id, _), _| channel_chain_id != chain_id);
+    }
+
+    /// The number of channels currently cached.
+    pub fn len(&self) -> usize {
+        self.channels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.channels.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+
+    use ibc_relayer_types::clients::ics07_tendermint::client_state::test_util::get_dummy_tendermint_client_state;
+    use ibc_relayer_types::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+    use ibc_relayer_types::core::ics03_connection::connection::{
+        ConnectionEnd, State as ConnectionState,
+    };
+    use ibc_relayer_types::core::ics04_channel::channel::{
+        ChannelEnd, Counterparty, Ordering, State as ChannelState,
+    };
+    use ibc_relayer_types::core::ics04_channel::version::Version;
+
+    use crate::chain::handle::MockChainHandle;
+
+    fn chain_with_channel() -> MockChainHandle {
+        chain_with_channel_on(ConnectionState::Open)
+    }
+
+    fn chain_with_channel_on(connection_state: ConnectionState) -> MockChainHandle {
+        let chain = MockChainHandle::new(ChainId::from_string("ibc-0"));
+        let client_id = ClientId::default();
+        let connection_id = ConnectionId::new(0);
+
+        chain.insert_client_state(
+            client_id.clone(),
+            get_dummy_tendermint_client_state(get_dummy_tendermint_header()).into(),
+        );
+        chain.insert_connection(
+            connection_id.clone(),
+            ConnectionEnd::new(
+                connection_state,
+                client_id,
+                Default::default(),
+                vec![],
+                Duration::ZERO,
+            ),
+        );
+        chain.insert_channel(
+            PortId::transfer(),
+            ChannelId::new(0),
+            ChannelEnd::new(
+                ChannelState::Open,
+                Ordering::Unordered,
+                Counterparty::default(),
+                vec![connection_id],
+                Version::default(),
+            ),
+        );
+
+        chain
+    }
+
+    #[test]
+    fn resolve_queries_the_chain_once() {
+        let chain = chain_with_channel();
+        let mut resolver = ChannelResolver::new();
+
+        let resolved = resolver
+            .resolve(&chain, &PortId::transfer(), &ChannelId::new(0))
+            .unwrap();
+
+        assert_eq!(resolved.connection_id, ConnectionId::new(0));
+        assert_eq!(resolved.client_id, ClientId::default());
+        assert_eq!(
+            resolved.counterparty_chain_id,
+            ChainId::from_string("test-chain-01")
+        );
+        assert_eq!(chain.calls("query_channel"), 1);
+
+        // Cache hit
+        let cached = resolver
+            .resolve(&chain, &PortId::transfer(), &ChannelId::new(0))
+            .unwrap();
+
+        assert_eq!(cached, resolved);
+        assert_eq!(chain.calls("query_channel"), 1);
+        assert_eq!(resolver.len(), 1);
+    }
+
+    #[test]
+    fn unknown_channels_are_not_cached() {
+        let chain = chain_with_channel();
+        let mut resolver = ChannelResolver::new();
+
+        for _ in 0..2 {
+            assert!(resolver
+                .resolve(&chain, &PortId::transfer(), &ChannelId::new(1))
+                .is_err());
+        }
+
+        assert_eq!(chain.calls("query_channel"), 2);
+        assert!(resolver.is_empty());
+    }
+
+    #[test]
+    fn channels_on_unopened_connections_are_not_cached() {
+        let chain = chain_with_channel_on(ConnectionState::TryOpen);
+        let mut resolver = ChannelResolver::new();
+
+        for _ in 0..2 {
+            let resolved = resolver
+                .resolve(&chain, &PortId::transfer(), &ChannelId::new(0))
+                .unwrap();
+
+            assert_eq!(resolved.connection_id, ConnectionId::new(0));
+        }
+
+        assert_eq!(chain.calls("query_channel"), 2);
+        assert!(resolver.is_empty());
+    }
+
+    #[test]
+    fn invalidate_forgets_the_channel() {
+        let chain = chain_with_channel();
+        let mut resolver = ChannelResolver::new();
+
+        resolver
+            .resolve(&chain, &PortId::transfer(), &ChannelId::new(0))
+            .unwrap();
+
+        resolver.invalidate(&chain.id(), &PortId::transfer(), &ChannelId::new(0));
+        assert!(resolver.is_empty());
+
+        resolver
+            .resolve(&chain, &PortId::transfer(), &ChannelId::new(0))
+            .unwrap();
+        assert_eq!(chain.calls("query_channel"), 2);
+
+        resolver.invalidate_chain(&ChainId::from_string("ibc-1"));
+        assert_eq!(resolver.len(), 1);
+
+        resolver.invalidate_chain(&chain.id());
+        assert!(resolver.is_empty());
+    }
+}
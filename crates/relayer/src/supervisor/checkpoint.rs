@@ -0,0 +1,216 @@
+//! Persistence of the last height processed by the supervisor for each chain,
+//! so that the blocks committed while Hermes was stopped can be processed on restart.
+
+use alloc::collections::btree_map::BTreeMap as HashMap;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use flex_error::{define_error, TraceError};
+use tracing::debug;
+
+use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+use ibc_relayer_types::Height;
+
+use crate::config::CheckpointConfig;
+
+const DEFAULT_CHECKPOINT_FILE: &str = ".hermes/checkpoints.json";
+
+/// Minimum delay between two writes of the checkpoint file.
+///
+/// At most the blocks processed during this delay are processed again on restart.
+const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+define_error! {
+    CheckpointError {
+        Io
+            { path: PathBuf }
+            [ TraceError<std::io::Error> ]
+            |e| { format!("I/O error on checkpoint file {}", e.path.display()) },
+
+        Decode
+            { path: PathBuf }
+            [ TraceError<serde_json::Error> ]
+            |e| { format!("failed to decode checkpoint file {}", e.path.display()) },
+
+        HomeLocationUnavailable
+            |_| { "home location is unavailable" },
+    }
+}
+
+/// The last height fully processed for each chain, as persisted in the checkpoint file.
+#[derive(Debug)]
+pub struct Checkpoints {
+    path: PathBuf,
+    heights: HashMap<ChainId, Height>,
+    /// Whether some heights changed since the checkpoints were last saved
+    dirty: bool,
+    last_saved: Option<Instant>,
+}
+
+impl Checkpoints {
+    /// Load the checkpoints from the file given in the configuration,
+    /// starting with no checkpoint if the file does not exist yet.
+    pub fn load(config: &CheckpointConfig) -> Result<Self, CheckpointError> {
+        let path = match &config.path {
+            Some(path) => path.clone(),
+            None => dirs_next::home_dir()
+                .ok_or_else(CheckpointError::home_location_unavailable)?
+                .join(DEFAULT_CHECKPOINT_FILE),
+        };
+
+        let heights = match fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map_err(|e| CheckpointError::decode(path.clone(), e))?,
+            Err(e) if e.kind() == ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(CheckpointError::io(path, e)),
+        };
+
+        Ok(Self {
+            path,
+            heights,
+            dirty: false,
+            last_saved: None,
+        })
+    }
+
+    /// The last height processed for the given chain, if any.
+    pub fn get(&self, chain_id: &ChainId) -> Option<Height> {
+        self.heights.get(chain_id).copied()
+    }
+
+    /// Record that all the blocks of the given chain up to `height` were processed.
+    ///
+    /// Heights lower than the current checkpoint of the chain are ignored.
+    /// Returns the checkpoints to save if they changed and were not saved for
+    /// at least [`SAVE_INTERVAL`], so that they can be written to disk by the
+    /// caller without holding a lock on them.
+    pub fn record(&mut self, chain_id: &ChainId, height: Height) -> Option<CheckpointsSnapshot> {
+        self.record_at(chain_id, height, Instant::now())
+    }
+
+    fn record_at(
+        &mut self,
+        chain_id: &ChainId,
+        height: Height,
+        now: Instant,
+    ) -> Option<CheckpointsSnapshot> {
+        if !matches!(self.heights.get(chain_id), Some(last) if *last >= height) {
+            self.heights.insert(chain_id.clone(), height);
+            self.dirty = true;
+        }
+
+        let due = match self.last_saved {
+            Some(last_saved) => now.saturating_duration_since(last_saved) >= SAVE_INTERVAL,
+            None => true,
+        };
+
+        if !self.dirty || !due {
+            return None;
+        }
+
+        self.dirty = false;
+        self.last_saved = Some(now);
+
+        Some(CheckpointsSnapshot {
+            path: self.path.clone(),
+            heights: self.heights.clone(),
+        })
+    }
+}
+
+/// A copy of the checkpoints, to be written to the checkpoint file.
+#[derive(Debug)]
+pub struct CheckpointsSnapshot {
+    path: PathBuf,
+    heights: HashMap<ChainId, Height>,
+}
+
+impl CheckpointsSnapshot {
+    /// Write the checkpoints to a temporary file which is then renamed,
+    /// so that the checkpoint file is never left half-written.
+    pub fn save(&self) -> Result<(), CheckpointError> {
+        let io_error = |e| CheckpointError::io(self.path.clone(), e);
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent).map_err(io_error)?;
+        }
+
+        let bytes = serde_json::to_vec_pretty(&self.heights)
+            .map_err(|e| CheckpointError::decode(self.path.clone(), e))?;
+
+        let tmp_path = self.path.with_extension("json.tmp");
+        fs::write(&tmp_path, bytes).map_err(io_error)?;
+        fs::rename(&tmp_path, &self.path).map_err(io_error)?;
+
+        debug!(path = %self.path.display(), "saved checkpoints");
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn height(height: u64) -> Height {
+        Height::new(0, height).unwrap()
+    }
+
+    fn checkpoints(path: PathBuf) -> Checkpoints {
+        Checkpoints::load(&CheckpointConfig {
+            enabled: true,
+            path: Some(path),
+            max_catch_up_blocks: 1000,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn save_at_most_once_per_interval() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let mut checkpoints = checkpoints(PathBuf::from("checkpoints.json"));
+        let start = Instant::now();
+
+        assert!(checkpoints.record_at(&chain_id, height(1), start).is_some());
+        assert!(checkpoints.record_at(&chain_id, height(2), start).is_none());
+        assert_eq!(checkpoints.get(&chain_id), Some(height(2)));
+
+        let later = start + SAVE_INTERVAL;
+        let snapshot = checkpoints.record_at(&chain_id, height(2), later).unwrap();
+        assert_eq!(snapshot.heights.get(&chain_id), Some(&height(2)));
+
+        // Nothing changed since the last save
+        let much_later = later + SAVE_INTERVAL;
+        assert!(checkpoints
+            .record_at(&chain_id, height(2), much_later)
+            .is_none());
+    }
+
+    #[test]
+    fn lower_heights_are_ignored() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let mut checkpoints = checkpoints(PathBuf::from("checkpoints.json"));
+        let now = Instant::now();
+
+        checkpoints.record_at(&chain_id, height(5), now);
+        checkpoints.record_at(&chain_id, height(3), now);
+
+        assert_eq!(checkpoints.get(&chain_id), Some(height(5)));
+    }
+
+    #[test]
+    fn load_saved_checkpoints() {
+        let dir = std::env::temp_dir().join(format!("hermes-checkpoints-{}", std::process::id()));
+        let path = dir.join("checkpoints.json");
+        let chain_id = ChainId::from_string("ibc-0");
+
+        let mut saved = checkpoints(path.clone());
+        saved.record(&chain_id, height(7)).unwrap().save().unwrap();
+
+        assert_eq!(checkpoints(path).get(&chain_id), Some(height(7)));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
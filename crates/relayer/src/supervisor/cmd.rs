@@ -5,4 +5,6 @@ use super::dump_state::SupervisorState;
 #[derive(Clone, Debug)]
 pub enum SupervisorCmd {
     DumpState(Sender<SupervisorState>),
+    ReprocessDeadLetters(Sender<usize>),
+    ResumeWorkers(Sender<usize>),
 }
@@ -8,7 +8,7 @@ use tracing::info;
 
 use crate::{
     object::{Object, ObjectType},
-    worker::{WorkerData, WorkerHandle, WorkerId},
+    worker::{WorkerData, WorkerHandle, WorkerId, WorkerStatsSnapshot},
 };
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
@@ -16,11 +16,23 @@ pub struct WorkerDesc {
     pub id: WorkerId,
     pub object: Object,
     pub data: Option<WorkerData>,
+    #[serde(default)]
+    pub stats: WorkerStatsSnapshot,
 }
 
 impl WorkerDesc {
-    pub fn new(id: WorkerId, object: Object, data: Option<WorkerData>) -> Self {
-        Self { id, object, data }
+    pub fn new(
+        id: WorkerId,
+        object: Object,
+        data: Option<WorkerData>,
+        stats: WorkerStatsSnapshot,
+    ) -> Self {
+        Self {
+            id,
+            object,
+            data,
+            stats,
+        }
     }
 }
 
@@ -38,7 +50,14 @@ impl SupervisorState {
         chains.sort();
 
         let workers = workers
-            .map(|h| WorkerDesc::new(h.id(), h.object().clone(), h.data().cloned()))
+            .map(|h| {
+                WorkerDesc::new(
+                    h.id(),
+                    h.object().clone(),
+                    h.data().cloned(),
+                    h.stats().snapshot(),
+                )
+            })
             .into_group_map_by(|desc| desc.object.object_type())
             .into_iter()
             .update(|(_, os)| os.sort_by_key(|desc| desc.object.short_name()))
@@ -47,6 +66,11 @@ impl SupervisorState {
         Self { chains, workers }
     }
 
+    /// All the workers, regardless of their type.
+    pub fn all_workers(&self) -> impl Iterator<Item = &WorkerDesc> {
+        self.workers.values().flatten()
+    }
+
     pub fn print_info(&self) {
         self.to_string()
             .split('\n')
@@ -69,6 +93,14 @@ impl Display for SupervisorState {
                 {
                     writeln!(f, "    | misbehaviour: {misbehaviour}, refresh: {refresh}")?;
                 }
+                writeln!(
+                    f,
+                    "    | last activity: {}, retries: {}, queued operational data: {}, pending txs: {}",
+                    desc.stats.last_activity.as_deref().unwrap_or("none"),
+                    desc.stats.retries,
+                    desc.stats.queued_operational_data,
+                    desc.stats.pending_txs
+                )?;
             }
         }
 
@@ -0,0 +1,267 @@
+//! Dispatch of the `SendPacket` events of a channel to its packet worker
+//! in sequence order, across event batches.
+
+use alloc::collections::btree_map::BTreeMap as HashMap;
+use alloc::collections::BTreeMap;
+use core::mem;
+
+use tracing::{trace, warn};
+
+use ibc_relayer_types::{core::ics04_channel::packet::Sequence, events::IbcEvent};
+
+use crate::{
+    event::IbcEventWithHeight,
+    object::{Object, Packet},
+    telemetry,
+};
+
+/// Holds back the `SendPacket` events of a channel which arrive ahead of
+/// the next expected sequence, until the missing events arrive.
+///
+/// A gap which is not filled within `gap_tolerance` batches is skipped:
+/// the events held back are dispatched anyway, and the missing packets
+/// are left to packet clearing.
+#[derive(Debug)]
+pub struct SequenceOrdering {
+    gap_tolerance: u64,
+    paths: HashMap<Packet, PathOrdering>,
+}
+
+#[derive(Debug, Default)]
+struct PathOrdering {
+    /// The sequence of the next `SendPacket` event to dispatch,
+    /// unknown until the first event on the path is seen.
+    next: Option<Sequence>,
+    /// Events held back because of a gap, by sequence.
+    pending: BTreeMap<Sequence, IbcEventWithHeight>,
+    /// Number of batches since the gap in front of `pending` appeared.
+    stalled_batches: u64,
+}
+
+impl SequenceOrdering {
+    pub fn new(gap_tolerance: u64) -> Self {
+        Self {
+            gap_tolerance,
+            paths: HashMap::new(),
+        }
+    }
+
+    /// Reorder the `SendPacket` events collected from a batch for each packet
+    /// object, holding back those past a gap, and add the events held back
+    /// for longer than the gap tolerance.
+    pub fn apply(&mut self, per_object: &mut HashMap<Object, Vec<IbcEventWithHeight>>) {
+        for (object, events) in per_object.iter_mut() {
+            let Object::Packet(path) = object else {
+                continue;
+            };
+
+            let ordering = self.paths.entry(path.clone()).or_default();
+            *events = ordering.order(path, mem::take(events));
+        }
+
+        for (path, ordering) in self.paths.iter_mut() {
+            if ordering.pending.is_empty() {
+                continue;
+            }
+
+            ordering.stalled_batches += 1;
+
+            if ordering.stalled_batches <= self.gap_tolerance {
+                continue;
+            }
+
+            let released = ordering.release();
+
+            warn!(
+                path = %path.short_name(),
+                "no SendPacket event received for sequences {} to {} after {} batches, \
+                dispatching {} events held back; the missing packets will be relayed \
+                by packet clearing",
+                released.first_missing,
+                released.last_missing,
+                self.gap_tolerance,
+                released.events.len(),
+            );
+
+            per_object
+                .entry(Object::Packet(path.clone()))
+                .or_default()
+                .extend(released.events);
+        }
+    }
+}
+
+struct Released {
+    first_missing: Sequence,
+    last_missing: Sequence,
+    events: Vec<IbcEventWithHeight>,
+}
+
+impl PathOrdering {
+    fn order(&mut self, path: &Packet, events: Vec<IbcEventWithHeight>) -> Vec<IbcEventWithHeight> {
+        let mut ordered = Vec::with_capacity(events.len());
+
+        for event in events {
+            let sequence = match &event.event {
+                IbcEvent::SendPacket(send_packet) => send_packet.packet.sequence,
+                _ => {
+                    ordered.push(event);
+                    continue;
+                }
+            };
+
+            match self.next {
+                Some(next) if sequence > next => {
+                    trace!(
+                        path = %path.short_name(),
+                        "holding back SendPacket event with sequence {} until sequence {} is received",
+                        sequence,
+                        next
+                    );
+
+                    telemetry!(
+                        out_of_order_send_packet_events,
+                        &path.src_chain_id,
+                        &path.src_channel_id,
+                        &path.src_port_id,
+                        &path.dst_chain_id
+                    );
+
+                    self.pending.insert(sequence, event);
+                }
+                Some(next) if sequence < next => {
+                    // Arrived after a gap it belonged to was skipped, or a duplicate:
+                    // the worker discards the packets which are already relayed.
+                    telemetry!(
+                        out_of_order_send_packet_events,
+                        &path.src_chain_id,
+                        &path.src_channel_id,
+                        &path.src_port_id,
+                        &path.dst_chain_id
+                    );
+
+                    ordered.push(event);
+                }
+                _ => {
+                    ordered.push(event);
+                    self.next = Some(sequence.increment());
+                    self.stalled_batches = 0;
+                    self.drain_into(&mut ordered);
+                }
+            }
+        }
+
+        ordered
+    }
+
+    /// Move the events held back which are now contiguous to `ordered`.
+    fn drain_into(&mut self, ordered: &mut Vec<IbcEventWithHeight>) {
+        while let Some(next) = self.next {
+            match self.pending.remove(&next) {
+                Some(event) => {
+                    ordered.push(event);
+                    self.next = Some(next.increment());
+                }
+                None => break,
+            }
+        }
+    }
+
+    /// Give up on the gap and release all the events held back.
+    fn release(&mut self) -> Released {
+        let pending = mem::take(&mut self.pending);
+
+        let first_missing = self.next.unwrap_or_default();
+        let last_missing = pending
+            .keys()
+            .next()
+            .map(|first_pending| u64::from(*first_pending).saturating_sub(1))
+            .unwrap_or_default()
+            .into();
+
+        if let Some(last_pending) = pending.keys().next_back() {
+            self.next = Some(last_pending.increment());
+        }
+
+        self.stalled_batches = 0;
+
+        Released {
+            first_missing,
+            last_missing,
+            events: pending.into_values().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::Packet as IbcPacket;
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+    use ibc_relayer_types::Height;
+
+    fn path() -> Packet {
+        Packet {
+            dst_chain_id: ChainId::from_string("ibc-1"),
+            src_chain_id: ChainId::from_string("ibc-0"),
+            src_channel_id: ChannelId::new(0),
+            src_port_id: PortId::transfer(),
+        }
+    }
+
+    fn send_packet(sequence: u64) -> IbcEventWithHeight {
+        let packet = IbcPacket {
+            sequence: sequence.into(),
+            ..Default::default()
+        };
+
+        IbcEventWithHeight::new(
+            IbcEvent::SendPacket(SendPacket { packet }),
+            Height::new(0, 1).unwrap(),
+        )
+    }
+
+    fn apply(ordering: &mut SequenceOrdering, sequences: &[u64]) -> Vec<u64> {
+        let mut per_object = HashMap::new();
+        per_object.insert(
+            Object::Packet(path()),
+            sequences.iter().copied().map(send_packet).collect(),
+        );
+
+        ordering.apply(&mut per_object);
+
+        per_object
+            .remove(&Object::Packet(path()))
+            .unwrap_or_default()
+            .into_iter()
+            .map(|event| match event.event {
+                IbcEvent::SendPacket(send_packet) => send_packet.packet.sequence.into(),
+                _ => unreachable!(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn reorders_across_batches() {
+        let mut ordering = SequenceOrdering::new(3);
+
+        assert_eq!(apply(&mut ordering, &[1, 2]), vec![1, 2]);
+        assert_eq!(apply(&mut ordering, &[4, 5]), Vec::<u64>::new());
+        assert_eq!(apply(&mut ordering, &[3, 6]), vec![3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn skips_gap_after_tolerance() {
+        let mut ordering = SequenceOrdering::new(2);
+
+        assert_eq!(apply(&mut ordering, &[1]), vec![1]);
+        assert_eq!(apply(&mut ordering, &[3]), Vec::<u64>::new());
+        assert_eq!(apply(&mut ordering, &[]), Vec::<u64>::new());
+        assert_eq!(apply(&mut ordering, &[]), vec![3]);
+
+        // A late event is still dispatched, and does not hold back the next ones
+        assert_eq!(apply(&mut ordering, &[2, 4]), vec![2, 4]);
+    }
+}
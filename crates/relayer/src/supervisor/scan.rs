@@ -816,6 +816,7 @@ fn query_all_clients<Chain: ChainHandle>(
 ) -> Result<Vec<IdentifiedAnyClientState>, Error> {
     let clients_req = QueryClientStatesRequest {
         pagination: Some(PageRequest::all()),
+        query_height: QueryHeight::Latest,
     };
 
     chain.query_clients(clients_req).map_err(Error::query)
@@ -894,7 +895,7 @@ fn init_telemetry(
     let mut tx_worker_enabled = false;
 
     let clear_packets = config.mode.packets.enabled
-        && (config.mode.packets.clear_on_start || config.mode.packets.clear_interval > 0);
+        && (config.mode.packets.clear_on_start || config.clear_interval(chain_id, channel_id) > 0);
 
     if config.mode.packets.enabled {
         tx_worker_enabled = true;
@@ -4,22 +4,29 @@ use std::str::FromStr;
 
 use core::time::Duration;
 
-use flex_error::{define_error, DetailOnly};
+use flex_error::{define_error, DetailOnly, TraceError};
 use ibc_proto::cosmos::base::v1beta1::Coin;
 use ibc_proto::google::protobuf::Any;
 use ibc_relayer_types::applications::transfer::error::Error as Ics20Error;
 use ibc_relayer_types::applications::transfer::msgs::transfer::MsgTransfer;
-use ibc_relayer_types::applications::transfer::Amount;
+use ibc_relayer_types::applications::transfer::packet::PacketData;
+use ibc_relayer_types::applications::transfer::{Amount, PrefixedCoin, PrefixedDenom};
+use ibc_relayer_types::core::ics02_client::height::Height;
 use ibc_relayer_types::core::ics04_channel::timeout::TimeoutHeight;
 use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
 use ibc_relayer_types::events::IbcEvent;
 use ibc_relayer_types::signer::Signer;
 use ibc_relayer_types::timestamp::{Timestamp, TimestampOverflowError};
 use ibc_relayer_types::tx_msg::Msg;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use tracing::warn;
 
 use crate::chain::endpoint::ChainStatus;
 use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{QueryHeight, QueryHostConsensusStateRequest};
 use crate::chain::tracking::TrackedMsgs;
+use crate::config::ChainConfig;
 use crate::error::Error;
 use crate::event::IbcEventWithHeight;
 
@@ -68,9 +75,76 @@ define_error! {
 
         ZeroTimeout
             | _ | { "packet timeout height and packet timeout timestamp cannot both be 0" },
+
+        PacketDataEncode
+            [ TraceError<serde_json::Error> ]
+            | _ | { "failed to encode packet data" },
+
+        PacketDataTooLarge
+            { chain_id: ChainId, size: usize, max_size: usize }
+            | e | {
+                format!("packet data of {} bytes exceeds the maximum transaction size of {} bytes on destination chain {}",
+                    e.size, e.max_size, e.chain_id)
+            },
+
+        TimeoutHeightElapsed
+            { chain_id: ChainId, timeout_height: TimeoutHeight, latest_height: Height }
+            | e | {
+                format!("packet timeout height {} has already been reached on destination chain {}, whose latest height is {}",
+                    e.timeout_height, e.chain_id, e.latest_height)
+            },
+
+        EscrowAddress
+            [ TraceError<bech32::Error> ]
+            | _ | { "failed to encode escrow address" },
+
+        TimeoutTimestampTooClose
+            { chain_id: ChainId, timeout_timestamp: Timestamp, latest_timestamp: Timestamp, clock_drift: Duration }
+            | e | {
+                format!("packet timeout timestamp {} is within the clock drift of {:?} of the latest timestamp {} on destination chain {}",
+                    e.timeout_timestamp, e.clock_drift, e.latest_timestamp, e.chain_id)
+            },
+
+        InvalidDenom
+            { denom: String }
+            | e | {
+                format!("invalid denomination {}: it must start with a letter, followed by 2 to 127 letters, digits or any of '/:._-'",
+                    e.denom)
+            },
+
+        ZeroAmount
+            | _ | { "the amount to transfer must be greater than zero" },
     }
 }
 
+/// The version of ICS-20 from which escrow addresses are derived.
+const ICS20_VERSION: &str = "ics20-1";
+
+/// Derive the address of the account in which the ICS-20 module escrows
+/// the tokens sent over the given channel, following `GetEscrowAddress` in ibc-go:
+/// the first 20 bytes of `sha256("ics20-1" || 0x00 || "<port_id>/<channel_id>")`.
+pub fn escrow_address(
+    account_prefix: &str,
+    port_id: &PortId,
+    channel_id: &ChannelId,
+) -> Result<String, TransferError> {
+    use bech32::ToBase32;
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(ICS20_VERSION.as_bytes());
+    hasher.update([0]);
+    hasher.update(format!("{port_id}/{channel_id}").as_bytes());
+    let hash = hasher.finalize();
+
+    bech32::encode(
+        account_prefix,
+        hash[..20].to_base32(),
+        bech32::Variant::Bech32,
+    )
+    .map_err(TransferError::escrow_address)
+}
+
 #[derive(Copy, Clone)]
 pub struct TransferTimeout {
     pub timeout_height: TimeoutHeight,
@@ -116,6 +190,150 @@ impl TransferTimeout {
     }
 }
 
+/// How long after being sent the transfers without an explicit timeout expire.
+pub const DEFAULT_TIMEOUT_DURATION: Duration = Duration::from_secs(10 * 60);
+
+/// Number of blocks over which the block time of the destination chain is measured.
+const BLOCK_TIME_SAMPLE_SIZE: u64 = 100;
+
+/**
+   The timeout height offset and duration to use for a transfer to the given
+   chain when none is specified: the packet expires after
+   [`DEFAULT_TIMEOUT_DURATION`], or after the number of blocks the destination
+   chain produces in that duration, given its average block time over its
+   last blocks, whichever comes first.
+
+   If the block time of the destination chain cannot be measured, eg. because
+   it only produced a single block or its past header cannot be queried, no
+   timeout height is set and the packet only expires after the duration.
+*/
+pub fn default_transfer_timeout<DstChain: ChainHandle>(
+    dst_chain: &DstChain,
+    status: &ChainStatus,
+) -> (u64, Duration) {
+    let sample_size = BLOCK_TIME_SAMPLE_SIZE.min(status.height.revision_height().saturating_sub(1));
+
+    let elapsed = match status.height - sample_size {
+        Ok(past_height) if sample_size > 0 => {
+            match dst_chain.query_host_consensus_state(QueryHostConsensusStateRequest {
+                height: QueryHeight::Specific(past_height),
+            }) {
+                Ok(consensus_state) => status
+                    .timestamp
+                    .duration_since(&consensus_state.timestamp()),
+                Err(e) => {
+                    warn!(
+                        chain = %dst_chain.id(),
+                        "failed to measure the block time, the transfer will only time out after {:?}: {}",
+                        DEFAULT_TIMEOUT_DURATION, e
+                    );
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
+    let timeout_height_offset = elapsed
+        .map(|elapsed| blocks_in(DEFAULT_TIMEOUT_DURATION, elapsed, sample_size))
+        .unwrap_or(0);
+
+    (timeout_height_offset, DEFAULT_TIMEOUT_DURATION)
+}
+
+/// The number of blocks produced in `duration`, given that `block_count`
+/// blocks were produced in `elapsed`, rounded up.
+fn blocks_in(duration: Duration, elapsed: Duration, block_count: u64) -> u64 {
+    if elapsed.is_zero() {
+        return 0;
+    }
+
+    let blocks = duration.as_secs_f64() * block_count as f64 / elapsed.as_secs_f64();
+    blocks.ceil() as u64
+}
+
+/// The denominations accepted by the Cosmos SDK, see `ValidateDenom` in `types/coin.go`.
+static DENOM_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^[a-zA-Z][a-zA-Z0-9/:._-]{2,127}$").unwrap());
+
+/**
+   Check that a packet built from the given transfer can be relayed to the
+   destination chain before submitting it, so that no fees are spent on
+   a transfer which is bound to time out or be rejected:
+
+   - the denomination must be a valid Cosmos SDK denomination,
+   - the amount must be positive and the receiver must not be empty,
+   - the packet data must fit within the destination's maximum transaction size,
+   - the timeout height must not have been reached on the destination yet,
+   - the timeout timestamp must be further in the future than the destination's clock drift.
+*/
+pub fn validate_transfer_packet(
+    packet_data: &PacketData,
+    timeout: &TransferTimeout,
+    destination_chain_status: &ChainStatus,
+    destination_chain_config: &ChainConfig,
+) -> Result<(), TransferError> {
+    let chain_id = &destination_chain_config.id;
+
+    let denom = packet_data.token.denom.to_string();
+    if !DENOM_REGEX.is_match(&denom) {
+        return Err(TransferError::invalid_denom(denom));
+    }
+
+    if packet_data.token.amount == Amount::from(0) {
+        return Err(TransferError::zero_amount());
+    }
+
+    if packet_data.receiver.as_ref().trim().is_empty() {
+        return Err(TransferError::receiver_address(SignerError::empty_signer()));
+    }
+
+    let size = serde_json::to_vec(packet_data)
+        .map_err(TransferError::packet_data_encode)?
+        .len();
+
+    let max_size = destination_chain_config.max_tx_size.to_usize();
+
+    if size > max_size {
+        return Err(TransferError::packet_data_too_large(
+            chain_id.clone(),
+            size,
+            max_size,
+        ));
+    }
+
+    if timeout
+        .timeout_height
+        .has_expired(destination_chain_status.height)
+    {
+        return Err(TransferError::timeout_height_elapsed(
+            chain_id.clone(),
+            timeout.timeout_height,
+            destination_chain_status.height,
+        ));
+    }
+
+    if timeout.timeout_timestamp != Timestamp::none() {
+        let clock_drift = destination_chain_config.clock_drift;
+
+        let remaining = timeout
+            .timeout_timestamp
+            .duration_since(&destination_chain_status.timestamp)
+            .unwrap_or_default();
+
+        if remaining <= clock_drift {
+            return Err(TransferError::timeout_timestamp_too_close(
+                chain_id.clone(),
+                timeout.timeout_timestamp,
+                destination_chain_status.timestamp,
+                clock_drift,
+            ));
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Clone, Debug)]
 pub struct TransferOptions {
     pub src_port_id: PortId,
@@ -123,8 +341,11 @@ pub struct TransferOptions {
     pub amount: Amount,
     pub denom: String,
     pub receiver: Option<String>,
-    pub timeout_height_offset: u64,
-    pub timeout_duration: Duration,
+    /// Timeout in number of blocks, 0 for none. When neither this nor the timeout
+    /// duration is given, the [`default_transfer_timeout`] is used.
+    pub timeout_height_offset: Option<u64>,
+    /// Timeout duration, zero for none.
+    pub timeout_duration: Option<Duration>,
     pub number_msgs: usize,
     pub memo: Option<String>,
 }
@@ -173,10 +394,35 @@ pub fn build_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHandle>(
         .query_application_status()
         .map_err(TransferError::relayer)?;
 
+    let (timeout_height_offset, timeout_duration) =
+        match (opts.timeout_height_offset, opts.timeout_duration) {
+            (None, None) => default_transfer_timeout(dst_chain, &destination_chain_status),
+            (height_offset, duration) => (height_offset.unwrap_or(0), duration.unwrap_or_default()),
+        };
+
     let timeout = TransferTimeout::new(
-        opts.timeout_height_offset,
-        opts.timeout_duration,
+        timeout_height_offset,
+        timeout_duration,
+        &destination_chain_status,
+    )?;
+
+    let packet_data = PacketData {
+        token: PrefixedCoin {
+            denom: PrefixedDenom::from_str(&opts.denom).map_err(TransferError::token_transfer)?,
+            amount: opts.amount,
+        },
+        sender: sender.clone(),
+        receiver: receiver.clone(),
+        memo: opts.memo.clone(),
+    };
+
+    let destination_chain_config = dst_chain.config().map_err(TransferError::relayer)?;
+
+    validate_transfer_packet(
+        &packet_data,
+        &timeout,
         &destination_chain_status,
+        &destination_chain_config,
     )?;
 
     let message = build_transfer_message(
@@ -232,3 +478,118 @@ pub fn build_and_send_transfer_messages<SrcChain: ChainHandle, DstChain: ChainHa
     let msgs = build_transfer_messages(src_chain, dst_chain, opts)?;
     send_messages(src_chain, msgs)
 }
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+    use std::str::FromStr;
+
+    use ibc_relayer_types::applications::transfer::packet::PacketData;
+    use ibc_relayer_types::applications::transfer::{Amount, PrefixedCoin, PrefixedDenom};
+    use ibc_relayer_types::core::ics24_host::identifier::ChainId;
+    use ibc_relayer_types::signer::Signer;
+    use ibc_relayer_types::timestamp::Timestamp;
+    use ibc_relayer_types::Height;
+
+    use super::{
+        blocks_in, default_transfer_timeout, validate_transfer_packet, TransferError,
+        TransferErrorDetail, TransferTimeout, DEFAULT_TIMEOUT_DURATION,
+    };
+    use crate::chain::endpoint::ChainStatus;
+    use crate::chain::handle::MockChainHandle;
+    use crate::config::{load, ChainConfig};
+
+    fn chain_config() -> ChainConfig {
+        let path = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/config/fixtures/relayer_conf_example.toml"
+        );
+
+        load(path).expect("could not parse config").chains.remove(0)
+    }
+
+    fn packet_data(denom: &str, amount: u64) -> PacketData {
+        PacketData {
+            token: PrefixedCoin {
+                denom: PrefixedDenom::from_str(denom).unwrap(),
+                amount: amount.into(),
+            },
+            sender: Signer::dummy(),
+            receiver: Signer::dummy(),
+            memo: None,
+        }
+    }
+
+    fn validate(packet_data: &PacketData) -> Result<(), TransferError> {
+        let status = ChainStatus {
+            height: Height::new(1, 200).unwrap(),
+            timestamp: Timestamp::now(),
+        };
+        let timeout = TransferTimeout::new(100, DEFAULT_TIMEOUT_DURATION, &status).unwrap();
+
+        validate_transfer_packet(packet_data, &timeout, &status, &chain_config())
+    }
+
+    #[test]
+    fn blocks_in_duration() {
+        let ten_minutes = Duration::from_secs(600);
+
+        assert_eq!(blocks_in(ten_minutes, Duration::from_secs(600), 100), 100);
+        assert_eq!(blocks_in(ten_minutes, Duration::from_secs(700), 100), 86);
+        assert_eq!(blocks_in(ten_minutes, Duration::from_secs(6), 1), 100);
+        assert_eq!(blocks_in(ten_minutes, Duration::ZERO, 100), 0);
+    }
+
+    #[test]
+    fn default_timeout_falls_back_to_the_duration() {
+        let dst_chain = MockChainHandle::new(ChainId::from_string("ibc-1"));
+        let status = ChainStatus {
+            height: Height::new(1, 200).unwrap(),
+            timestamp: Timestamp::now(),
+        };
+
+        // The mock chain cannot be queried for its past headers
+        assert_eq!(
+            default_transfer_timeout(&dst_chain, &status),
+            (0, DEFAULT_TIMEOUT_DURATION)
+        );
+    }
+
+    #[test]
+    fn validate_valid_packet() {
+        assert!(validate(&packet_data("uatom", 100)).is_ok());
+        assert!(validate(&packet_data("transfer/channel-0/uatom", 1)).is_ok());
+    }
+
+    #[test]
+    fn validate_rejects_bad_denom() {
+        for denom in ["1uatom", "ua", "u atom"] {
+            assert!(matches!(
+                validate(&packet_data(denom, 100)),
+                Err(TransferError(TransferErrorDetail::InvalidDenom(_), _))
+            ));
+        }
+    }
+
+    #[test]
+    fn validate_rejects_zero_amount() {
+        assert!(matches!(
+            validate(&packet_data("uatom", 0)),
+            Err(TransferError(TransferErrorDetail::ZeroAmount(_), _))
+        ));
+
+        // Negative amounts cannot be represented
+        assert!(Amount::from_str("-1").is_err());
+    }
+
+    #[test]
+    fn validate_rejects_empty_receiver() {
+        let mut packet_data = packet_data("uatom", 100);
+        packet_data.receiver = serde_json::from_str("\" \"").unwrap();
+
+        assert!(matches!(
+            validate(&packet_data),
+            Err(TransferError(TransferErrorDetail::ReceiverAddress(_), _))
+        ));
+    }
+}
@@ -9,6 +9,7 @@ pub mod lock;
 pub mod pretty;
 pub mod profiling;
 pub mod queue;
+pub mod rate_limit;
 pub mod retry;
 pub mod stream;
 pub mod task;
@@ -0,0 +1,85 @@
+use core::time::Duration;
+use std::time::Instant;
+
+/// A token bucket rate limiter.
+///
+/// The bucket holds at most `capacity` tokens and is refilled at a rate
+/// of `refill_rate` tokens per second. Each successful call to
+/// [`TokenBucket::try_acquire`] consumes a single token.
+#[derive(Clone, Debug)]
+pub struct TokenBucket {
+    capacity: f64,
+    refill_rate: f64,
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    /// Create a new, full, token bucket.
+    ///
+    /// ## Panics
+    /// If `refill_rate` is not strictly positive.
+    pub fn new(capacity: u32, refill_rate: f64) -> Self {
+        assert!(refill_rate > 0.0, "refill rate must be strictly positive");
+
+        let capacity = f64::from(capacity.max(1));
+
+        Self {
+            capacity,
+            refill_rate,
+            tokens: capacity,
+            last_refill: Instant::now(),
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.last_refill);
+        self.tokens = (self.tokens + elapsed.as_secs_f64() * self.refill_rate).min(self.capacity);
+        self.last_refill = now;
+    }
+
+    /// Try to consume a token without blocking.
+    ///
+    /// Returns `Ok(())` if a token was consumed, otherwise returns
+    /// how long to wait until a token becomes available.
+    pub fn try_acquire(&mut self) -> Result<(), Duration> {
+        self.refill(Instant::now());
+
+        if self.tokens >= 1.0 {
+            self.tokens -= 1.0;
+            Ok(())
+        } else {
+            let missing = 1.0 - self.tokens;
+            Err(Duration::from_secs_f64(missing / self.refill_rate))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use super::TokenBucket;
+
+    #[test]
+    fn burst_up_to_capacity() {
+        let mut bucket = TokenBucket::new(3, 0.001);
+
+        assert!(bucket.try_acquire().is_ok());
+        assert!(bucket.try_acquire().is_ok());
+        assert!(bucket.try_acquire().is_ok());
+        assert!(bucket.try_acquire().is_err());
+    }
+
+    #[test]
+    fn try_acquire_after_refill() {
+        let mut bucket = TokenBucket::new(1, 100.0);
+
+        assert!(bucket.try_acquire().is_ok());
+
+        let delay = bucket.try_acquire().unwrap_err();
+        thread::sleep(delay);
+
+        assert!(bucket.try_acquire().is_ok());
+    }
+}
@@ -25,6 +25,21 @@ pub struct TaskHandle {
     join_handle: DropJoinHandle,
 }
 
+/**
+   The status of a background task, which can be shared to check whether
+   the task is still running, without the ability to stop it.
+*/
+#[derive(Clone, Debug)]
+pub struct TaskStatus {
+    stopped: Arc<RwLock<bool>>,
+}
+
+/**
+   Marks the task as stopped when dropped, including when the thread
+   running the task unwinds after a panic.
+*/
+struct StoppedGuard(Arc<RwLock<bool>>);
+
 /**
    A wrapper to [`std::thread::JoinHandle`] so that the handle is joined
    when it is dropped.
@@ -98,6 +113,7 @@ pub fn spawn_background_task<E: Display>(
     let (shutdown_sender, receiver) = bounded(1);
 
     let join_handle = thread::spawn(move || {
+        let _stopped = StoppedGuard(write_stopped);
         let _entered = span.enter();
         loop {
             match receiver.try_recv() {
@@ -124,8 +140,6 @@ pub fn spawn_background_task<E: Display>(
             }
         }
 
-        *write_stopped.acquire_write() = true;
-
         debug!("task terminated");
     });
 
@@ -174,13 +188,44 @@ impl TaskHandle {
     }
 
     /**
-       Check whether a background task has been stopped prematurely.
+       The status of the background task, which can be shared with other tasks.
+    */
+    pub fn status(&self) -> TaskStatus {
+        TaskStatus {
+            stopped: self.stopped.clone(),
+        }
+    }
+
+    /**
+       Check whether a background task has been stopped prematurely,
+       including because its step runner panicked.
+    */
+    pub fn is_stopped(&self) -> bool {
+        *self.stopped.acquire_read()
+            || self
+                .join_handle
+                .0
+                .as_ref()
+                .map_or(true, |handle| handle.is_finished())
+    }
+}
+
+impl TaskStatus {
+    /**
+       Check whether the background task has stopped, including because
+       its step runner panicked.
     */
     pub fn is_stopped(&self) -> bool {
         *self.stopped.acquire_read()
     }
 }
 
+impl Drop for StoppedGuard {
+    fn drop(&mut self) {
+        *self.0.acquire_write() = true;
+    }
+}
+
 impl Drop for DropJoinHandle {
     fn drop(&mut self) {
         if let Some(handle) = mem::take(&mut self.0) {
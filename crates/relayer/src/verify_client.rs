@@ -0,0 +1,203 @@
+//! Verification of the state stored by a client against
+//! the chain it is tracking.
+
+use flex_error::define_error;
+use serde::Serialize;
+
+use ibc_relayer_types::core::ics02_client::client_state::ClientState;
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ClientId};
+use ibc_relayer_types::Height;
+
+use crate::chain::handle::ChainHandle;
+use crate::chain::requests::{
+    IncludeProof, PageRequest, QueryClientStateRequest, QueryConsensusStateHeightsRequest,
+    QueryConsensusStateRequest, QueryHeight,
+};
+use crate::consensus_state::AnyConsensusState;
+use crate::error::Error;
+
+define_error! {
+    VerifyClientError {
+        Query
+            { chain_id: ChainId }
+            [ Error ]
+            |e| { format!("error querying chain {0}", e.chain_id) },
+
+        Verify
+            {
+                chain_id: ChainId,
+                height: Height,
+            }
+            [ Error ]
+            |e| {
+                format!("failed to verify the header of chain {0} at height {1}",
+                    e.chain_id, e.height)
+            },
+
+        ChainIdMismatch
+            {
+                client_id: ClientId,
+                expected: ChainId,
+                actual: ChainId,
+            }
+            |e| {
+                format!("client {0} tracks chain {1} but the reference chain is {2}",
+                    e.client_id, e.actual, e.expected)
+            },
+    }
+}
+
+/// Outcome of verifying a client consensus state against the reference chain.
+#[derive(Clone, Debug, Serialize)]
+pub struct VerifyClientReport {
+    pub client_id: ClientId,
+    pub host_chain_id: ChainId,
+    pub reference_chain_id: ChainId,
+    pub height: Height,
+    /// The consensus state stored by the client on the host chain
+    pub stored: AnyConsensusState,
+    /// The consensus state built from the verified header of the reference chain
+    pub expected: AnyConsensusState,
+    pub matches: bool,
+}
+
+/// Fetch the client state of `client_id` on `host_chain` along with its consensus
+/// state at `height` (or at the client's latest height if unspecified), and compare
+/// the latter with the consensus state built from the header of `reference_chain`
+/// at that height.
+///
+/// The reference header is verified by the light client of `reference_chain`,
+/// starting from `trusted_height` if specified, or otherwise from the latest
+/// consensus state of the client below `height` (see [`latest_trusted_height`]).
+pub fn verify_client<HostChain: ChainHandle, ReferenceChain: ChainHandle>(
+    host_chain: &HostChain,
+    reference_chain: &ReferenceChain,
+    client_id: &ClientId,
+    height: Option<Height>,
+    trusted_height: Option<Height>,
+) -> Result<VerifyClientReport, VerifyClientError> {
+    let (client_state, _) = host_chain
+        .query_client_state(
+            QueryClientStateRequest {
+                client_id: client_id.clone(),
+                height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(|e| VerifyClientError::query(host_chain.id(), e))?;
+
+    if client_state.chain_id() != reference_chain.id() {
+        return Err(VerifyClientError::chain_id_mismatch(
+            client_id.clone(),
+            reference_chain.id(),
+            client_state.chain_id(),
+        ));
+    }
+
+    let height = height.unwrap_or_else(|| client_state.latest_height());
+
+    let (stored, _) = host_chain
+        .query_consensus_state(
+            QueryConsensusStateRequest {
+                client_id: client_id.clone(),
+                consensus_height: height,
+                query_height: QueryHeight::Latest,
+            },
+            IncludeProof::No,
+        )
+        .map_err(|e| VerifyClientError::query(host_chain.id(), e))?;
+
+    let trusted_height = match trusted_height {
+        Some(trusted_height) => trusted_height,
+        None => latest_trusted_height(host_chain, client_id, height)?,
+    };
+
+    let expected = reference_chain
+        .build_consensus_state(trusted_height, height, client_state)
+        .map_err(|e| VerifyClientError::verify(reference_chain.id(), height, e))?;
+
+    Ok(VerifyClientReport {
+        client_id: client_id.clone(),
+        host_chain_id: host_chain.id(),
+        reference_chain_id: reference_chain.id(),
+        height,
+        matches: stored == expected,
+        stored,
+        expected,
+    })
+}
+
+/// The height of the latest consensus state of `client_id` on `host_chain`
+/// below `height`, ie. the latest height from which the client itself could
+/// have verified the header at `height`.
+///
+/// If the client has no consensus state below `height`, eg. because it was
+/// created at that height, the header served by the reference chain's full
+/// node at `height` is trusted instead, and `height` is returned.
+pub fn latest_trusted_height<HostChain: ChainHandle>(
+    host_chain: &HostChain,
+    client_id: &ClientId,
+    height: Height,
+) -> Result<Height, VerifyClientError> {
+    let heights = host_chain
+        .query_consensus_state_heights(QueryConsensusStateHeightsRequest {
+            client_id: client_id.clone(),
+            pagination: Some(PageRequest::all()),
+            query_height: QueryHeight::Latest,
+        })
+        .map_err(|e| VerifyClientError::query(host_chain.id(), e))?;
+
+    Ok(heights
+        .into_iter()
+        .filter(|h| *h < height)
+        .max()
+        .unwrap_or(height))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::clients::ics07_tendermint::consensus_state::ConsensusState as TmConsensusState;
+    use ibc_relayer_types::clients::ics07_tendermint::header::test_util::get_dummy_tendermint_header;
+
+    use crate::chain::handle::MockChainHandle;
+
+    fn chain_with_consensus_states(heights: &[u64]) -> MockChainHandle {
+        let chain = MockChainHandle::new(ChainId::from_string("ibc-0"));
+        let consensus_state = TmConsensusState::from(get_dummy_tendermint_header());
+
+        for height in heights {
+            chain.insert_consensus_state(
+                ClientId::default(),
+                Height::new(1, *height).unwrap(),
+                consensus_state.clone().into(),
+            );
+        }
+
+        chain
+    }
+
+    #[test]
+    fn trust_the_latest_consensus_state_below_the_height() {
+        let chain = chain_with_consensus_states(&[5, 10, 20, 30]);
+        let height = |h| Height::new(1, h).unwrap();
+
+        let trusted = |h| latest_trusted_height(&chain, &ClientId::default(), height(h)).unwrap();
+
+        assert_eq!(trusted(30), height(20));
+        assert_eq!(trusted(25), height(20));
+        assert_eq!(trusted(10), height(5));
+    }
+
+    #[test]
+    fn trust_the_height_without_consensus_state_below_it() {
+        let chain = chain_with_consensus_states(&[5]);
+        let height = Height::new(1, 5).unwrap();
+
+        assert_eq!(
+            latest_trusted_height(&chain, &ClientId::default(), height).unwrap(),
+            height
+        );
+    }
+}
@@ -0,0 +1,365 @@
+//! Notification of selected IBC events to operator-defined webhooks.
+//!
+//! The webhooks are initialized once by the supervisor from the `[[webhooks]]`
+//! sections of the configuration. Each matching event is sent as a JSON object in
+//! the body of an HTTP `POST` request. Every webhook has its own queue, drained by
+//! a background thread which retries failed deliveries, so that an unreachable
+//! webhook does not delay the notifications of the others. When no webhook is
+//! configured, notifying is a no-op.
+
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crossbeam_channel::{Receiver, Sender, TrySendError};
+use flex_error::{define_error, TraceError};
+use hmac::{Hmac, Mac};
+use once_cell::sync::OnceCell;
+use reqwest::blocking::Client;
+use reqwest::header::CONTENT_TYPE;
+use reqwest::Url;
+use serde::Serialize;
+use sha2::Sha256;
+use tracing::{debug, error, warn};
+
+use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId};
+
+use crate::config::WebhookConfig;
+use crate::event::IbcEventWithHeight;
+
+define_error! {
+    WebhookError {
+        Http
+            [ TraceError<reqwest::Error> ]
+            |_| { "HTTP error while notifying the webhook" },
+
+        Encode
+            [ TraceError<serde_json::Error> ]
+            |_| { "failed to encode the webhook payload" },
+
+        InvalidUrl
+            { url: String, reason: String }
+            |e| { format!("invalid webhook URL '{}': {}", e.url, e.reason) },
+
+        Status
+            { status: String }
+            |e| { format!("webhook answered with status '{}'", e.status) },
+    }
+}
+
+/// Header carrying the HMAC-SHA256 signature of the payload, hex-encoded,
+/// for the webhooks configured with a secret.
+pub const SIGNATURE_HEADER: &str = "X-Hermes-Signature";
+
+/// Maximum number of notifications awaiting delivery, per webhook.
+const QUEUE_CAPACITY: usize = 1000;
+
+/// Maximum number of attempts at delivering a notification.
+const MAX_ATTEMPTS: u32 = 3;
+
+/// Delay before retrying a delivery, doubled after each attempt.
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The body of the request sent to a webhook for an event.
+#[derive(Debug, Serialize)]
+pub struct WebhookPayload<'a> {
+    pub chain_id: &'a ChainId,
+    pub event: &'a IbcEventWithHeight,
+}
+
+/// A webhook, with its parsed URL and the HTTP client used to notify it.
+#[derive(Debug)]
+pub struct Webhook {
+    config: WebhookConfig,
+    url: Url,
+    client: Client,
+}
+
+impl Webhook {
+    /// Check the URL of the webhook, which must use the `http` or `https` scheme.
+    pub fn new(config: WebhookConfig) -> Result<Self, WebhookError> {
+        let invalid_url =
+            |reason: &str| WebhookError::invalid_url(config.url.clone(), reason.into());
+
+        let url = Url::parse(&config.url).map_err(|e| invalid_url(&e.to_string()))?;
+
+        if !matches!(url.scheme(), "http" | "https") {
+            return Err(invalid_url(
+                "only the `http` and `https` schemes are supported",
+            ));
+        }
+
+        if url.host().is_none() {
+            return Err(invalid_url("missing host"));
+        }
+
+        let client = Client::builder()
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(WebhookError::http)?;
+
+        Ok(Self {
+            config,
+            url,
+            client,
+        })
+    }
+
+    /// Whether the given event, emitted by the given chain, should be notified to this webhook.
+    pub fn matches(&self, chain_id: &ChainId, event: &IbcEventWithHeight) -> bool {
+        let event_type = event.event.event_type();
+
+        if !self
+            .config
+            .events
+            .iter()
+            .any(|kind| kind == event_type.as_str())
+        {
+            return false;
+        }
+
+        if matches!(&self.config.chain, Some(chain) if chain != chain_id) {
+            return false;
+        }
+
+        match &self.config.channel {
+            Some(channel_id) => event_channels(event).contains(channel_id),
+            None => true,
+        }
+    }
+
+    fn deliver(&self, payload: &[u8]) -> Result<(), WebhookError> {
+        let mut request = self
+            .client
+            .post(self.url.clone())
+            .header(CONTENT_TYPE, "application/json");
+
+        if let Some(secret) = &self.config.secret {
+            let signature = hex::encode(hmac_sha256(secret.as_bytes(), payload));
+            request = request.header(SIGNATURE_HEADER, format!("sha256={signature}"));
+        }
+
+        let response = request
+            .body(payload.to_vec())
+            .send()
+            .map_err(WebhookError::http)?;
+
+        let status = response.status();
+        if status.is_success() {
+            Ok(())
+        } else {
+            Err(WebhookError::status(status.to_string()))
+        }
+    }
+}
+
+/// The channels on either end of the given event, if it relates to a channel.
+fn event_channels(event: &IbcEventWithHeight) -> Vec<ChannelId> {
+    if let Some(packet) = event.event.packet() {
+        return vec![
+            packet.source_channel.clone(),
+            packet.destination_channel.clone(),
+        ];
+    }
+
+    event
+        .event
+        .clone()
+        .channel_attributes()
+        .map(|attrs| {
+            attrs
+                .channel_id
+                .into_iter()
+                .chain(attrs.counterparty_channel_id)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// HMAC-SHA256 of the message, keyed with the given secret.
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+/// A webhook and the queue of the notifications awaiting delivery to it.
+struct WebhookQueue {
+    webhook: Arc<Webhook>,
+    sender: Sender<Vec<u8>>,
+}
+
+static WEBHOOKS: OnceCell<Vec<WebhookQueue>> = OnceCell::new();
+
+/// Initialize the webhooks from the given configuration, and spawn
+/// a thread delivering the notifications of each webhook.
+///
+/// Webhooks with an invalid URL are skipped. Does nothing if no webhook is
+/// configured, or if the webhooks were already initialized.
+pub fn init(configs: &[WebhookConfig]) {
+    if WEBHOOKS.get().is_some() {
+        return;
+    }
+
+    let webhooks: Vec<_> = configs
+        .iter()
+        .cloned()
+        .filter_map(|config| {
+            Webhook::new(config)
+                .map_err(|e| error!("skipping webhook: {}", e))
+                .ok()
+        })
+        .collect();
+
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let mut receivers = Vec::with_capacity(webhooks.len());
+    let queues = webhooks
+        .into_iter()
+        .map(|webhook| {
+            let (sender, receiver) = crossbeam_channel::bounded(QUEUE_CAPACITY);
+            let webhook = Arc::new(webhook);
+            receivers.push((webhook.clone(), receiver));
+            WebhookQueue { webhook, sender }
+        })
+        .collect();
+
+    if WEBHOOKS.set(queues).is_ok() {
+        for (webhook, receiver) in receivers {
+            thread::spawn(move || deliver_notifications(webhook, receiver));
+        }
+    }
+}
+
+/// Notify the webhooks of the events emitted by the given chain which match their filters.
+pub fn notify(chain_id: &ChainId, events: &[IbcEventWithHeight]) {
+    let queues = match WEBHOOKS.get() {
+        Some(queues) => queues,
+        None => return,
+    };
+
+    for event in events {
+        for queue in queues {
+            let webhook = &queue.webhook;
+            if !webhook.matches(chain_id, event) {
+                continue;
+            }
+
+            let payload = match serde_json::to_vec(&WebhookPayload { chain_id, event }) {
+                Ok(payload) => payload,
+                Err(e) => {
+                    error!("failed to notify webhook: {}", WebhookError::encode(e));
+                    continue;
+                }
+            };
+
+            if let Err(TrySendError::Full(_)) = queue.sender.try_send(payload) {
+                warn!(
+                    url = %webhook.config.url,
+                    "too many notifications awaiting delivery, dropping notification"
+                );
+            }
+        }
+    }
+}
+
+fn deliver_notifications(webhook: Arc<Webhook>, receiver: Receiver<Vec<u8>>) {
+    for payload in receiver {
+        let mut backoff = RETRY_BACKOFF;
+
+        for attempt in 1..=MAX_ATTEMPTS {
+            match webhook.deliver(&payload) {
+                Ok(()) => {
+                    debug!(url = %webhook.config.url, "notified webhook");
+                    break;
+                }
+                Err(e) if attempt < MAX_ATTEMPTS => {
+                    warn!(url = %webhook.config.url, attempt, "failed to notify webhook, retrying: {}", e);
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+                Err(e) => {
+                    error!(url = %webhook.config.url, "failed to notify webhook, giving up: {}", e);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics04_channel::events::SendPacket;
+    use ibc_relayer_types::core::ics04_channel::packet::Packet;
+    use ibc_relayer_types::events::IbcEvent;
+    use ibc_relayer_types::Height;
+
+    fn new_webhook(channel: Option<ChannelId>) -> Webhook {
+        Webhook::new(WebhookConfig {
+            url: "http://127.0.0.1:8080/hermes".to_string(),
+            events: vec!["send_packet".to_string(), "chain_error".to_string()],
+            chain: Some(ChainId::from_string("ibc-0")),
+            channel,
+            secret: None,
+        })
+        .unwrap()
+    }
+
+    fn event(event: IbcEvent) -> IbcEventWithHeight {
+        IbcEventWithHeight::new(event, Height::new(0, 1).unwrap())
+    }
+
+    #[test]
+    fn hmac_sha256_rfc4231() {
+        assert_eq!(
+            hex::encode(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn parse_url() {
+        let webhook = new_webhook(None);
+        assert_eq!(webhook.url.host_str(), Some("127.0.0.1"));
+        assert_eq!(webhook.url.port(), Some(8080));
+        assert_eq!(webhook.url.path(), "/hermes");
+
+        let https = WebhookConfig {
+            url: "https://example.com/hermes".to_string(),
+            ..webhook.config.clone()
+        };
+        assert!(Webhook::new(https).is_ok());
+
+        let ftp = WebhookConfig {
+            url: "ftp://example.com".to_string(),
+            ..webhook.config
+        };
+        assert!(Webhook::new(ftp).is_err());
+    }
+
+    #[test]
+    fn filter_events() {
+        let chain_id = ChainId::from_string("ibc-0");
+        let send_packet = event(IbcEvent::SendPacket(SendPacket {
+            packet: Packet {
+                source_channel: ChannelId::new(1),
+                ..Default::default()
+            },
+        }));
+        let chain_error = event(IbcEvent::ChainError("out of gas".to_string()));
+
+        let webhook = new_webhook(None);
+        assert!(webhook.matches(&chain_id, &send_packet));
+        assert!(webhook.matches(&chain_id, &chain_error));
+        assert!(!webhook.matches(&ChainId::from_string("ibc-1"), &send_packet));
+
+        let webhook = new_webhook(Some(ChannelId::new(1)));
+        assert!(webhook.matches(&chain_id, &send_packet));
+        assert!(!webhook.matches(&chain_id, &chain_error));
+    }
+}
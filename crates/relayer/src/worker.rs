@@ -27,6 +27,12 @@ pub use cmd::WorkerCmd;
 mod map;
 pub use map::WorkerMap;
 
+mod stats;
+pub use stats::{WorkerStats, WorkerStatsSnapshot};
+
+mod circuit_breaker;
+pub use circuit_breaker::CircuitBreaker;
+
 pub mod channel;
 pub mod client;
 pub mod connection;
@@ -61,6 +67,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
     config: &Config,
 ) -> WorkerHandle {
     let mut task_handles = Vec::new();
+    let stats = WorkerStats::default();
 
     let (cmd_tx, data) = match &object {
         Object::Client(client) => {
@@ -68,7 +75,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
 
             let (mut refresh, mut misbehaviour) = (false, false);
 
-            let refresh_task = client::spawn_refresh_client(client.clone());
+            let refresh_task = client::spawn_refresh_client(client.clone(), stats.clone());
             if let Some(refresh_task) = refresh_task {
                 task_handles.push(refresh_task);
                 refresh = true;
@@ -76,7 +83,8 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
 
             let cmd_tx = if config.mode.clients.misbehaviour {
                 let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-                let misbehavior_task = client::detect_misbehavior_task(cmd_rx, client);
+                let misbehavior_task =
+                    client::detect_misbehavior_task(cmd_rx, client, stats.clone());
                 if let Some(task) = misbehavior_task {
                     task_handles.push(task);
                     misbehaviour = true;
@@ -96,15 +104,20 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
         Object::Connection(connection) => {
             let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-            let connection_task =
-                connection::spawn_connection_worker(connection.clone(), chains, cmd_rx);
+            let connection_task = connection::spawn_connection_worker(
+                connection.clone(),
+                chains,
+                cmd_rx,
+                stats.clone(),
+            );
             task_handles.push(connection_task);
 
             (Some(cmd_tx), None)
         }
         Object::Channel(channel) => {
             let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
-            let channel_task = channel::spawn_channel_worker(channel.clone(), chains, cmd_rx);
+            let channel_task =
+                channel::spawn_channel_worker(channel.clone(), chains, cmd_rx, stats.clone());
             task_handles.push(channel_task);
 
             (Some(cmd_tx), None)
@@ -123,14 +136,34 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
             );
 
             match link_res {
-                Ok(link) => {
+                Ok(mut link) => {
+                    // Only relay the directions allowed by the packet filters of both ends
+                    let src_direction =
+                        config.packet_direction(&path.src_chain_id, &path.src_channel_id);
+                    let dst_direction =
+                        config.packet_direction(&path.dst_chain_id, link.a_to_b.dst_channel_id());
+
+                    link.a_to_b.set_relayed_events(
+                        src_direction.relays_outbound() && dst_direction.relays_inbound(),
+                        src_direction.relays_inbound() && dst_direction.relays_outbound(),
+                    );
+
+                    let clear_interval =
+                        config.clear_interval(&path.src_chain_id, &path.src_channel_id);
+                    let resubmit = Resubmit::from_clear_interval(clear_interval);
+
+                    link.a_to_b.set_max_queued_operational_data(
+                        packets_config.max_queued_operational_data,
+                        clear_interval,
+                    );
+
                     let channel_ordering = link.a_to_b.channel().ordering;
                     let should_clear_on_start =
                         packets_config.clear_on_start || channel_ordering == Ordering::Ordered;
 
                     let (cmd_tx, cmd_rx) = crossbeam_channel::unbounded();
                     let link = Arc::new(Mutex::new(link));
-                    let resubmit = Resubmit::from_clear_interval(packets_config.clear_interval);
+                    let breaker = Arc::new(Mutex::new(CircuitBreaker::default()));
 
                     let src_chain_config =
                         config.chains.iter().find(|chain| chain.id == chains.a.id());
@@ -156,18 +189,28 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
                             link.clone(),
                             path.clone(),
                             filter,
+                            breaker.clone(),
+                            stats.clone(),
                         ),
                         None => packet::spawn_packet_cmd_worker(
                             cmd_rx,
                             link.clone(),
                             should_clear_on_start,
-                            packets_config.clear_interval,
+                            clear_interval,
                             path.clone(),
+                            breaker.clone(),
+                            stats.clone(),
                         ),
                     };
                     task_handles.push(packet_task);
 
-                    let link_task = packet::spawn_packet_worker(path.clone(), link, resubmit);
+                    let link_task = packet::spawn_packet_worker(
+                        path.clone(),
+                        link,
+                        resubmit,
+                        breaker,
+                        stats.clone(),
+                    );
                     task_handles.push(link_task);
 
                     (Some(cmd_tx), None)
@@ -182,7 +225,7 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         Object::Wallet(wallet) => {
             assert_eq!(wallet.chain_id, chains.a.id());
 
-            let wallet_task = wallet::spawn_wallet_worker(chains.a);
+            let wallet_task = wallet::spawn_wallet_worker(chains.a, stats.clone());
             task_handles.push(wallet_task);
 
             (None, None)
@@ -202,5 +245,5 @@ pub fn spawn_worker_tasks<ChainA: ChainHandle, ChainB: ChainHandle>(
         }
     };
 
-    WorkerHandle::new(id, object, data, cmd_tx, task_handles)
+    WorkerHandle::new(id, object, data, cmd_tx, task_handles, stats)
 }
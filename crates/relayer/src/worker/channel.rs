@@ -12,7 +12,7 @@ use crate::{
 };
 
 use super::error::RunError;
-use super::WorkerCmd;
+use super::{WorkerCmd, WorkerStats};
 
 fn max_block_times<ChainA: ChainHandle, ChainB: ChainHandle>(
     chains: &ChainHandlePair<ChainA, ChainB>,
@@ -32,6 +32,7 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     channel: Channel,
     chains: ChainHandlePair<ChainA, ChainB>,
     cmd_rx: Receiver<WorkerCmd>,
+    stats: WorkerStats,
 ) -> TaskHandle {
     let mut complete_handshake_on_new_block = true;
     spawn_background_task(
@@ -51,14 +52,17 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                         if let Some(event_with_height) = last_event {
                             retry_with_index(
                                 channel_handshake_retry::default_strategy(max_block_times),
-                                |index| match RelayChannel::restore_from_event(
-                                    chains.a.clone(),
-                                    chains.b.clone(),
-                                    event_with_height.event.clone(),
-                                ) {
-                                    Ok(mut handshake_channel) => handshake_channel
-                                        .step_event(&event_with_height.event, index),
-                                    Err(_) => RetryResult::Retry(index),
+                                |index| {
+                                    stats.record_attempt(index);
+                                    match RelayChannel::restore_from_event(
+                                        chains.a.clone(),
+                                        chains.b.clone(),
+                                        event_with_height.event.clone(),
+                                    ) {
+                                        Ok(mut handshake_channel) => handshake_channel
+                                            .step_event(&event_with_height.event, index),
+                                        Err(_) => RetryResult::Retry(index),
+                                    }
                                 },
                             )
                             .map_err(|e| TaskError::Fatal(RunError::retry(e)))
@@ -80,16 +84,19 @@ pub fn spawn_channel_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                         complete_handshake_on_new_block = false;
                         retry_with_index(
                             channel_handshake_retry::default_strategy(max_block_times),
-                            |index| match RelayChannel::restore_from_state(
-                                chains.a.clone(),
-                                chains.b.clone(),
-                                channel.clone(),
-                                height,
-                            ) {
-                                Ok((mut handshake_channel, state)) => {
-                                    handshake_channel.step_state(state, index)
+                            |index| {
+                                stats.record_attempt(index);
+                                match RelayChannel::restore_from_state(
+                                    chains.a.clone(),
+                                    chains.b.clone(),
+                                    channel.clone(),
+                                    height,
+                                ) {
+                                    Ok((mut handshake_channel, state)) => {
+                                        handshake_channel.step_state(state, index)
+                                    }
+                                    Err(_) => RetryResult::Retry(index),
                                 }
-                                Err(_) => RetryResult::Retry(index),
                             },
                         )
                         .map_err(|e| TaskError::Fatal(RunError::retry(e)))
@@ -0,0 +1,117 @@
+use core::time::Duration;
+use std::time::Instant;
+
+/// Delay before retrying after the first failure, doubled at each consecutive failure.
+const BASE_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Maximum delay between two attempts, which is also the delay between
+/// two attempts of a suspended worker.
+const MAX_BACKOFF: Duration = Duration::from_secs(5 * 60);
+
+/// Number of consecutive failures after which the worker is suspended.
+pub(super) const FAILURE_BUDGET: u32 = 10;
+
+/// Keeps a worker whose transactions are all rejected by a chain, eg. because
+/// the client is frozen or the wallet is out of funds, from retrying in a hot loop.
+///
+/// Each consecutive failure doubles the delay before the next attempt, up to
+/// [`MAX_BACKOFF`]. Once the [`FAILURE_BUDGET`] is exhausted, the worker is
+/// suspended: it only makes an attempt every [`MAX_BACKOFF`], until one
+/// succeeds or the breaker is [`reset`](CircuitBreaker::reset).
+#[derive(Debug, Default)]
+pub struct CircuitBreaker {
+    consecutive_failures: u32,
+    retry_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    /// Whether the failure budget is exhausted.
+    pub fn is_suspended(&self) -> bool {
+        self.consecutive_failures >= FAILURE_BUDGET
+    }
+
+    /// Whether the worker may make an attempt at `now`.
+    pub fn allows(&self, now: Instant) -> bool {
+        self.retry_at.map_or(true, |retry_at| now >= retry_at)
+    }
+
+    /// Record a failed attempt at `now`, returning whether the worker just got suspended.
+    pub fn record_failure(&mut self, now: Instant) -> bool {
+        self.consecutive_failures = self.consecutive_failures.saturating_add(1);
+        self.retry_at = Some(now + self.backoff());
+
+        self.consecutive_failures == FAILURE_BUDGET
+    }
+
+    /// Record a successful attempt, returning whether the worker was suspended.
+    pub fn record_success(&mut self) -> bool {
+        self.reset()
+    }
+
+    /// Allow attempts again right away, returning whether the worker was suspended.
+    pub fn reset(&mut self) -> bool {
+        let was_suspended = self.is_suspended();
+
+        self.consecutive_failures = 0;
+        self.retry_at = None;
+
+        was_suspended
+    }
+
+    fn backoff(&self) -> Duration {
+        let exponent = self.consecutive_failures.saturating_sub(1).min(31);
+
+        BASE_BACKOFF
+            .checked_mul(1 << exponent)
+            .map_or(MAX_BACKOFF, |backoff| backoff.min(MAX_BACKOFF))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escalating_backoff() {
+        let now = Instant::now();
+        let mut breaker = CircuitBreaker::default();
+        assert!(breaker.allows(now));
+
+        breaker.record_failure(now);
+        assert!(!breaker.allows(now));
+        assert!(breaker.allows(now + Duration::from_secs(1)));
+
+        breaker.record_failure(now);
+        assert!(!breaker.allows(now + Duration::from_secs(1)));
+        assert!(breaker.allows(now + Duration::from_secs(2)));
+
+        breaker.record_failure(now);
+        assert!(breaker.allows(now + Duration::from_secs(4)));
+    }
+
+    #[test]
+    fn suspend_and_resume() {
+        let now = Instant::now();
+        let mut breaker = CircuitBreaker::default();
+
+        for _ in 1..FAILURE_BUDGET {
+            assert!(!breaker.record_failure(now));
+        }
+
+        assert!(!breaker.is_suspended());
+        assert!(breaker.record_failure(now));
+        assert!(breaker.is_suspended());
+
+        assert!(!breaker.allows(now + MAX_BACKOFF - Duration::from_secs(1)));
+        assert!(breaker.allows(now + MAX_BACKOFF));
+
+        // Further failures keep the worker suspended without reporting it again
+        assert!(!breaker.record_failure(now));
+        assert!(breaker.is_suspended());
+
+        assert!(breaker.record_success());
+        assert!(!breaker.is_suspended());
+        assert!(breaker.allows(now));
+        assert!(!breaker.reset());
+    }
+}
@@ -1,6 +1,7 @@
 use core::convert::Infallible;
 use core::time::Duration;
 use crossbeam_channel::Receiver;
+use itertools::Itertools;
 use retry::delay::Fibonacci;
 use retry::retry_with_index;
 use std::time::Instant;
@@ -12,11 +13,11 @@ use ibc_relayer_types::events::IbcEvent;
 use crate::util::retry::clamp_total;
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 use crate::{
-    chain::handle::ChainHandle,
+    chain::{counterparty::client_channels, handle::ChainHandle},
     foreign_client::{ForeignClient, MisbehaviourResults},
 };
 
-use super::WorkerCmd;
+use super::{WorkerCmd, WorkerStats};
 
 const REFRESH_INTERVAL: Duration = Duration::from_secs(2); // 2 seconds
 const INITIAL_BACKOFF: Duration = Duration::from_secs(1); // 1 second
@@ -25,6 +26,7 @@ const MAX_REFRESH_TOTAL_DELAY: Duration = Duration::from_secs(60 * 60 * 24); //
 
 pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
     mut client: ForeignClient<ChainA, ChainB>,
+    stats: WorkerStats,
 ) -> Option<TaskHandle> {
     if client.is_expired_or_frozen() {
         warn!(
@@ -32,6 +34,8 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
             "skipping refresh client task on frozen client",
         );
 
+        warn_affected_channels(&client);
+
         return None;
     }
 
@@ -54,7 +58,10 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
             }
 
             // Use retry mechanism only if `client.refresh()` fails.
-            let res = retry_with_index(refresh_strategy(), |_| client.refresh());
+            let res = retry_with_index(refresh_strategy(), |index| {
+                stats.record_attempt(index);
+                client.refresh()
+            });
 
             match res {
                 // If `client.refresh()` was successful, update the `next_refresh` call.
@@ -65,15 +72,41 @@ pub fn spawn_refresh_client<ChainA: ChainHandle, ChainB: ChainHandle>(
                 }
                 // If `client.refresh()` failed and the retry mechanism
                 // exceeded the maximum delay, return a fatal error.
-                Err(e) => Err(TaskError::Fatal(e)),
+                Err(e) => {
+                    warn_affected_channels(&client);
+                    Err(TaskError::Fatal(e))
+                }
             }
         },
     ))
 }
 
+/// Report the channels whose packets can no longer be relayed
+/// because they are verified by the given degraded client.
+fn warn_affected_channels<ChainA: ChainHandle, ChainB: ChainHandle>(
+    client: &ForeignClient<ChainA, ChainB>,
+) {
+    match client_channels(&client.dst_chain, &client.id) {
+        Ok(channels) if !channels.is_empty() => warn!(
+            client = %client.id,
+            channels = %channels
+                .iter()
+                .map(|c| format!("{}/{}", c.port_id, c.channel_id))
+                .join(", "),
+            "packets on these channels cannot be relayed until the client is recovered",
+        ),
+        Ok(_) => {}
+        Err(e) => debug!(
+            client = %client.id,
+            "failed to query the channels of the client: {}", e
+        ),
+    }
+}
+
 pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
     receiver: Receiver<WorkerCmd>,
     client: ForeignClient<ChainB, ChainA>,
+    stats: WorkerStats,
 ) -> Option<TaskHandle> {
     if client.is_expired_or_frozen() {
         warn!(
@@ -110,7 +143,7 @@ pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
 
                 for event_with_height in batch.events {
                     if let IbcEvent::UpdateClient(update) = event_with_height.event {
-                        match on_client_update(&client, update) {
+                        match on_client_update(&client, update, &stats) {
                             Next::Continue => continue,
                             Next::Abort => return Ok(Next::Abort),
                         }
@@ -128,6 +161,7 @@ pub fn detect_misbehavior_task<ChainA: ChainHandle, ChainB: ChainHandle>(
 fn on_client_update<ChainA: ChainHandle, ChainB: ChainHandle>(
     client: &ForeignClient<ChainB, ChainA>,
     update: UpdateClient,
+    stats: &WorkerStats,
 ) -> Next {
     let _span = debug_span!(
         "on_client_update",
@@ -151,6 +185,7 @@ fn on_client_update<ChainA: ChainHandle, ChainB: ChainHandle>(
         MisbehaviourResults::VerificationError => {
             // can retry in next call
             debug!("client verification error, will retry in next call");
+            stats.record_retry();
 
             Next::Continue
         }
@@ -8,7 +8,7 @@ use crate::{
     chain::handle::{ChainHandle, ChainHandlePair},
     object::Connection,
     util::retry::retry_with_index,
-    worker::retry_strategy,
+    worker::{retry_strategy, WorkerStats},
 };
 
 use super::error::RunError;
@@ -18,6 +18,7 @@ pub fn spawn_connection_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     connection: Connection,
     chains: ChainHandlePair<ChainA, ChainB>,
     cmd_rx: Receiver<WorkerCmd>,
+    stats: WorkerStats,
 ) -> TaskHandle {
     let mut complete_handshake_on_new_block = true;
     spawn_background_task(
@@ -43,6 +44,7 @@ pub fn spawn_connection_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                             .map_err(|e| TaskError::Fatal(RunError::connection(e)))?;
 
                             retry_with_index(retry_strategy::worker_default_strategy(), |index| {
+                                stats.record_attempt(index);
                                 handshake_connection.step_event(&event_with_height.event, index)
                             })
                             .map_err(|e| TaskError::Fatal(RunError::retry(e)))
@@ -73,6 +75,7 @@ pub fn spawn_connection_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
                         complete_handshake_on_new_block = false;
 
                         retry_with_index(retry_strategy::worker_default_strategy(), |index| {
+                            stats.record_attempt(index);
                             handshake_connection.step_state(state, index)
                         })
                         .map_err(|e| TaskError::Fatal(RunError::retry(e)))
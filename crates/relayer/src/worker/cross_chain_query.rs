@@ -98,12 +98,21 @@ fn handle_cross_chain_query<ChainA: ChainHandle, ChainB: ChainHandle>(
                 )
                 .map_err(|_| TaskError::Fatal(RunError::query()))?;
 
-                let target_height = Height::new(
+                // The proofs of the responses are verified against the consensus state
+                // of the queried chain given by its proof height policy
+                let proof_height_policy = chain_b_handle
+                    .config()
+                    .map_err(|_| TaskError::Fatal(RunError::query()))?
+                    .r#type
+                    .proof_height_policy();
+
+                let query_height = Height::new(
                     chain_b_handle.id().version(),
                     cross_chain_query_responses.get(0).unwrap().height as u64,
                 )
-                .map_err(|_| TaskError::Fatal(RunError::query()))?
-                .increment();
+                .map_err(|_| TaskError::Fatal(RunError::query()))?;
+
+                let target_height = proof_height_policy.proof_height(query_height);
 
                 // Push update client msg
                 let mut chain_a_msgs = client_a
@@ -17,7 +17,7 @@ use crate::util::lock::{LockExt, RwArc};
 use crate::util::task::TaskHandle;
 use crate::{event::monitor::EventBatch, object::Object};
 
-use super::{WorkerCmd, WorkerId};
+use super::{WorkerCmd, WorkerId, WorkerStats};
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -31,6 +31,7 @@ pub struct WorkerHandle {
     data: Option<WorkerData>,
     tx: RwArc<Option<Sender<WorkerCmd>>>,
     task_handles: Vec<TaskHandle>,
+    stats: WorkerStats,
 }
 
 impl WorkerHandle {
@@ -40,6 +41,7 @@ impl WorkerHandle {
         data: Option<WorkerData>,
         tx: Option<Sender<WorkerCmd>>,
         task_handles: Vec<TaskHandle>,
+        stats: WorkerStats,
     ) -> Self {
         Self {
             id,
@@ -47,11 +49,13 @@ impl WorkerHandle {
             data,
             tx: <RwArc<_>>::new_lock(tx),
             task_handles,
+            stats,
         }
     }
 
     pub fn try_send_command(&self, cmd: WorkerCmd) {
         let res = if let Some(tx) = self.tx.acquire_read().as_ref() {
+            self.stats.record_activity();
             tx.send(cmd)
         } else {
             Ok(())
@@ -91,6 +95,17 @@ impl WorkerHandle {
         self.try_send_command(WorkerCmd::ClearPendingPackets);
     }
 
+    /// Resume the worker if it is suspended by its circuit breaker, which also
+    /// clears its pending packets, returning whether it was suspended.
+    pub fn resume(&self) -> bool {
+        if self.stats.is_suspended() {
+            self.clear_pending_packets();
+            true
+        } else {
+            false
+        }
+    }
+
     /// Shutdown all worker tasks without waiting for them to terminate.
     pub fn shutdown(&self) {
         for task in self.task_handles.iter() {
@@ -140,6 +155,11 @@ impl WorkerHandle {
     pub fn data(&self) -> Option<&WorkerData> {
         self.data.as_ref()
     }
+
+    /// Get a reference to the worker's activity statistics.
+    pub fn stats(&self) -> &WorkerStats {
+        &self.stats
+    }
 }
 
 // Drop handle to send shutdown signals to background tasks in parallel
@@ -158,3 +178,37 @@ impl fmt::Debug for WorkerHandle {
             .finish_non_exhaustive()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::object::Client;
+
+    #[test]
+    fn resume_only_suspended_workers() {
+        let (tx, rx) = crossbeam_channel::unbounded();
+        let stats = WorkerStats::default();
+
+        let handle = WorkerHandle::new(
+            WorkerId::new(1),
+            Object::Client(Client {
+                dst_chain_id: ChainId::from_string("ibc-0"),
+                dst_client_id: "07-tendermint-0".parse().unwrap(),
+                src_chain_id: ChainId::from_string("ibc-1"),
+            }),
+            None,
+            Some(tx),
+            vec![],
+            stats.clone(),
+        );
+
+        assert!(!handle.resume());
+        assert!(rx.try_recv().is_err());
+
+        stats.set_suspended(true);
+
+        assert!(handle.resume());
+        assert!(matches!(rx.try_recv(), Ok(WorkerCmd::ClearPendingPackets)));
+    }
+}
@@ -9,7 +9,8 @@ use itertools::Itertools;
 use moka::sync::Cache;
 use std::borrow::BorrowMut;
 use std::sync::{Arc, Mutex};
-use tracing::debug;
+use std::time::Instant;
+use tracing::{debug, info, warn};
 
 use crossbeam_channel::Receiver;
 use ibc_proto::ibc::apps::fee::v1::{IdentifiedPacketFees, QueryIncentivizedPacketRequest};
@@ -27,15 +28,15 @@ use crate::chain::handle::ChainHandle;
 use crate::config::filter::FeePolicy;
 use crate::event::monitor::EventBatch;
 use crate::foreign_client::HasExpiredOrFrozenError;
-use crate::link::Resubmit;
 use crate::link::{error::LinkError, Link};
+use crate::link::{RelaySummary, Resubmit};
 use crate::object::Packet;
 use crate::telemetry;
 use crate::util::lock::{LockExt, RwArc};
 use crate::util::task::{spawn_background_task, Next, TaskError, TaskHandle};
 
 use super::error::RunError;
-use super::WorkerCmd;
+use super::{CircuitBreaker, WorkerCmd, WorkerStats};
 
 const INCENTIVIZED_CACHE_TTL: Duration = Duration::from_secs(10 * 60);
 const INCENTIVIZED_CACHE_MAX_CAPACITY: u64 = 1000;
@@ -57,6 +58,8 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     // Mutex is used to prevent race condition between the packet workers
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     resubmit: Resubmit,
+    breaker: Arc<Mutex<CircuitBreaker>>,
+    stats: WorkerStats,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -70,7 +73,11 @@ pub fn spawn_packet_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     };
 
     spawn_background_task(span, Some(Duration::from_millis(1000)), move || {
-        handle_execute_schedule(&mut link.lock().unwrap(), &path, resubmit)?;
+        let mut link = link.lock().unwrap();
+        let result = handle_execute_schedule(&mut link, &path, resubmit, &breaker);
+        update_stats(&stats, &link, &breaker, &result);
+        result?;
+
         Ok(Next::Continue)
     })
 }
@@ -82,6 +89,8 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
     mut should_clear_on_start: bool,
     clear_interval: u64,
     path: Packet,
+    breaker: Arc<Mutex<CircuitBreaker>>,
+    stats: WorkerStats,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -101,13 +110,17 @@ pub fn spawn_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHandle>(
             // If clearing fails after all these retries with ignorable error the task continues
             // (see `handle_link_error_in_task`) and clearing is retried with the next
             // (`NewBlock`) `cmd` that matches the clearing interval.
-            handle_packet_cmd(
-                &mut link.lock().unwrap(),
+            let mut link = link.lock().unwrap();
+            let result = handle_packet_cmd(
+                &mut link,
                 &mut should_clear_on_start,
                 clear_interval,
                 &path,
+                &breaker,
                 cmd,
-            )?;
+            );
+            update_stats(&stats, &link, &breaker, &result);
+            result?;
         }
 
         Ok(Next::Continue)
@@ -120,6 +133,8 @@ pub fn spawn_incentivized_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHa
     link: Arc<Mutex<Link<ChainA, ChainB>>>,
     path: Packet,
     fee_filter: FeePolicy,
+    breaker: Arc<Mutex<CircuitBreaker>>,
+    stats: WorkerStats,
 ) -> TaskHandle {
     let span = {
         let relay_path = &link.lock().unwrap().a_to_b;
@@ -143,13 +158,17 @@ pub fn spawn_incentivized_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHa
 
     spawn_background_task(span, Some(Duration::from_millis(200)), move || {
         if let Ok(cmd) = cmd_rx.try_recv() {
-            handle_incentivized_packet_cmd(
-                &mut link.lock().unwrap(),
+            let mut link = link.lock().unwrap();
+            let result = handle_incentivized_packet_cmd(
+                &mut link,
                 &path,
                 cmd,
                 &incentivized_recv_cache,
                 &fee_filter,
-            )?;
+                &breaker,
+            );
+            update_stats(&stats, &link, &breaker, &result);
+            result?;
         }
 
         Ok(Next::Continue)
@@ -164,7 +183,8 @@ pub fn spawn_incentivized_packet_cmd_worker<ChainA: ChainHandle, ChainB: ChainHa
 /// Given a `NewBlock` command, checks if packet clearing should occur
 /// and performs it if so.
 ///
-/// Given a `ClearPendingPackets` command, clears pending packets.
+/// Given a `ClearPendingPackets` command, clears pending packets,
+/// resuming the worker if it was suspended by its circuit breaker.
 ///
 /// Regardless of the incoming command, this method also refreshes and
 /// and executes any scheduled operational data that is ready.
@@ -173,6 +193,7 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     should_clear_on_start: &mut bool,
     clear_interval: u64,
     path: &Packet,
+    breaker: &Mutex<CircuitBreaker>,
     cmd: WorkerCmd,
 ) -> Result<(), TaskError<RunError>> {
     // Handle packet clearing which is triggered from a command
@@ -195,7 +216,13 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
             }
         }
 
-        WorkerCmd::ClearPendingPackets => (true, None),
+        WorkerCmd::ClearPendingPackets => {
+            if breaker.lock().unwrap().reset() {
+                resumed(path);
+            }
+
+            (true, None)
+        }
     };
 
     if do_clear {
@@ -204,12 +231,12 @@ fn handle_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
         if *should_clear_on_start {
             *should_clear_on_start = false;
         }
-        handle_clear_packet(link, clear_interval, path, maybe_height)?;
+        handle_clear_packet(link, clear_interval, path, maybe_height, breaker)?;
     }
 
     // Handle command-specific task
     if let WorkerCmd::IbcEvents { batch } = cmd {
-        handle_update_schedule(link, clear_interval, path, batch)
+        handle_update_schedule(link, clear_interval, path, batch, breaker)
     } else {
         Ok(())
     }
@@ -231,6 +258,7 @@ fn handle_incentivized_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
     cmd: WorkerCmd,
     incentivized_recv_cache: &RwArc<Cache<Sequence, IncentivizedPacket>>,
     fee_filter: &FeePolicy,
+    breaker: &Mutex<CircuitBreaker>,
 ) -> Result<(), TaskError<RunError>> {
     // Handle command-specific task
     if let WorkerCmd::IbcEvents { mut batch } = cmd {
@@ -251,7 +279,7 @@ fn handle_incentivized_packet_cmd<ChainA: ChainHandle, ChainB: ChainHandle>(
             //IbcEvent::WriteAcknowledgement(ack) => get_incentivized_for_write_acknowledgement(link, ack, event.height.revision_height(), incentivized_ack_cache.clone()),
         }
         filter_batch(batch.borrow_mut(), incentivized_recv_cache, fee_filter);
-        handle_update_schedule(link, 0, path, batch)
+        handle_update_schedule(link, 0, path, batch, breaker)
     } else {
         Ok(())
     }
@@ -339,6 +367,26 @@ fn retrieve_all_fees_from_incentivized_packet(
         .collect()
 }
 
+/// Record the outcome of a step of a packet worker, along with the amount
+/// of operational data left to submit and whether the worker is suspended
+/// by its circuit breaker, in its statistics.
+fn update_stats<ChainA: ChainHandle, ChainB: ChainHandle>(
+    stats: &WorkerStats,
+    link: &Link<ChainA, ChainB>,
+    breaker: &Mutex<CircuitBreaker>,
+    result: &Result<(), TaskError<RunError>>,
+) {
+    if let Err(TaskError::Ignore(_)) = result {
+        stats.record_retry();
+    }
+
+    stats.set_queued_operational_data(
+        link.a_to_b.src_operational_data.len() + link.a_to_b.dst_operational_data.len(),
+    );
+    stats.set_pending_txs(link.a_to_b.pending_tx_count());
+    stats.set_suspended(breaker.lock().unwrap().is_suspended());
+}
+
 /// Whether or not to clear pending packets at this `step` for some height.
 /// If the relayer has been configured to clear packets on start and that has not
 /// occurred yet, then packets are cleared.
@@ -354,12 +402,18 @@ fn handle_update_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     clear_interval: u64,
     path: &Packet,
     batch: EventBatch,
+    breaker: &Mutex<CircuitBreaker>,
 ) -> Result<(), TaskError<RunError>> {
     link.a_to_b
         .update_schedule(batch)
         .map_err(handle_link_error_in_task)?;
 
-    handle_execute_schedule(link, path, Resubmit::from_clear_interval(clear_interval))
+    handle_execute_schedule(
+        link,
+        path,
+        Resubmit::from_clear_interval(clear_interval),
+        breaker,
+    )
 }
 
 fn handle_clear_packet<ChainA: ChainHandle, ChainB: ChainHandle>(
@@ -367,38 +421,63 @@ fn handle_clear_packet<ChainA: ChainHandle, ChainB: ChainHandle>(
     clear_interval: u64,
     path: &Packet,
     height: Option<Height>,
+    breaker: &Mutex<CircuitBreaker>,
 ) -> Result<(), TaskError<RunError>> {
     link.a_to_b
         .schedule_packet_clearing(height)
         .map_err(handle_link_error_in_task)?;
 
-    handle_execute_schedule(link, path, Resubmit::from_clear_interval(clear_interval))
+    handle_execute_schedule(
+        link,
+        path,
+        Resubmit::from_clear_interval(clear_interval),
+        breaker,
+    )
 }
 
+/// Executes the operational data which is ready, unless the circuit breaker of the
+/// worker requires to back off after previous failures, and processes the pending txs.
 fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     link: &mut Link<ChainA, ChainB>,
-    _path: &Packet,
+    path: &Packet,
     resubmit: Resubmit,
+    breaker: &Mutex<CircuitBreaker>,
 ) -> Result<(), TaskError<RunError>> {
     link.a_to_b
         .refresh_schedule()
         .map_err(handle_link_error_in_task)?;
 
-    link.a_to_b.execute_schedule().map_err(|e| {
-        if e.is_expired_or_frozen_error() {
-            TaskError::Fatal(RunError::link(e))
-        } else {
-            error!("will retry: schedule execution encountered error: {}", e,);
-            TaskError::Ignore(RunError::link(e))
+    let has_scheduled_data = !link.a_to_b.src_operational_data.is_empty()
+        || !link.a_to_b.dst_operational_data.is_empty();
+
+    if has_scheduled_data && !breaker.lock().unwrap().allows(Instant::now()) {
+        trace!("backing off after repeated failures, not executing the schedule");
+    } else {
+        match link.a_to_b.execute_schedule() {
+            Ok(()) => {
+                if has_scheduled_data && breaker.lock().unwrap().record_success() {
+                    resumed(path);
+                }
+            }
+            Err(e) if e.is_expired_or_frozen_error() => {
+                return Err(TaskError::Fatal(RunError::link(e)));
+            }
+            Err(e) => {
+                error!("will retry: schedule execution encountered error: {}", e,);
+
+                record_failure(breaker, path);
+
+                return Err(TaskError::Ignore(RunError::link(e)));
+            }
         }
-    })?;
+    }
 
-    let summary = link.a_to_b.process_pending_txs(resubmit);
+    let summary = check_pending_txs(link.a_to_b.process_pending_txs(resubmit), path, breaker);
 
     if !summary.is_empty() {
         trace!("produced relay summary: {:?}", summary);
         telemetry!(packet_metrics(
-            _path,
+            path,
             &summary,
             &link.a_to_b.path_id.counterparty_channel_id,
             &link.a_to_b.path_id.counterparty_port_id
@@ -408,8 +487,55 @@ fn handle_execute_schedule<ChainA: ChainHandle, ChainB: ChainHandle>(
     Ok(())
 }
 
-#[cfg(feature = "telemetry")]
-use crate::link::RelaySummary;
+/// The summary of processing the pending txs, counting a failure to process
+/// them against the circuit breaker of the worker.
+fn check_pending_txs(
+    result: Result<RelaySummary, LinkError>,
+    path: &Packet,
+    breaker: &Mutex<CircuitBreaker>,
+) -> RelaySummary {
+    result.unwrap_or_else(|_| {
+        record_failure(breaker, path);
+        RelaySummary::empty()
+    })
+}
+
+/// Record a failure of the worker of the given path in its circuit breaker,
+/// reporting whether the worker got suspended.
+fn record_failure(breaker: &Mutex<CircuitBreaker>, path: &Packet) {
+    if breaker.lock().unwrap().record_failure(Instant::now()) {
+        warn!(
+            path = %path.short_name(),
+            "suspending the worker after too many consecutive failures, will keep trying periodically"
+        );
+
+        telemetry!(
+            packet_worker_suspended,
+            &path.src_chain_id,
+            &path.dst_chain_id,
+            &path.src_channel_id,
+            &path.src_port_id,
+            true
+        );
+    }
+}
+
+/// Report that the worker of the given path resumed after being suspended by its circuit breaker.
+fn resumed(path: &Packet) {
+    info!(
+        path = %path.short_name(),
+        "resuming the worker after it was suspended"
+    );
+
+    telemetry!(
+        packet_worker_suspended,
+        &path.src_chain_id,
+        &path.dst_chain_id,
+        &path.src_channel_id,
+        &path.src_port_id,
+        false
+    );
+}
 
 #[cfg(feature = "telemetry")]
 fn packet_metrics(
@@ -502,3 +628,36 @@ fn timeout_metrics(
         count as u64,
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use ibc_relayer_types::core::ics24_host::identifier::{ChainId, ChannelId, PortId};
+
+    use crate::worker::circuit_breaker::FAILURE_BUDGET;
+
+    #[test]
+    fn pending_tx_errors_suspend_the_worker() {
+        let path = Packet {
+            dst_chain_id: ChainId::from_string("ibc-1"),
+            src_chain_id: ChainId::from_string("ibc-0"),
+            src_channel_id: ChannelId::new(0),
+            src_port_id: PortId::transfer(),
+        };
+        let breaker = Mutex::new(CircuitBreaker::default());
+
+        let summary = check_pending_txs(Ok(RelaySummary::empty()), &path, &breaker);
+        assert!(summary.is_empty());
+        assert!(breaker.lock().unwrap().allows(Instant::now()));
+
+        for _ in 0..FAILURE_BUDGET {
+            assert!(!breaker.lock().unwrap().is_suspended());
+
+            let result = Err(LinkError::old_packet_clearing_failed());
+            assert!(check_pending_txs(result, &path, &breaker).is_empty());
+        }
+
+        assert!(breaker.lock().unwrap().is_suspended());
+    }
+}
@@ -0,0 +1,120 @@
+use core::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// Activity statistics of a worker, shared between the worker's
+/// tasks, which update them, and its [`WorkerHandle`](super::WorkerHandle),
+/// which exposes them in the supervisor's state dump.
+#[derive(Clone, Debug, Default)]
+pub struct WorkerStats(Arc<WorkerStatsInner>);
+
+#[derive(Debug, Default)]
+struct WorkerStatsInner {
+    /// Milliseconds since the UNIX epoch of the last activity, 0 if none
+    last_activity: AtomicU64,
+    retries: AtomicU64,
+    queued_operational_data: AtomicU64,
+    pending_txs: AtomicU64,
+    suspended: AtomicBool,
+}
+
+impl WorkerStats {
+    /// Record that the worker received a command or made progress.
+    pub fn record_activity(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+
+        self.0.last_activity.store(now, Ordering::Relaxed);
+    }
+
+    /// Record that the worker failed to perform some work and will try again.
+    pub fn record_retry(&self) {
+        self.0.retries.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Record the given attempt, as numbered by [`retry_with_index`], at some
+    /// work, which counts as a retry unless it is the first one.
+    ///
+    /// [`retry_with_index`]: crate::util::retry::retry_with_index
+    pub fn record_attempt(&self, index: u64) {
+        if index > 1 {
+            self.record_retry();
+        }
+    }
+
+    /// Set the number of operational data batches awaiting submission.
+    pub fn set_queued_operational_data(&self, count: usize) {
+        self.0
+            .queued_operational_data
+            .store(count as u64, Ordering::Relaxed);
+    }
+
+    /// Set the number of submitted transactions awaiting confirmation.
+    pub fn set_pending_txs(&self, count: usize) {
+        self.0.pending_txs.store(count as u64, Ordering::Relaxed);
+    }
+
+    /// Set whether the worker is suspended by its circuit breaker.
+    pub fn set_suspended(&self, suspended: bool) {
+        self.0.suspended.store(suspended, Ordering::Relaxed);
+    }
+
+    /// Whether the worker is suspended by its circuit breaker.
+    pub fn is_suspended(&self) -> bool {
+        self.0.suspended.load(Ordering::Relaxed)
+    }
+
+    pub fn snapshot(&self) -> WorkerStatsSnapshot {
+        let last_activity = match self.0.last_activity.load(Ordering::Relaxed) {
+            0 => None,
+            millis => Some(UNIX_EPOCH + Duration::from_millis(millis)),
+        };
+
+        WorkerStatsSnapshot {
+            last_activity: last_activity.map(|t| humantime::format_rfc3339_millis(t).to_string()),
+            retries: self.0.retries.load(Ordering::Relaxed),
+            queued_operational_data: self.0.queued_operational_data.load(Ordering::Relaxed),
+            pending_txs: self.0.pending_txs.load(Ordering::Relaxed),
+            suspended: self.is_suspended(),
+        }
+    }
+}
+
+/// The activity statistics of a worker at a given point in time.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerStatsSnapshot {
+    /// RFC 3339 timestamp of the last activity of the worker, if any
+    pub last_activity: Option<String>,
+    /// Number of times the worker had to retry its work after an error
+    pub retries: u64,
+    /// Number of operational data batches awaiting submission (packet workers only)
+    pub queued_operational_data: u64,
+    /// Number of submitted transactions awaiting confirmation (packet workers only)
+    #[serde(default)]
+    pub pending_txs: u64,
+    /// Whether the worker is suspended after too many consecutive failures (packet workers only)
+    #[serde(default)]
+    pub suspended: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WorkerStats;
+
+    #[test]
+    fn only_later_attempts_count_as_retries() {
+        let stats = WorkerStats::default();
+
+        stats.record_attempt(1);
+        assert_eq!(stats.snapshot().retries, 0);
+
+        stats.record_attempt(2);
+        stats.record_attempt(3);
+        stats.record_retry();
+        assert_eq!(stats.snapshot().retries, 3);
+    }
+}
@@ -8,7 +8,9 @@ use crate::{
     util::task::{spawn_background_task, Next, TaskError, TaskHandle},
 };
 
-pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
+use super::WorkerStats;
+
+pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain, stats: WorkerStats) -> TaskHandle {
     let span = error_span!("wallet", chain = %chain.id());
 
     spawn_background_task(span, Some(Duration::from_secs(5)), move || {
@@ -17,6 +19,7 @@ pub fn spawn_wallet_worker<Chain: ChainHandle>(chain: Chain) -> TaskHandle {
         })?;
 
         let balance = chain.query_balance(None, None).map_err(|e| {
+            stats.record_retry();
             TaskError::Ignore(format!("failed to query balance for the account: {e}"))
         })?;
 
@@ -7,7 +7,7 @@ use std::{
 use dashmap::{DashMap, DashSet};
 use opentelemetry::{
     global,
-    metrics::{Counter, ObservableGauge, UpDownCounter},
+    metrics::{Counter, Histogram, ObservableGauge, UpDownCounter},
     Context, KeyValue,
 };
 use opentelemetry_prometheus::PrometheusExporter;
@@ -68,6 +68,14 @@ const QUERY_TYPES: [&str; 26] = [
 // Current value is 7 days.
 const FEE_LIFETIME: Duration = Duration::from_secs(60 * 60 * 24 * 7);
 
+/// The local time in milliseconds since the UNIX epoch.
+fn now_millis() -> u64 {
+    match Time::now().duration_since(Time::unix_epoch()) {
+        Ok(ts) => ts.as_millis() as u64,
+        Err(_) => 0,
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
 pub enum WorkerType {
     Client,
@@ -118,15 +126,27 @@ pub struct TelemetryState {
     /// Number of cache hits for queries submitted by Hermes, per chain and query type
     queries_cache_hits: Counter<u64>,
 
+    /// Latency of the queries submitted by Hermes, per chain and query type. Milliseconds.
+    query_latency: ObservableGauge<u64>,
+
+    /// Number of queries submitted by Hermes which failed, per chain and query type
+    query_errors: Counter<u64>,
+
     /// Number of times Hermes reconnected to the websocket endpoint, per chain
     ws_reconnect: Counter<u64>,
 
     /// How many IBC events did Hermes receive via the WebSocket subscription, per chain
     ws_events: Counter<u64>,
 
+    /// How many blocks the event monitor is behind the latest height of the node, per chain
+    event_monitor_block_lag: ObservableGauge<u64>,
+
     /// Number of messages submitted to a specific chain
     messages_submitted: Counter<u64>,
 
+    /// Number of transaction submissions delayed by the rate limiter, per chain
+    tx_throttled: Counter<u64>,
+
     /// The balance of each wallet Hermes uses per chain
     wallet_balance: ObservableGauge<f64>,
 
@@ -156,6 +176,9 @@ pub struct TelemetryState {
     /// Number of SendPacket events received during the initial and periodic clearing
     cleared_send_packet_events: Counter<u64>,
 
+    /// Number of SendPacket events received out of sequence order
+    out_of_order_send_packet_events: Counter<u64>,
+
     /// Number of WriteAcknowledgement events received during the initial and periodic clearing
     cleared_acknowledgment_events: Counter<u64>,
 
@@ -165,20 +188,30 @@ pub struct TelemetryState {
     /// SendPacket events were relayed.
     backlog_oldest_sequence: ObservableGauge<u64>,
 
+    /// Distribution of the end-to-end latency of relaying packets on a specific path,
+    /// i.e. the difference between the moment when Hermes observed a SendPacket event
+    /// and when it observed the corresponding WriteAcknowledgement event. Milliseconds.
+    packet_relay_latency: Histogram<u64>,
+
     /// Record the timestamp related to `backlog_oldest_sequence`.
-    /// The timestamp is the time passed since since the unix epoch in seconds.
+    /// The timestamp is the time passed since the unix epoch in seconds,
+    /// while the backlogs record the timestamps in milliseconds.
     backlog_oldest_timestamp: ObservableGauge<u64>,
 
     /// Records the length of the backlog, i.e., how many packets are pending.
     backlog_size: ObservableGauge<u64>,
 
+    /// Whether the packet worker of a path is suspended after
+    /// too many consecutive transaction failures, 1 if so and 0 otherwise.
+    packet_worker_suspended: ObservableGauge<u64>,
+
     /// Stores the backlogs for all the paths the relayer is active on.
     /// This is a map of multiple inner backlogs, one inner backlog per path.
     ///
     /// Each inner backlog is represented as a [`DashMap`].
-    /// Each inner backlog captures the sequence numbers & timestamp for all SendPacket events
-    /// that the relayer observed, and for which there was no associated Acknowledgement or
-    /// Timeout event.
+    /// Each inner backlog captures the sequence numbers & timestamp (in milliseconds)
+    /// for all SendPacket events that the relayer observed, and for which there was
+    /// no associated Acknowledgement or Timeout event.
     backlogs: DashMap<PathIdentifier, DashMap<u64, u64>>,
 
     /// Total amount of fees received from ICS29 fees.
@@ -211,7 +244,9 @@ impl TelemetryState {
 
         self.ws_reconnect.add(&cx, 0, labels);
         self.ws_events.add(&cx, 0, labels);
+        self.event_monitor_block_lag.observe(&cx, 0, labels);
         self.messages_submitted.add(&cx, 0, labels);
+        self.tx_throttled.add(&cx, 0, labels);
 
         self.init_queries(chain_id);
     }
@@ -261,6 +296,7 @@ impl TelemetryState {
         self.send_packet_events.add(&cx, 0, labels);
         self.acknowledgement_events.add(&cx, 0, labels);
         self.timeout_events.add(&cx, 0, labels);
+        self.out_of_order_send_packet_events.add(&cx, 0, labels);
 
         if clear_packets {
             self.cleared_send_packet_events.add(&cx, 0, labels);
@@ -270,6 +306,7 @@ impl TelemetryState {
         self.backlog_oldest_sequence.observe(&cx, 0, labels);
         self.backlog_oldest_timestamp.observe(&cx, 0, labels);
         self.backlog_size.observe(&cx, 0, labels);
+        self.packet_worker_suspended.observe(&cx, 0, labels);
     }
 
     pub fn init_per_client(
@@ -304,6 +341,7 @@ impl TelemetryState {
             ];
 
             self.queries.add(&cx, 0, labels);
+            self.query_errors.add(&cx, 0, labels);
         }
 
         for query_type in QUERY_TYPES_CACHE {
@@ -470,6 +508,31 @@ impl TelemetryState {
         self.queries_cache_hits.add(&cx, 1, labels);
     }
 
+    /// Latency of a query emitted by the relayer, per chain and query type
+    pub fn query_latency(&self, chain_id: &ChainId, query_type: &'static str, latency: Duration) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("query_type", query_type),
+        ];
+
+        self.query_latency
+            .observe(&cx, latency.as_millis() as u64, labels);
+    }
+
+    /// Number of failed queries emitted by the relayer, per chain and query type
+    pub fn query_error(&self, chain_id: &ChainId, query_type: &'static str) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("query_type", query_type),
+        ];
+
+        self.query_errors.add(&cx, 1, labels);
+    }
+
     /// Number of time the relayer had to reconnect to the WebSocket endpoint, per chain
     pub fn ws_reconnect(&self, chain_id: &ChainId) {
         let cx = Context::current();
@@ -488,6 +551,38 @@ impl TelemetryState {
         self.ws_events.add(&cx, count, labels);
     }
 
+    /// How many blocks the event monitor is behind the latest height of the node, per chain
+    pub fn event_monitor_block_lag(&self, chain_id: &ChainId, lag: u64) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.event_monitor_block_lag.observe(&cx, lag, labels);
+    }
+
+    /// Whether the packet worker relaying from `chain` to `counterparty` on the given
+    /// channel is suspended after too many consecutive transaction failures
+    pub fn packet_worker_suspended(
+        &self,
+        chain: &ChainId,
+        counterparty: &ChainId,
+        channel: &ChannelId,
+        port: &PortId,
+        suspended: bool,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain.to_string()),
+            KeyValue::new("counterparty", counterparty.to_string()),
+            KeyValue::new("channel", channel.to_string()),
+            KeyValue::new("port", port.to_string()),
+        ];
+
+        self.packet_worker_suspended
+            .observe(&cx, suspended as u64, labels);
+    }
+
     /// How many messages Hermes submitted to the chain
     pub fn messages_submitted(&self, chain_id: &ChainId, count: u64) {
         let cx = Context::current();
@@ -497,6 +592,15 @@ impl TelemetryState {
         self.messages_submitted.add(&cx, count, labels);
     }
 
+    /// How many transaction submissions to the chain were delayed by the rate limiter
+    pub fn tx_throttled(&self, chain_id: &ChainId, count: u64) {
+        let cx = Context::current();
+
+        let labels = &[KeyValue::new("chain", chain_id.to_string())];
+
+        self.tx_throttled.add(&cx, count, labels);
+    }
+
     /// The balance in each wallet that Hermes is using, per account, denom and chain.
     /// The amount given is of unit: 10^6 * `denom`
     pub fn wallet_balance(&self, chain_id: &ChainId, account: &str, amount: f64, denom: &str) {
@@ -637,6 +741,25 @@ impl TelemetryState {
         self.timeout_events.add(&cx, 1, labels);
     }
 
+    pub fn out_of_order_send_packet_events(
+        &self,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+    ) {
+        let cx = Context::current();
+
+        let labels = &[
+            KeyValue::new("chain", chain_id.to_string()),
+            KeyValue::new("counterparty", counterparty_chain_id.to_string()),
+            KeyValue::new("channel", channel_id.to_string()),
+            KeyValue::new("port", port_id.to_string()),
+        ];
+
+        self.out_of_order_send_packet_events.add(&cx, 1, labels);
+    }
+
     pub fn cleared_send_packet_events(
         &self,
         _seq_nr: u64,
@@ -706,11 +829,7 @@ impl TelemetryState {
         ];
 
         // Retrieve local timestamp when this SendPacket event was recorded.
-        let now = Time::now();
-        let timestamp = match now.duration_since(Time::unix_epoch()) {
-            Ok(ts) => ts.as_secs(),
-            Err(_) => 0,
-        };
+        let timestamp = now_millis();
 
         // Update the backlog with the incoming data and retrieve the oldest values
         let (oldest_sn, oldest_ts, total) = if let Some(path_backlog) = self.backlogs.get(&path_uid)
@@ -755,7 +874,7 @@ impl TelemetryState {
         // Update metrics to reflect the new state of the backlog
         self.backlog_oldest_sequence.observe(&cx, oldest_sn, labels);
         self.backlog_oldest_timestamp
-            .observe(&cx, oldest_ts, labels);
+            .observe(&cx, oldest_ts / 1000, labels);
         self.backlog_size.observe(&cx, total, labels);
     }
 
@@ -793,7 +912,7 @@ impl TelemetryState {
                 if let Some(min_key) = path_backlog.iter().map(|v| *v.key()).min() {
                     if let Some(oldest) = path_backlog.get(&min_key) {
                         self.backlog_oldest_timestamp
-                            .observe(&cx, *oldest.value(), labels);
+                            .observe(&cx, *oldest.value() / 1000, labels);
                     } else {
                         self.backlog_oldest_timestamp.observe(&cx, 0, labels);
                     }
@@ -812,6 +931,55 @@ impl TelemetryState {
         }
     }
 
+    /// Records the end-to-end relay latency of the packet with the given sequence number,
+    /// and evicts it from the backlog.
+    /// This happens when the relayer observed a WriteAcknowledgement event for the packet,
+    /// which means that it was received on the counterparty chain.
+    ///
+    /// The packet was sent on the given channel and port of `chain_id`, and received on the
+    /// given counterparty channel and port of `counterparty_chain_id`. The latency is labeled
+    /// like the `*_confirmed` counters, with the sending end as the source.
+    #[allow(clippy::too_many_arguments)]
+    pub fn packet_received(
+        &self,
+        seq_nr: u64,
+        chain_id: &ChainId,
+        channel_id: &ChannelId,
+        port_id: &PortId,
+        counterparty_chain_id: &ChainId,
+        counterparty_channel_id: &ChannelId,
+        counterparty_port_id: &PortId,
+    ) {
+        let cx = Context::current();
+
+        let path_uid: PathIdentifier = PathIdentifier::new(
+            chain_id.to_string(),
+            channel_id.to_string(),
+            port_id.to_string(),
+        );
+
+        let observed_at = self
+            .backlogs
+            .get(&path_uid)
+            .and_then(|path_backlog| path_backlog.get(&seq_nr).map(|ts| *ts.value()));
+
+        if let Some(observed_at) = observed_at {
+            let labels = &[
+                KeyValue::new("src_chain", chain_id.to_string()),
+                KeyValue::new("dst_chain", counterparty_chain_id.to_string()),
+                KeyValue::new("src_channel", channel_id.to_string()),
+                KeyValue::new("dst_channel", counterparty_channel_id.to_string()),
+                KeyValue::new("src_port", port_id.to_string()),
+                KeyValue::new("dst_port", counterparty_port_id.to_string()),
+            ];
+
+            let latency = now_millis().saturating_sub(observed_at);
+            self.packet_relay_latency.record(&cx, latency, labels);
+        }
+
+        self.backlog_remove(seq_nr, chain_id, channel_id, port_id, counterparty_chain_id);
+    }
+
     /// Record the rewarded fee from ICS29 if the address is in the registered addresses
     /// list.
     pub fn fees_amount(&self, chain_id: &ChainId, receiver: &Signer, fee_amounts: Coin<String>) {
@@ -890,6 +1058,8 @@ impl AggregatorSelector for CustomAggregatorSelector {
             "backlog_oldest_sequence" => Some(Arc::new(last_value())),
             "backlog_oldest_timestamp" => Some(Arc::new(last_value())),
             "backlog_size" => Some(Arc::new(last_value())),
+            "event_monitor_block_lag" => Some(Arc::new(last_value())),
+            "packet_worker_suspended" => Some(Arc::new(last_value())),
             // Prometheus' supports only collector for histogram, sum, and last value aggregators.
             // https://docs.rs/opentelemetry-prometheus/0.10.0/src/opentelemetry_prometheus/lib.rs.html#411-418
             // TODO: Once quantile sketches are supported, replace histograms with that.
@@ -899,6 +1069,12 @@ impl AggregatorSelector for CustomAggregatorSelector {
             "tx_latency_confirmed" => Some(Arc::new(histogram(&[
                 1000.0, 5000.0, 9000.0, 13000.0, 17000.0, 20000.0,
             ]))),
+            "query_latency" => Some(Arc::new(histogram(&[
+                10.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 5000.0,
+            ]))),
+            "packet_relay_latency" => Some(Arc::new(histogram(&[
+                5000.0, 10000.0, 20000.0, 30000.0, 60000.0, 120000.0, 300000.0,
+            ]))),
             "ics29_period_fees" => Some(Arc::new(last_value())),
             _ => Some(Arc::new(sum())),
         }
@@ -965,6 +1141,17 @@ impl Default for TelemetryState {
                 .with_description("Number of cache hits for queries submitted by Hermes")
                 .init(),
 
+            query_latency: meter
+                .u64_observable_gauge("query_latency")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("Latency of the queries submitted by Hermes. Milliseconds.")
+                .init(),
+
+            query_errors: meter
+                .u64_counter("query_errors")
+                .with_description("Number of queries submitted by Hermes which failed")
+                .init(),
+
             ws_reconnect: meter
                 .u64_counter("ws_reconnect")
                 .with_description("Number of times Hermes reconnected to the websocket endpoint")
@@ -975,11 +1162,21 @@ impl Default for TelemetryState {
                 .with_description("How many IBC events did Hermes receive via the websocket subscription")
                 .init(),
 
+            event_monitor_block_lag: meter
+                .u64_observable_gauge("event_monitor_block_lag")
+                .with_description("How many blocks the event monitor is behind the latest height of the node")
+                .init(),
+
             messages_submitted: meter
                 .u64_counter("messages_submitted")
                 .with_description("Number of messages submitted to a specific chain")
                 .init(),
 
+            tx_throttled: meter
+                .u64_counter("tx_throttled")
+                .with_description("Number of transaction submissions to a specific chain delayed by the rate limiter")
+                .init(),
+
             wallet_balance: meter
                 .f64_observable_gauge("wallet_balance")
                 .with_description("The balance of each wallet Hermes uses per chain. Please note that when converting the balance to f64 a loss in precision might be introduced in the displayed value")
@@ -1000,6 +1197,11 @@ impl Default for TelemetryState {
                 .with_description("Number of TimeoutPacket events received")
                 .init(),
 
+            out_of_order_send_packet_events: meter
+                .u64_counter("out_of_order_send_packet_events")
+                .with_description("Number of SendPacket events received out of sequence order")
+                .init(),
+
             cleared_send_packet_events: meter
                 .u64_counter("cleared_send_packet_events")
                 .with_description("Number of SendPacket events received during the initial and periodic clearing")
@@ -1031,6 +1233,14 @@ impl Default for TelemetryState {
                 .time_to_idle(Duration::from_secs(30 * 60)) // Remove entries if they have been idle for 30 minutes
                 .build(),
 
+            packet_relay_latency: meter
+                .u64_histogram("packet_relay_latency")
+                .with_unit(Unit::new("milliseconds"))
+                .with_description("The end-to-end latency of relaying packets on a specific path, \
+                    i.e. the difference between the moment when Hermes observed a SendPacket event \
+                    and when it observed the corresponding WriteAcknowledgement event. Milliseconds.")
+                .init(),
+
             backlogs: DashMap::new(),
 
             backlog_oldest_sequence: meter
@@ -1049,6 +1259,11 @@ impl Default for TelemetryState {
                 .with_description("Total number of SendPacket events in the backlog")
                 .init(),
 
+            packet_worker_suspended: meter
+                .u64_observable_gauge("packet_worker_suspended")
+                .with_description("Whether the packet worker of a path is suspended after too many consecutive transaction failures")
+                .init(),
+
             fee_amounts: meter
                 .u64_counter("ics29_fee_amounts")
                 .with_description("Total amount received from ICS29 fees")
@@ -1065,3 +1280,85 @@ impl Default for TelemetryState {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn backlog_len(state: &TelemetryState, chain_id: &ChainId, channel_id: &ChannelId) -> usize {
+        let path_uid = PathIdentifier::new(
+            chain_id.to_string(),
+            channel_id.to_string(),
+            PortId::transfer().to_string(),
+        );
+
+        state
+            .backlogs
+            .get(&path_uid)
+            .map_or(0, |path_backlog| path_backlog.len())
+    }
+
+    #[test]
+    fn packet_received_evicts_the_packet_sent_on_the_counterparty() {
+        let state = TelemetryState::default();
+
+        let sender = ChainId::from_string("ibc-0");
+        let receiver = ChainId::from_string("ibc-1");
+        let sender_channel = ChannelId::new(0);
+        let receiver_channel = ChannelId::new(1);
+        let port = PortId::transfer();
+
+        state.backlog_insert(1, &sender, &sender_channel, &port, &receiver);
+        state.backlog_insert(2, &sender, &sender_channel, &port, &receiver);
+
+        // The backlog is keyed by the sending end of the path, in milliseconds
+        let path_uid = PathIdentifier::new(
+            sender.to_string(),
+            sender_channel.to_string(),
+            port.to_string(),
+        );
+        let observed_at = *state
+            .backlogs
+            .get(&path_uid)
+            .unwrap()
+            .get(&1)
+            .unwrap()
+            .value();
+        assert!(now_millis() - observed_at < 1000);
+
+        // The receiving end of the path does not identify the packet
+        state.packet_received(
+            1,
+            &receiver,
+            &receiver_channel,
+            &port,
+            &sender,
+            &sender_channel,
+            &port,
+        );
+        assert_eq!(backlog_len(&state, &sender, &sender_channel), 2);
+
+        state.packet_received(
+            1,
+            &sender,
+            &sender_channel,
+            &port,
+            &receiver,
+            &receiver_channel,
+            &port,
+        );
+        assert_eq!(backlog_len(&state, &sender, &sender_channel), 1);
+
+        // Packets which are not in the backlog are ignored
+        state.packet_received(
+            3,
+            &sender,
+            &sender_channel,
+            &port,
+            &receiver,
+            &receiver_channel,
+            &port,
+        );
+        assert_eq!(backlog_len(&state, &sender, &sender_channel), 1);
+    }
+}
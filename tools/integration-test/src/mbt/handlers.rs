@@ -1,5 +1,6 @@
 use ibc_relayer::util::task::TaskHandle;
 use ibc_relayer::worker::client::spawn_refresh_client;
+use ibc_relayer::worker::WorkerStats;
 
 use ibc_test_framework::bootstrap::binary::chain::bootstrap_foreign_client_pair;
 use ibc_test_framework::bootstrap::binary::connection::bootstrap_connection;
@@ -23,11 +24,17 @@ pub fn setup_chains<ChainA: ChainHandle, ChainB: ChainHandle>(
     chains: &ConnectedChains<ChainA, ChainB>,
 ) -> Result<(), Error> {
     {
-        let _refresh_task_a = spawn_refresh_client(chains.foreign_clients.client_b_to_a.clone())
-            .ok_or_else(|| eyre!("expect refresh task spawned"))?;
-
-        let _refresh_task_b = spawn_refresh_client(chains.foreign_clients.client_a_to_b.clone())
-            .ok_or_else(|| eyre!("expect refresh task spawned"))?;
+        let _refresh_task_a = spawn_refresh_client(
+            chains.foreign_clients.client_b_to_a.clone(),
+            WorkerStats::default(),
+        )
+        .ok_or_else(|| eyre!("expect refresh task spawned"))?;
+
+        let _refresh_task_b = spawn_refresh_client(
+            chains.foreign_clients.client_a_to_b.clone(),
+            WorkerStats::default(),
+        )
+        .ok_or_else(|| eyre!("expect refresh task spawned"))?;
 
         bootstrap_connection(&chains.foreign_clients, Default::default())?;
     };
@@ -73,12 +80,12 @@ pub fn create_channel<ChainA: ChainHandle, ChainB: ChainHandle>(
         bootstrap_foreign_client_pair(chain_handle_a, chain_handle_b, Default::default())?;
 
     *refresh_task_a = Some(
-        spawn_refresh_client(clients2.client_b_to_a.clone())
+        spawn_refresh_client(clients2.client_b_to_a.clone(), WorkerStats::default())
             .ok_or_else(|| eyre!("expect refresh task spawned"))?,
     );
 
     *refresh_task_b = Some(
-        spawn_refresh_client(clients2.client_a_to_b.clone())
+        spawn_refresh_client(clients2.client_a_to_b.clone(), WorkerStats::default())
             .ok_or_else(|| eyre!("expect refresh task spawned"))?,
     );
 
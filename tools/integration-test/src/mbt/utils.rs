@@ -2,8 +2,8 @@ use std::thread::sleep;
 use std::time::Duration;
 
 use ibc_relayer::chain::requests::{
-    QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest, QueryUnreceivedAcksRequest,
-    QueryUnreceivedPacketsRequest,
+    QueryHeight, QueryPacketAcknowledgementsRequest, QueryPacketCommitmentsRequest,
+    QueryUnreceivedAcksRequest, QueryUnreceivedPacketsRequest,
 };
 use ibc_relayer_types::core::ics04_channel::packet::Sequence;
 use ibc_test_framework::ibc::denom::Denom;
@@ -99,6 +99,7 @@ pub fn get_committed_packets_at_src<ChainA: ChainHandle, ChainB: ChainHandle>(
         port_id: port_id_a.clone(),
         channel_id: channel_id_a.clone(),
         pagination: None,
+        query_height: QueryHeight::Latest,
     };
     let (sequences, _) = chain.query_packet_commitments(request)?;
     Ok(sequences)
@@ -128,6 +129,7 @@ pub fn get_acknowledged_packets_at_dst<ChainA: ChainHandle, ChainB: ChainHandle>
         port_id: port_id_a.clone(),
         channel_id: channel_id_a.clone(),
         pagination: None,
+        query_height: QueryHeight::Latest,
         packet_commitment_sequences: Vec::new(),
     };
     Ok(chain.query_packet_acknowledgements(request)?.0)
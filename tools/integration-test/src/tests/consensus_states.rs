@@ -2,7 +2,9 @@ use std::time::Duration;
 
 use ibc_relayer::chain::{
     cosmos::query::consensus_state::query_consensus_states,
-    requests::{PageRequest, QueryConsensusStateHeightsRequest, QueryConsensusStatesRequest},
+    requests::{
+        PageRequest, QueryConsensusStateHeightsRequest, QueryConsensusStatesRequest, QueryHeight,
+    },
 };
 
 use ibc_test_framework::prelude::*;
@@ -42,6 +44,7 @@ impl BinaryChainTest for ConsensusStateHeights {
                 .query_consensus_state_heights(QueryConsensusStateHeightsRequest {
                     client_id: (*chains.client_id_b().value()).clone(),
                     pagination: Some(PageRequest::all()),
+                    query_height: QueryHeight::Latest,
                 })?;
 
         assert_eq(
@@ -71,6 +74,7 @@ impl BinaryChainTest for ConsensusStateHeights {
                     QueryConsensusStatesRequest {
                         client_id: (*chains.client_id_b().value()).clone(),
                         pagination: Some(PageRequest::all()),
+                        query_height: QueryHeight::Latest,
                     },
                 ))?;
 
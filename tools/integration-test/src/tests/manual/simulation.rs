@@ -89,8 +89,8 @@ fn tx_raw_ft_transfer<SrcChain: ChainHandle, DstChain: ChainHandle>(
         amount: amount.into(),
         denom: denom.value().to_string(),
         receiver: Some(recipient.value().0.clone()),
-        timeout_height_offset,
-        timeout_duration,
+        timeout_height_offset: Some(timeout_height_offset),
+        timeout_duration: Some(timeout_duration),
         number_msgs: number_messages,
         memo,
     };
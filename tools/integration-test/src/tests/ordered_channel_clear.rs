@@ -217,8 +217,8 @@ impl BinaryChannelTest for OrderedChannelClearEqualCLITest {
             amount: random_u64_range(1000, 5000).into(),
             denom: chains.node_a.denom().value().to_string(),
             receiver: Some(chains.node_b.wallets().user1().address().value().0.clone()),
-            timeout_height_offset: 1000,
-            timeout_duration: Duration::from_secs(0),
+            timeout_height_offset: Some(1000),
+            timeout_duration: Some(Duration::from_secs(0)),
             number_msgs: num_msgs,
             memo: None,
         };
@@ -28,9 +28,10 @@ use ibc_proto::ibc::apps::fee::v1::{
 };
 use ibc_relayer::account::Balance;
 use ibc_relayer::chain::client::ClientSettings;
-use ibc_relayer::chain::endpoint::{ChainStatus, HealthCheck};
+use ibc_relayer::chain::endpoint::{ChainStakingParams, ChainStatus, HealthCheck};
 use ibc_relayer::chain::handle::{ChainHandle, ChainRequest, Subscription};
 use ibc_relayer::chain::requests::*;
+use ibc_relayer::chain::rpc_stats::QueryStatsSummary;
 use ibc_relayer::chain::tracking::TrackedMsgs;
 use ibc_relayer::client_state::{AnyClientState, IdentifiedAnyClientState};
 use ibc_relayer::config::ChainConfig;
@@ -42,6 +43,7 @@ use ibc_relayer::event::IbcEventWithHeight;
 use ibc_relayer::keyring::AnySigningKeyPair;
 use ibc_relayer::light_client::AnyHeader;
 use ibc_relayer::misbehaviour::MisbehaviourEvidence;
+use ibc_relayer_types::applications::ics29_fee::packet_fee::IdentifiedPacketFees;
 use ibc_relayer_types::applications::ics31_icq::response::CrossChainQueryResponse;
 use ibc_relayer_types::core::ics02_client::events::UpdateClient;
 use ibc_relayer_types::core::ics03_connection::connection::ConnectionEnd;
@@ -87,10 +89,18 @@ where
         self.value().health_check()
     }
 
+    fn is_syncing(&self) -> Result<bool, Error> {
+        self.value().is_syncing()
+    }
+
     fn subscribe(&self) -> Result<Subscription, Error> {
         self.value().subscribe()
     }
 
+    fn subscribe_if_running(&self) -> Result<Option<Subscription>, Error> {
+        self.value().subscribe_if_running()
+    }
+
     fn send_messages_and_wait_commit(
         &self,
         tracked_msgs: TrackedMsgs,
@@ -129,6 +139,10 @@ where
         self.value().query_application_status()
     }
 
+    fn query_staking_params(&self) -> Result<ChainStakingParams, Error> {
+        self.value().query_staking_params()
+    }
+
     fn query_latest_height(&self) -> Result<Height, Error> {
         self.value().query_latest_height()
     }
@@ -384,6 +398,10 @@ where
         self.value().query_packet_events(request)
     }
 
+    fn query_block_events(&self, height: Height) -> Result<Vec<IbcEventWithHeight>, Error> {
+        self.value().query_block_events(height)
+    }
+
     fn query_host_consensus_state(
         &self,
         request: QueryHostConsensusStateRequest,
@@ -403,6 +421,10 @@ where
         self.value().query_all_balances(key_name)
     }
 
+    fn query_address_balances(&self, address: String) -> Result<Vec<Balance>, Error> {
+        self.value().query_address_balances(address)
+    }
+
     fn maybe_register_counterparty_payee(
         &self,
         channel_id: ChannelId,
@@ -417,6 +439,10 @@ where
         self.value().query_denom_trace(hash)
     }
 
+    fn query_rpc_stats(&self) -> Result<Vec<QueryStatsSummary>, Error> {
+        self.value().query_rpc_stats()
+    }
+
     fn cross_chain_query(
         &self,
         request: Vec<CrossChainQueryRequest>,
@@ -430,4 +456,20 @@ where
     ) -> Result<QueryIncentivizedPacketResponse, Error> {
         self.value().query_incentivized_packet(request)
     }
+
+    fn query_incentivized_packets(
+        &self,
+        channel_id: ChannelId,
+        port_id: PortId,
+    ) -> Result<Vec<IdentifiedPacketFees>, Error> {
+        self.value().query_incentivized_packets(channel_id, port_id)
+    }
+
+    fn query_counterparty_payee(
+        &self,
+        channel_id: ChannelId,
+        address: Signer,
+    ) -> Result<Option<String>, Error> {
+        self.value().query_counterparty_payee(channel_id, address)
+    }
 }
@@ -35,6 +35,7 @@ pub fn gas_config_for_test() -> GasConfig {
         gas_price,
         max_fee,
         fee_granter,
+        gas_multiplier_tuner: None,
     }
 }
 
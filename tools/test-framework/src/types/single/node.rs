@@ -142,8 +142,12 @@ impl FullNode {
             grpc_addr: Url::from_str(&self.chain_driver.grpc_address())?,
             rpc_timeout: ibc_relayer::config::default::rpc_timeout(),
             batch_delay: ibc_relayer::config::default::batch_delay(),
+            event_rpc_fallback: ibc_relayer::config::default::event_rpc_fallback(),
+            event_source: Default::default(),
+            max_block_lag: None,
             trusted_node: false,
             genesis_restart: None,
+            evidence_dir: None,
             account_prefix: self.chain_driver.account_prefix.clone(),
             key_name: self.wallets.relayer.id.0.clone(),
             key_store_type: Store::Test,
@@ -156,7 +160,11 @@ impl FullNode {
             fee_granter: None,
             max_msg_num: Default::default(),
             max_tx_size: Default::default(),
+            tx_rate_limit: None,
+            gas_multiplier_tuning: None,
             max_grpc_decoding_size: config::default::max_grpc_decoding_size(),
+            max_ws_message_size: config::default::max_ws_message_size(),
+            max_ws_frame_size: config::default::max_ws_frame_size(),
             max_block_time: Duration::from_secs(30),
             clock_drift: Duration::from_secs(5),
             trusting_period: Some(Duration::from_secs(14 * 24 * 3600)),
@@ -168,6 +176,7 @@ impl FullNode {
             memo_prefix: Default::default(),
             proof_specs: Default::default(),
             extension_options: Default::default(),
+            denom_metadata: Default::default(),
             sequential_batch_tx: false,
         })
     }